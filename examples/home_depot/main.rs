@@ -14,17 +14,15 @@ macro_rules! path {
 
 fn main() -> Result<(), ClientError> {
     let mut client = Client::connect("localhost", 1984, "admin", "admin")?;
-    let mut catalog = File::open(path!("catalog.xml"))?;
-    let mut warehouse = File::open(path!("warehouse.xml"))?;
     let mut xquery = File::open(path!("hornbach.xq"))?;
 
     let info = client.create("hornbach")?.without_input()?;
     assert!(info.starts_with("Database 'hornbach' created"));
 
-    let info = client.add("catalog", &mut catalog)?;
+    let info = client.add("catalog", path!("catalog.xml"))?;
     assert!(info.starts_with("Resource(s) added"));
 
-    let info = client.add("warehouse", &mut warehouse)?;
+    let info = client.add("warehouse", path!("warehouse.xml"))?;
     assert!(info.starts_with("Resource(s) added"));
 
     let query = client.query(&mut xquery)?.without_info()?;
@@ -0,0 +1,289 @@
+use crate::connection::Authenticated;
+#[cfg(feature = "tls")]
+use crate::TlsStream;
+use crate::{Client, Connection, DatabaseStream, Result};
+use std::fmt::{Debug, Formatter};
+use std::io;
+use std::io::ErrorKind;
+use std::net::{TcpStream, ToSocketAddrs};
+use std::time::Duration;
+
+/// Time after which [`Client::connect`] gives up on connecting to a resolved address.
+///
+/// [`Client::connect`]: crate::client::Client<TcpStream>::connect
+pub(crate) const DEFAULT_CONNECT_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Connects to the first address `host` resolves to that accepts the connection within the `timeout`. IPv6 literals
+/// are accepted both with and without the enclosing brackets, e.g. `[::1]` and `::1`.
+pub(crate) fn connect_tcp(host: &str, port: u16, timeout: Duration) -> Result<TcpStream> {
+    let host = host
+        .strip_prefix('[')
+        .and_then(|host| host.strip_suffix(']'))
+        .unwrap_or(host);
+
+    connect_any((host, port), timeout)
+}
+
+/// Connects to the first of the `addresses` that accepts the connection within the `timeout`.
+pub(crate) fn connect_any(addresses: impl ToSocketAddrs, timeout: Duration) -> Result<TcpStream> {
+    let mut last_error = None;
+
+    for address in addresses.to_socket_addrs()? {
+        match TcpStream::connect_timeout(&address, timeout) {
+            Ok(stream) => return Ok(stream),
+            Err(error) => last_error = Some(error),
+        }
+    }
+
+    Err(last_error
+        .unwrap_or_else(|| io::Error::new(ErrorKind::InvalidInput, "could not resolve to any address"))
+        .into())
+}
+
+/// Gathers all the options for connecting to BaseX server in one place.
+///
+/// Connects to `localhost:1984` by default, giving up after 30 seconds and never timing out the reads. The credentials
+/// have to be set.
+///
+/// # Example
+///
+/// ```
+/// # use basex::{ClientBuilder, ClientError};
+/// # use std::time::Duration;
+/// # fn main() -> Result<(), ClientError> {
+/// let client = ClientBuilder::new()
+///     .host("localhost")
+///     .port(1984)
+///     .credentials("admin", "admin")
+///     .connect_timeout(Duration::from_secs(5))
+///     .read_timeout(Some(Duration::from_secs(10)))
+///     .connect()?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone)]
+pub struct ClientBuilder {
+    host: String,
+    port: u16,
+    user: String,
+    password: String,
+    connect_timeout: Duration,
+    read_timeout: Option<Duration>,
+}
+
+impl Debug for ClientBuilder {
+    /// Leaves out the password, so that it does not end up in logs.
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ClientBuilder")
+            .field("host", &self.host)
+            .field("port", &self.port)
+            .field("user", &self.user)
+            .field("password", &format_args!("<redacted>"))
+            .field("connect_timeout", &self.connect_timeout)
+            .field("read_timeout", &self.read_timeout)
+            .finish()
+    }
+}
+
+impl Default for ClientBuilder {
+    fn default() -> Self {
+        Self {
+            host: "localhost".to_owned(),
+            port: 1984,
+            user: String::new(),
+            password: String::new(),
+            connect_timeout: DEFAULT_CONNECT_TIMEOUT,
+            read_timeout: None,
+        }
+    }
+}
+
+impl ClientBuilder {
+    /// Creates a builder with the default options.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the `host` name or address of the server.
+    pub fn host(mut self, host: &str) -> Self {
+        self.host = host.to_owned();
+        self
+    }
+
+    /// Sets the `port` the server listens on.
+    pub fn port(mut self, port: u16) -> Self {
+        self.port = port;
+        self
+    }
+
+    /// Sets the `user` and `password` to authenticate with.
+    pub fn credentials(mut self, user: &str, password: &str) -> Self {
+        self.user = user.to_owned();
+        self.password = password.to_owned();
+        self
+    }
+
+    /// Sets the `timeout` after which connecting to each resolved address is given up.
+    pub fn connect_timeout(mut self, timeout: Duration) -> Self {
+        self.connect_timeout = timeout;
+        self
+    }
+
+    /// Sets the `timeout` of reads from the server. Passing `None` blocks the reads indefinitely.
+    pub fn read_timeout(mut self, timeout: Option<Duration>) -> Self {
+        self.read_timeout = timeout;
+        self
+    }
+
+    /// Connects and authenticates to the server using TCP stream.
+    pub fn connect(self) -> Result<Client<TcpStream>> {
+        let read_timeout = self.read_timeout;
+
+        self.connect_with(move |host, port, timeout| {
+            let stream = connect_tcp(host, port, timeout)?;
+            stream.set_read_timeout(read_timeout)?;
+            Ok(stream)
+        })
+    }
+
+    /// Connects to the server through a TLS tunnel, verifying the certificate of `server_name`, and authenticates.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use basex::{ClientBuilder, ClientError};
+    /// # fn main() -> Result<(), ClientError> {
+    /// let client = ClientBuilder::new()
+    ///     .host("basex.example.com")
+    ///     .port(1985)
+    ///     .credentials("admin", "admin")
+    ///     .connect_tls("basex.example.com")?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg(feature = "tls")]
+    pub fn connect_tls(self, server_name: &str) -> Result<Client<TlsStream>> {
+        let read_timeout = self.read_timeout;
+        let server_name = server_name.to_owned();
+
+        self.connect_with(move |host, port, timeout| {
+            let stream = connect_tcp(host, port, timeout)?;
+            stream.set_read_timeout(read_timeout)?;
+            TlsStream::connect(stream, &server_name)
+        })
+    }
+
+    /// Opens a stream using `open` with the host, port and connect timeout, and authenticates over it. The client
+    /// keeps `open` around for reconnecting.
+    pub(crate) fn connect_with<T, F>(self, open: F) -> Result<Client<T>>
+    where
+        T: DatabaseStream,
+        F: Fn(&str, u16, Duration) -> Result<T> + Send + Sync + 'static,
+    {
+        let Self {
+            host,
+            port,
+            user,
+            password,
+            connect_timeout,
+            ..
+        } = self;
+
+        Client::dialed(move || -> Result<Connection<T, Authenticated>> {
+            let stream = open(&host, port, connect_timeout)?;
+            Connection::new(stream).authenticate(&user, &password)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::{spawn_server, MockStream};
+    use crate::ClientError;
+    use std::sync::{Arc, Mutex};
+
+    #[test]
+    fn test_builds_authenticated_client_with_options() {
+        let dialed = Arc::new(Mutex::new(vec![]));
+        let dialed_by_factory = Arc::clone(&dialed);
+
+        let client = ClientBuilder::new()
+            .host("basex.example.com")
+            .port(1985)
+            .credentials("admin", "admin")
+            .connect_timeout(Duration::from_secs(5))
+            .connect_with(move |host, port, timeout| {
+                dialed_by_factory.lock().unwrap().push((host.to_owned(), port, timeout));
+                Ok(MockStream::new("BaseX:19501915960728\0".to_owned()))
+            })
+            .unwrap();
+
+        assert_eq!(
+            vec![("basex.example.com".to_owned(), 1985, Duration::from_secs(5))],
+            *dialed.lock().unwrap()
+        );
+        assert_eq!(
+            "admin\0af13b20af0e0b0e3517a406c42622d3d\0",
+            client.into_inner().into_inner().to_string()
+        );
+    }
+
+    #[test]
+    fn test_formats_as_debug_without_password() {
+        let builder = ClientBuilder::new().credentials("admin", "pink_index_69");
+
+        let debug = format!("{:?}", builder);
+
+        assert!(debug.contains("admin"));
+        assert!(!debug.contains("pink_index_69"));
+    }
+
+    #[test]
+    fn test_client_formats_as_debug_without_password() {
+        let client = ClientBuilder::new()
+            .credentials("admin", "pink_index_69")
+            .connect_with(|_, _, _| Ok(MockStream::new("BaseX:19501915960728\0".to_owned())))
+            .unwrap();
+
+        assert!(!format!("{:?}", client).contains("pink_index_69"));
+    }
+
+    #[test]
+    fn test_defaults_to_local_server() {
+        let builder = ClientBuilder::new();
+
+        assert_eq!("localhost", builder.host);
+        assert_eq!(1984, builder.port);
+        assert_eq!(DEFAULT_CONNECT_TIMEOUT, builder.connect_timeout);
+        assert_eq!(None, builder.read_timeout);
+    }
+
+    #[test]
+    fn test_building_fails_when_factory_fails() {
+        let actual_error = ClientBuilder::new()
+            .connect_with(|_, _, _| -> Result<MockStream> { Err(io::Error::from(ErrorKind::ConnectionRefused).into()) })
+            .expect_err("Operation must fail");
+
+        assert!(matches!(actual_error, ClientError::Io(e) if e.kind() == ErrorKind::ConnectionRefused));
+    }
+
+    #[test]
+    fn test_connecting_sets_read_timeout() {
+        let (address, server) = spawn_server();
+
+        let client = ClientBuilder::new()
+            .host("127.0.0.1")
+            .port(address.port())
+            .credentials("admin", "admin")
+            .read_timeout(Some(Duration::from_millis(100)))
+            .connect()
+            .unwrap();
+        let _ = server.join().unwrap();
+
+        assert_eq!(
+            Some(Duration::from_millis(100)),
+            client.into_inner().into_inner().read_timeout().unwrap()
+        );
+    }
+}
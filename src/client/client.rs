@@ -1,14 +1,104 @@
-use crate::client::Response;
+use crate::client::builder::{connect_any, connect_tcp, DEFAULT_CONNECT_TIMEOUT};
+use crate::client::database::{elapsed_from_info, names_from_table, validate_name};
+use crate::client::option::validate_value;
+use crate::client::{
+    ClientBuilder, Command, CommandOutcome, CreateOptions, CreateOutcome, DatabaseInfo, DatabaseStats, DropTarget,
+    Observer, QueryObserver, RawServerInfo, ResourceEntry, Response, ServerInfo, ServerOption, Version,
+};
 use crate::connection::Authenticated;
-use crate::query::{WithInfo, WithoutInfo};
+use crate::query::{close_query, escape_xquery_string, WithInfo, WithoutInfo};
 use crate::resource::AsResource;
-use crate::{Connection, DatabaseStream, Query, Result};
+#[cfg(feature = "tls")]
+use crate::TlsStream;
+use crate::{ClientError, Connection, DatabaseStream, Query, Result, TeeStream};
 use std::borrow::{Borrow, BorrowMut};
+use std::collections::BTreeSet;
+use std::fmt::{Debug, Formatter};
+use std::io;
+use std::io::ErrorKind;
+use std::io::Write;
 use std::marker::PhantomData;
-use std::net::TcpStream;
+use std::net::{SocketAddr, TcpStream, ToSocketAddrs};
+use std::ops::{Deref, DerefMut};
+#[cfg(unix)]
+use std::os::unix::net::UnixStream;
+#[cfg(unix)]
+use std::path::Path;
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError};
+use std::time::Duration;
+
+/// Dials the server the client was originally connected to and authenticates a fresh connection.
+struct Dialer<T: DatabaseStream>(Arc<dyn Fn() -> Result<Connection<T, Authenticated>> + Send + Sync>);
+
+impl<T: DatabaseStream> Dialer<T> {
+    fn dial(&self) -> Result<Connection<T, Authenticated>> {
+        (self.0)()
+    }
+}
+
+impl<T: DatabaseStream> Clone for Dialer<T> {
+    fn clone(&self) -> Self {
+        Self(Arc::clone(&self.0))
+    }
+}
+
+impl<T: DatabaseStream> Debug for Dialer<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str("Dialer")
+    }
+}
+
+/// Identifiers of the queries opened on the server and not closed yet, shared by the client and its clones.
+#[derive(Debug, Clone, Default)]
+struct LiveQueries(Arc<Mutex<BTreeSet<String>>>);
+
+impl LiveQueries {
+    fn ids(&self) -> MutexGuard<'_, BTreeSet<String>> {
+        self.0.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+/// Tells whether the error means the connection to the server is no longer usable.
+fn is_broken_connection(error: &io::Error) -> bool {
+    matches!(
+        error.kind(),
+        ErrorKind::BrokenPipe
+            | ErrorKind::ConnectionReset
+            | ErrorKind::ConnectionAborted
+            | ErrorKind::NotConnected
+            | ErrorKind::UnexpectedEof
+    )
+}
+
+/// Tells whether the `command` only reads the server state that does not depend on the session, like the opened
+/// database or the options, so that running it again on a new session returns the same.
+fn is_read_only(command: &str) -> bool {
+    let command = command.to_ascii_uppercase();
+    let words: Vec<&str> = command.split_whitespace().collect();
+
+    matches!(words.as_slice(), ["LIST", ..] | ["INFO"] | ["SHOW", "BACKUPS"])
+}
+
+/// Generates the updating query replacing `count` documents in the database `$db`, each at `$path{i}` with the XML
+/// parsed from `$input{i}`.
+fn replace_many_query(count: usize) -> String {
+    let mut query = "declare variable $db external;\n".to_owned();
+    for i in 0..count {
+        query.push_str(&format!(
+            "declare variable $path{i} external;\ndeclare variable $input{i} external;\n",
+            i = i
+        ));
+    }
+
+    let replaces: Vec<String> = (0..count)
+        .map(|i| format!("db:replace($db, $path{i}, parse-xml($input{i}))", i = i))
+        .collect();
+    query.push_str(&replaces.join(",\n"));
+    query
+}
 
 /// Represents database command code in the [standard mode](https://docs.basex.org/wiki/Standard_Mode).
-enum Command {
+enum CommandCode {
     Query = 0,
     Create = 8,
     Add = 9,
@@ -36,9 +126,26 @@ where
     }
 
     /// Sends the input to the command and executes it, returning its response as a string.
+    ///
+    /// The command is already on its way to the server, so when the `input` fails to be read, it is ended by an
+    /// unfinished tag, which the XML parser rejects, and the read error is returned as [`ClientError::Io`] once the
+    /// server has answered. The connection is then ready for the next command.
+    ///
+    /// [`ClientError::Io`]: crate::ClientError::Io
     pub fn with_input<'b, R: AsResource<'b>>(self, input: R) -> Result<String> {
-        self.connection.send_arg(&mut input.into_read())?;
-        self.connection.get_response()
+        let written = self.connection.send_arg_with(
+            |out| {
+                io::copy(&mut input.try_into_read()?, out)?;
+                Ok(())
+            },
+            b"<",
+        )?;
+        let response = self.connection.get_response();
+
+        match written {
+            Ok(()) => response,
+            Err(error) => Err(error.into()),
+        }
     }
 
     /// Omits the input from command and executes it, returning its response as a string.
@@ -46,6 +153,71 @@ where
         self.connection.skip_arg()?;
         self.connection.get_response()
     }
+
+    /// Sends the input to the command and executes it, returning its response parsed as [`CreateOutcome`].
+    ///
+    /// [`CreateOutcome`]: crate::client::CreateOutcome
+    pub fn with_input_parsed<'b, R: AsResource<'b>>(self, input: R) -> Result<CreateOutcome> {
+        CreateOutcome::from_info(self.with_input(input)?)
+    }
+
+    /// Omits the input from command and executes it, returning its response parsed as [`CreateOutcome`].
+    ///
+    /// [`CreateOutcome`]: crate::client::CreateOutcome
+    pub fn without_input_parsed(self) -> Result<CreateOutcome> {
+        CreateOutcome::from_info(self.without_input()?)
+    }
+}
+
+/// Database opened by [`Client::open_scoped`]. Dereferences to the [`Client`] and closes the database on drop.
+///
+/// Dropping cannot return errors, so failing to close the database on drop is ignored and the database may stay open.
+/// Call [`close`] to find out whether closing has succeeded.
+///
+/// [`Client::open_scoped`]: crate::Client::open_scoped
+/// [`Client`]: crate::Client
+/// [`close`]: self::OpenDbGuard::close
+#[derive(Debug)]
+pub struct OpenDbGuard<'a, T>
+where
+    T: DatabaseStream,
+{
+    client: &'a mut Client<T>,
+    open: bool,
+}
+
+impl<'a, T> OpenDbGuard<'a, T>
+where
+    T: DatabaseStream,
+{
+    /// Closes the database, returning the server info message.
+    pub fn close(mut self) -> Result<String> {
+        self.open = false;
+        let (_, info) = self.client.command(&Command::Close.to_string())?;
+        Ok(info)
+    }
+}
+
+impl<T: DatabaseStream> Deref for OpenDbGuard<'_, T> {
+    type Target = Client<T>;
+
+    fn deref(&self) -> &Self::Target {
+        self.client
+    }
+}
+
+impl<T: DatabaseStream> DerefMut for OpenDbGuard<'_, T> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        self.client
+    }
+}
+
+impl<T: DatabaseStream> Drop for OpenDbGuard<'_, T> {
+    fn drop(&mut self) {
+        if self.open {
+            let _ = self.client.command(&Command::Close.to_string());
+        }
+    }
 }
 
 /// Represents an interface to communicate with the BaseX server. Its main purpose is to send database
@@ -83,6 +255,12 @@ where
     T: DatabaseStream,
 {
     connection: Connection<T, Authenticated>,
+    dialer: Option<Dialer<T>>,
+    retries: u32,
+    observer: Observer,
+    queries: LiveQueries,
+    readonly: Option<String>,
+    version: Option<Version>,
 }
 
 impl Client<TcpStream> {
@@ -98,13 +276,248 @@ impl Client<TcpStream> {
     /// # }
     /// ```
     pub fn connect(host: &str, port: u16, user: &str, password: &str) -> Result<Client<TcpStream>> {
-        let stream = TcpStream::connect(&format!("{}:{}", host, port))?;
+        Self::connect_with_timeout(host, port, user, password, DEFAULT_CONNECT_TIMEOUT)
+    }
+
+    /// Connects and authenticates to BaseX server using TCP stream, giving up on each resolved address once the
+    /// `timeout` elapses.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use basex::{Client, ClientError};
+    /// # use std::time::Duration;
+    /// # fn main() -> Result<(), ClientError> {
+    /// let client = Client::connect_with_timeout("localhost", 1984, "admin", "admin", Duration::from_secs(5))?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn connect_with_timeout(
+        host: &str,
+        port: u16,
+        user: &str,
+        password: &str,
+        timeout: Duration,
+    ) -> Result<Client<TcpStream>> {
+        ClientBuilder::new()
+            .host(host)
+            .port(port)
+            .credentials(user, password)
+            .connect_timeout(timeout)
+            .connect()
+    }
+
+    /// Connects and authenticates to BaseX server using TCP stream, trying each of the addresses `address` resolves to.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use basex::{Client, ClientError};
+    /// # fn main() -> Result<(), ClientError> {
+    /// let client = Client::connect_addr("[::1]:1984", "admin", "admin")?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn connect_addr(address: impl ToSocketAddrs, user: &str, password: &str) -> Result<Client<TcpStream>> {
+        let addresses: Vec<SocketAddr> = address.to_socket_addrs()?.collect();
+
+        ClientBuilder::new()
+            .credentials(user, password)
+            .connect_with(move |_, _, timeout| connect_any(&addresses[..], timeout))
+    }
+
+    /// Connects and authenticates to BaseX server using TCP stream, mirroring all the bytes sent and received into the
+    /// `log` as described by [`TeeStream`]. Meant only for debugging the protocol, e.g. when the session gets out of
+    /// sync.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use basex::{Client, ClientError};
+    /// # use std::io::stderr;
+    /// # fn main() -> Result<(), ClientError> {
+    /// let mut client = Client::connect_with_tee("localhost", 1984, "admin", "admin", stderr())?;
+    /// let databases = client.list()?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn connect_with_tee<W: Write + Send + 'static>(
+        host: &str,
+        port: u16,
+        user: &str,
+        password: &str,
+        log: W,
+    ) -> Result<Client<TeeStream<TcpStream, W>>> {
+        let log = Arc::new(Mutex::new(log));
+
+        ClientBuilder::new()
+            .host(host)
+            .port(port)
+            .credentials(user, password)
+            .connect_with(move |host, port, timeout| {
+                let stream = connect_tcp(host, port, timeout)?;
+                Ok(TeeStream::shared(stream, Arc::clone(&log)))
+            })
+    }
+
+    /// Turns on automatic reconnecting. When the connection turns out to be broken while sending a command, the client
+    /// dials the original host and port up to `retries` times, authenticates again and then sends the command once more.
+    ///
+    /// * Only the read-only commands not depending on the session are retried, like [`list`], [`info`] or
+    ///   [`list_backups`]. Any other command may have already been run by the server, or would run in a different
+    ///   context on the new session, so it fails with the error instead.
+    /// * The command is retried only when sending it has failed. Once the response has started to arrive, the error is
+    ///   returned.
+    /// * The new session starts clean, so the opened database and session options have to be set again.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use basex::{Client, ClientError};
+    /// # fn main() -> Result<(), ClientError> {
+    /// let mut client = Client::connect("localhost", 1984, "admin", "admin")?.with_reconnect(3);
+    /// let databases = client.list()?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// [`info`]: crate::client::Client::info
+    /// [`list`]: crate::client::Client::list
+    /// [`list_backups`]: crate::client::Client::list_backups
+    pub fn with_reconnect(mut self, retries: u32) -> Self {
+        self.retries = retries;
+        self
+    }
+}
+
+#[cfg(feature = "tls")]
+impl Client<TlsStream> {
+    /// Connects to BaseX server through a TLS tunnel, verifying the certificate of `server_name`. The TLS handshake
+    /// is performed before authentication.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use basex::{Client, ClientError};
+    /// # fn main() -> Result<(), ClientError> {
+    /// let client = Client::connect_tls("basex.example.com", 1985, "admin", "admin", "basex.example.com")?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn connect_tls(
+        host: &str,
+        port: u16,
+        user: &str,
+        password: &str,
+        server_name: &str,
+    ) -> Result<Client<TlsStream>> {
+        ClientBuilder::new()
+            .host(host)
+            .port(port)
+            .credentials(user, password)
+            .connect_tls(server_name)
+    }
+}
+
+#[cfg(unix)]
+impl Client<UnixStream> {
+    /// Connects and authenticates to BaseX server listening on the Unix domain socket at `path`, e.g. when the socket
+    /// is forwarded to a co-located server.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use basex::{Client, ClientError};
+    /// # fn main() -> Result<(), ClientError> {
+    /// let mut client = Client::connect_unix("/var/run/basex.sock", "admin", "admin")?;
+    /// let databases = client.list()?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn connect_unix(path: impl AsRef<Path>, user: &str, password: &str) -> Result<Client<UnixStream>> {
+        let stream = UnixStream::connect(path)?;
         let connection = Connection::new(stream).authenticate(user, password)?;
 
         Ok(Client::new(connection))
     }
 }
 
+impl Client<TcpStream> {
+    /// Sets the read timeout of the underlying stream. Reads exceeding the timeout fail with [`ClientError::Io`].
+    ///
+    /// Passing `None` blocks the reads indefinitely, which is the default.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use basex::{Client, ClientError};
+    /// # use std::time::Duration;
+    /// # fn main() -> Result<(), ClientError> {
+    /// let client = Client::connect("localhost", 1984, "admin", "admin")?;
+    /// client.set_read_timeout(Some(Duration::from_secs(10)))?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// [`ClientError::Io`]: crate::ClientError::Io
+    pub fn set_read_timeout(&self, timeout: Option<Duration>) -> Result<()> {
+        Ok(self.connection.stream().set_read_timeout(timeout)?)
+    }
+
+    /// Sets the write timeout of the underlying stream. Writes exceeding the timeout fail with [`ClientError::Io`].
+    ///
+    /// Passing `None` blocks the writes indefinitely, which is the default.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use basex::{Client, ClientError};
+    /// # use std::time::Duration;
+    /// # fn main() -> Result<(), ClientError> {
+    /// let client = Client::connect("localhost", 1984, "admin", "admin")?;
+    /// client.set_write_timeout(Some(Duration::from_secs(10)))?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// [`ClientError::Io`]: crate::ClientError::Io
+    pub fn set_write_timeout(&self, timeout: Option<Duration>) -> Result<()> {
+        Ok(self.connection.stream().set_write_timeout(timeout)?)
+    }
+
+    /// Returns the address of the server this client is connected to.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use basex::{Client, ClientError};
+    /// # fn main() -> Result<(), ClientError> {
+    /// let client = Client::connect("localhost", 1984, "admin", "admin")?;
+    /// println!("Connected to {}", client.peer_addr()?);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn peer_addr(&self) -> Result<SocketAddr> {
+        Ok(self.connection.stream().peer_addr()?)
+    }
+
+    /// Returns the local address this client is connected from.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use basex::{Client, ClientError};
+    /// # fn main() -> Result<(), ClientError> {
+    /// let client = Client::connect("localhost", 1984, "admin", "admin")?;
+    /// println!("Connected from {}", client.local_addr()?);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn local_addr(&self) -> Result<SocketAddr> {
+        Ok(self.connection.stream().local_addr()?)
+    }
+}
+
 impl<T> Client<T>
 where
     T: DatabaseStream,
@@ -130,7 +543,110 @@ where
     ///
     /// [`Client::connect`]: crate::client::Client<TcpStream>::connect
     pub fn new(connection: Connection<T, Authenticated>) -> Self {
-        Self { connection }
+        Self {
+            connection,
+            dialer: None,
+            retries: 0,
+            observer: Default::default(),
+            queries: Default::default(),
+            readonly: None,
+            version: None,
+        }
+    }
+
+    /// Connects using `dial` and keeps it around for reconnecting.
+    pub(crate) fn dialed<F>(dial: F) -> Result<Self>
+    where
+        F: Fn() -> Result<Connection<T, Authenticated>> + Send + Sync + 'static,
+    {
+        let dialer = Dialer(Arc::new(dial));
+        let connection = dialer.dial()?;
+
+        Ok(Self {
+            connection,
+            dialer: Some(dialer),
+            retries: 0,
+            observer: Default::default(),
+            queries: Default::default(),
+            readonly: None,
+            version: None,
+        })
+    }
+
+    /// Replaces the [`QueryObserver`] notified about the queries run by this client and its clones created afterwards.
+    /// By default, the queries are not observed.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use basex::{Client, ClientError, QueryObserver};
+    /// struct Noop;
+    /// impl QueryObserver for Noop {}
+    ///
+    /// # fn main() -> Result<(), ClientError> {
+    /// let mut client = Client::connect("localhost", 1984, "admin", "admin")?;
+    /// client.set_observer(Box::new(Noop));
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// [`QueryObserver`]: crate::QueryObserver
+    pub fn set_observer(&mut self, observer: Box<dyn QueryObserver>) {
+        self.observer = Observer::new(observer);
+    }
+
+    pub(crate) fn observer(&self) -> &dyn QueryObserver {
+        self.observer.get()
+    }
+
+    /// Whether the connection is broken, as told by the last failed read or write, so that the client is of no further
+    /// use.
+    pub(crate) fn is_broken(&self) -> bool {
+        self.connection.is_broken()
+    }
+
+    /// Records the query with the given `id` as opened on the server.
+    pub(crate) fn track_query(&self, id: &str) {
+        self.queries.ids().insert(id.to_owned());
+    }
+
+    /// Stops tracking the query with the given `id`, telling whether it was still opened.
+    pub(crate) fn forget_query(&self, id: &str) -> bool {
+        self.queries.ids().remove(id)
+    }
+
+    /// Closes all the queries opened by this client and its clones that were not closed yet, e.g. when their handles
+    /// got lost. The handles of the closed queries no longer close them when dropped.
+    ///
+    /// Tries to close all of them and fails with the first error.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use basex::{Client, Result};
+    /// # fn main() -> Result<()> {
+    /// let mut client = Client::connect("localhost", 1984, "admin", "admin")?;
+    /// let query = client.clone().query("count(/)")?.without_info()?;
+    /// std::mem::forget(query);
+    ///
+    /// client.close_all_queries()?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn close_all_queries(&mut self) -> Result<()> {
+        let ids = std::mem::take(&mut *self.queries.ids());
+        let mut first_error = None;
+
+        for id in ids {
+            if let Err(error) = close_query(&mut self.connection, &id) {
+                first_error.get_or_insert(error);
+            }
+        }
+
+        match first_error {
+            Some(error) => Err(error),
+            None => Ok(()),
+        }
     }
 
     /// Executes a server [`command`](https://docs.basex.org/wiki/Commands) including arguments.
@@ -154,15 +670,16 @@ where
     /// [`Read`]: std::io::Read
     pub fn execute(mut self, command: &str) -> Result<Response<T>> {
         self.connection.send_arg(&mut command.as_bytes())?;
+        self.connection.flush_pending()?;
         Ok(Response::new(self))
     }
 
-    /// Creates a new database with the specified `name` and, optionally, an initial `input` and opens it.
+    /// Runs a server [`command`](https://docs.basex.org/wiki/Commands) without moving the client, returning the
+    /// command result and the server info message. The result is empty for commands that produce none, such as `CLOSE`.
     ///
-    /// * Overwrites existing database with the same `name`.
-    /// * The `name` must be [valid database name](http://docs.basex.org/wiki/Commands#Valid_Names).
-    /// * The `input` is a stream with valid XML.
-    /// * More options can be controlled by setting [Create Options](http://docs.basex.org/wiki/Options#Create_Options)
+    /// Meant for commands with a short response only, as the response is read whole into memory. Use [`execute`] to
+    /// stream large results instead. Read-only commands are retried when reconnecting is on, as described by
+    /// [`with_reconnect`].
     ///
     /// # Example
     ///
@@ -170,65 +687,239 @@ where
     /// # use basex::{Client, Result};
     /// # fn main() -> Result<()> {
     /// let mut client = Client::connect("localhost", 1984, "admin", "admin")?;
-    /// client.create("boy_sminem")?.with_input("<wojak pink_index=\"69\"></wojak>")?;
-    /// client.create("bogdanoff")?.without_input()?;
+    /// let (list, _) = client.run_command("LIST")?;
+    /// println!("{}", list);
+    /// let (_, info) = client.run_command("CLOSE")?;
+    /// println!("{}", info);
     /// # Ok(())
     /// # }
     /// ```
-    pub fn create(&mut self, name: &str) -> Result<CommandWithOptionalInput<T>> {
-        self.connection.send_cmd(Command::Create as u8)?;
-        self.connection.send_arg(&mut name.as_bytes())?;
-        Ok(CommandWithOptionalInput::new(&mut self.connection))
+    ///
+    /// [`execute`]: crate::Client::execute
+    /// [`with_reconnect`]: crate::Client::with_reconnect
+    pub fn run_command(&mut self, command: &str) -> Result<(String, String)> {
+        self.command(command)
     }
 
-    /// Replaces resources in the currently opened database, addressed by `path`, with the XML document read from
-    /// `input`, or adds new documents if no resource exists at the specified path.
+    /// Sets the server `option` to the `value` for the current session, returning the server info message.
+    ///
+    /// * Fails with [`InvalidName`] without contacting the server when the name of [`ServerOption::Other`] is not made
+    ///   of ASCII letters and digits only, or the `value` contains a semicolon or a line break.
+    /// * Fails with [`CommandFailed`] when the server does not know the option or rejects the value.
     ///
     /// # Example
     ///
     /// ```
-    /// # use basex::{Client, Result};
+    /// # use basex::{Client, Result, ServerOption};
     /// # fn main() -> Result<()> {
     /// let mut client = Client::connect("localhost", 1984, "admin", "admin")?;
-    /// client.create("bell")?.without_input()?;
-    /// client.replace("bogdanoff", "<wojak pink_index=\"69\"></wojak>")?;
+    /// client.set_option(ServerOption::Serializer, "indent=yes")?;
     /// # Ok(())
     /// # }
     /// ```
-    pub fn replace<'a>(&mut self, path: &str, input: impl AsResource<'a>) -> Result<String> {
-        self.connection.send_cmd(Command::Replace as u8)?;
-        self.connection.send_arg(&mut path.as_bytes())?;
-        self.connection.send_arg(&mut input.into_read())?;
-        self.connection.get_response()
+    ///
+    /// [`CommandFailed`]: crate::ClientError::CommandFailed
+    /// [`InvalidName`]: crate::ClientError::InvalidName
+    /// [`ServerOption::Other`]: crate::ServerOption::Other
+    pub fn set_option(&mut self, option: ServerOption, value: &str) -> Result<String> {
+        option.validate()?;
+        validate_value(value)?;
+        let (_, info) = self.command(&format!("SET {} {}", option, value))?;
+        Ok(info)
     }
 
-    /// Stores a binary file from `input` in the currently opened database under `path`. Overwrites existing resource.
+    /// Returns the current value of the server `option`.
+    ///
+    /// Fails with [`InvalidName`] without contacting the server when the name of [`ServerOption::Other`] is not made of
+    /// ASCII letters and digits only.
     ///
     /// # Example
     ///
     /// ```
-    /// # use basex::{Client, Result};
+    /// # use basex::{Client, Result, ServerOption};
     /// # fn main() -> Result<()> {
     /// let mut client = Client::connect("localhost", 1984, "admin", "admin")?;
-    /// let mut blob = [0 as u8, 1, 2, 3];
-    /// client.create("asylum")?.without_input()?;
-    /// client.store("bogdanoff", &mut &blob[..])?;
+    /// let chop = client.get_option(ServerOption::Chop)?;
+    /// println!("CHOP is {}", chop);
     /// # Ok(())
     /// # }
     /// ```
-    pub fn store<'a>(&mut self, path: &str, input: impl AsResource<'a>) -> Result<String> {
-        self.connection.send_cmd(Command::Store as u8)?;
-        self.connection.send_arg(&mut path.as_bytes())?;
-        self.connection.send_arg(&mut input.into_read())?;
-        self.connection.get_response()
+    ///
+    /// [`InvalidName`]: crate::ClientError::InvalidName
+    /// [`ServerOption::Other`]: crate::ServerOption::Other
+    pub fn get_option(&mut self, option: ServerOption) -> Result<String> {
+        option.validate()?;
+        let (result, _) = self.command(&format!("GET {}", option))?;
+        result
+            .split_once(':')
+            .map(|(_, value)| value.trim().to_owned())
+            .ok_or_else(|| ClientError::Protocol(format!("expected option value, got \"{}\"", result)))
     }
 
-    /// Adds an XML resource to the currently opened database under the specified `path`.
+    /// Applies the create `options` to the session, calls `run` with the client, e.g. to [`create`] a database or
+    /// [`add`] documents, and restores the options to their previous values, returning what `run` returned.
     ///
-    /// * Keeps multiple documents with the same `path`. If this is unwanted, use `Client::replace`.
-    /// * On the server-side if the stream is too large to be added in one go, its data structures will be cached to
-    /// disk first. Caching can be enforced by turning the `ADDCACHE` option on.
+    /// The previous values are read before each option is set. The options are restored in reverse order, even if
+    /// setting them or `run` fails, in which case that failure is returned. Fails with [`InvalidName`] without
+    /// contacting the server when any of the options or values is not valid, as described by [`set_option`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use basex::{Client, CreateOptions, Result};
+    /// # fn main() -> Result<()> {
+    /// let mut client = Client::connect("localhost", 1984, "admin", "admin")?;
+    /// let options = CreateOptions::new().chop(false).text_index(false);
+    /// let info = client.with_create_options(&options, |client| client.create("sirius")?.without_input())?;
+    /// println!("{}", info);
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// [`create`]: crate::Client::create
+    /// [`add`]: crate::Client::add
+    /// [`InvalidName`]: crate::ClientError::InvalidName
+    /// [`set_option`]: crate::Client::set_option
+    pub fn with_create_options<R, F>(&mut self, options: &CreateOptions, run: F) -> Result<R>
+    where
+        F: FnOnce(&mut Self) -> Result<R>,
+    {
+        options.validate()?;
+        let mut previous = vec![];
+        let outcome = self.apply_options(options, &mut previous).and_then(|_| run(self));
+        let restored = self.restore_options(previous);
+
+        let value = outcome?;
+        restored?;
+        Ok(value)
+    }
+
+    /// Sets the `options`, pushing the previous value of each one set into `previous`.
+    fn apply_options(&mut self, options: &CreateOptions, previous: &mut Vec<(ServerOption, String)>) -> Result<()> {
+        for (option, value) in options.iter() {
+            let value_before = self.get_option(option.clone())?;
+            self.set_option(option.clone(), value)?;
+            previous.push((option.clone(), value_before));
+        }
+        Ok(())
+    }
+
+    /// Sets the `previous` options back in reverse order, trying all of them and failing with the first error.
+    fn restore_options(&mut self, previous: Vec<(ServerOption, String)>) -> Result<()> {
+        let mut first_error = None;
+        for (option, value) in previous.into_iter().rev() {
+            if let Err(error) = self.set_option(option, &value) {
+                first_error.get_or_insert(error);
+            }
+        }
+
+        match first_error {
+            Some(error) => Err(error),
+            None => Ok(()),
+        }
+    }
+
+    /// Checks that the connection is alive by running an empty query, which leaves the opened database and options
+    /// untouched.
+    ///
+    /// Never reconnects, so that a broken connection is reported as an error even when reconnecting is on.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use basex::{Client, Result};
+    /// # fn main() -> Result<()> {
+    /// let mut client = Client::connect("localhost", 1984, "admin", "admin")?;
+    /// client.ping()?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn ping(&mut self) -> Result<()> {
+        self.try_command("XQUERY ()")?;
+        Ok(())
+    }
+
+    /// Runs a typed server [`Command`] without moving the client, telling apart the commands that produce a result from
+    /// those that produce only the info message, as reported by [`Command::has_body`].
+    ///
+    /// Meant for commands with a short response only, as the response is read whole into memory. Use [`run`] to
+    /// stream large or binary results, such as those of [`Command::Retrieve`], instead.
+    ///
+    /// Fails with [`InvalidName`] without contacting the server when a name or path the `command` carries is not valid,
+    /// such as a glob pattern in place of a database name.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use basex::{Client, Command, CommandOutcome, Result};
+    /// # fn main() -> Result<()> {
+    /// let mut client = Client::connect("localhost", 1984, "admin", "admin")?;
+    /// if let CommandOutcome::Body { result, .. } = client.run_outcome(Command::List)? {
+    ///     println!("{}", result);
+    /// }
+    /// let outcome = client.run_outcome(Command::Close)?;
+    /// assert_eq!(None, outcome.result());
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// [`Command`]: crate::Command
+    /// [`Command::has_body`]: crate::Command::has_body
+    /// [`Command::Retrieve`]: crate::Command::Retrieve
+    /// [`InvalidName`]: crate::ClientError::InvalidName
+    /// [`run`]: crate::Client::run
+    pub fn run_outcome(&mut self, command: Command) -> Result<CommandOutcome> {
+        command.validate()?;
+        if command.updates_opened_database() {
+            self.ensure_writable()?;
+        }
+        let (result, info) = self.command(&command.to_string())?;
+        match command.has_body() {
+            true => Ok(CommandOutcome::Body { result, info }),
+            false => Ok(CommandOutcome::Status(info)),
+        }
+    }
+
+    /// Runs a typed server [`Command`]. Use [`execute`] for commands that are not covered.
+    ///
+    /// Returns response which can be read using the [`Read`] trait.
+    ///
+    /// Fails with [`InvalidName`] without contacting the server when a name or path the `command` carries is not valid,
+    /// such as a glob pattern in place of a database name.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use basex::{Client, Command, Result};
+    /// # use std::io::Read;
+    /// # fn main() -> Result<()> {
+    /// let client = Client::connect("localhost", 1984, "admin", "admin")?;
+    /// let mut list = String::new();
+    /// client.run(Command::List)?.read_to_string(&mut list)?;
+    /// println!("{}", list);
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// [`Command`]: crate::Command
+    /// [`execute`]: crate::Client::execute
+    /// [`InvalidName`]: crate::ClientError::InvalidName
+    /// [`Read`]: std::io::Read
+    pub fn run(self, command: Command) -> Result<Response<T>> {
+        command.validate()?;
+        if command.updates_opened_database() {
+            self.ensure_writable()?;
+        }
+        self.execute(&command.to_string())
+    }
+
+    /// Creates a new database with the specified `name` and, optionally, an initial `input` and opens it.
+    ///
+    /// * Overwrites existing database with the same `name`.
+    /// * The `name` must be [valid database name](http://docs.basex.org/wiki/Commands#Valid_Names), otherwise fails
+    ///   with [`InvalidName`] without contacting the server.
     /// * The `input` is a stream with valid XML.
+    /// * More options can be controlled by setting [Create Options](http://docs.basex.org/wiki/Options#Create_Options)
     ///
     /// # Example
     ///
@@ -236,182 +927,2236 @@ where
     /// # use basex::{Client, Result};
     /// # fn main() -> Result<()> {
     /// let mut client = Client::connect("localhost", 1984, "admin", "admin")?;
-    /// client.create("taurus")?.without_input()?;
-    /// client.add("bogdanoff", &mut "<wojak pink_index=\"69\"></wojak>".as_bytes())?;
+    /// client.create("boy_sminem")?.with_input("<wojak pink_index=\"69\"></wojak>")?;
+    /// client.create("bogdanoff")?.without_input()?;
     /// # Ok(())
     /// # }
     /// ```
-    pub fn add<'a>(&mut self, path: &str, input: impl AsResource<'a>) -> Result<String> {
-        self.connection.send_cmd(Command::Add as u8)?;
-        self.connection.send_arg(&mut path.as_bytes())?;
-        self.connection.send_arg(&mut input.into_read())?;
-        self.connection.get_response()
+    ///
+    /// [`InvalidName`]: crate::ClientError::InvalidName
+    pub fn create(&mut self, name: &str) -> Result<CommandWithOptionalInput<'_, T>> {
+        validate_name(name)?;
+        self.ensure_writable()?;
+        self.connection.send_cmd(CommandCode::Create as u8)?;
+        self.connection.send_arg(&mut name.as_bytes())?;
+        Ok(CommandWithOptionalInput::new(&mut self.connection))
     }
 
-    /// Creates a new `query` from given XQuery code.
+    /// Opens the database with the specified `name`, returning the server info message.
     ///
-    /// You then need to make a statement about collecting compiler info using either [`with_info`] or [`without_info`].
+    /// * Closes the currently opened database, if there is any.
+    /// * Fails with [`InvalidName`] without contacting the server when the `name` is not
+    ///   [valid database name](http://docs.basex.org/wiki/Commands#Valid_Names).
+    /// * Fails with [`CommandFailed`] when no database with the given `name` exists.
     ///
     /// # Example
     ///
     /// ```
     /// # use basex::{Client, Result};
-    /// # use std::io::Read;
     /// # fn main() -> Result<()> {
     /// let mut client = Client::connect("localhost", 1984, "admin", "admin")?;
+    /// client.create("lunar")?.without_input()?;
+    /// let info = client.open("lunar")?;
+    /// assert!(info.starts_with("Database 'lunar' was opened"));
+    /// # Ok(())
+    /// # }
+    /// ```
     ///
-    /// let info = client.create("triangle")?
-    ///     .with_input("<polygon><line></line><line></line><line></line></polygon>")?;
-    /// assert!(info.starts_with("Database 'triangle' created"));
+    /// [`CommandFailed`]: crate::ClientError::CommandFailed
+    /// [`InvalidName`]: crate::ClientError::InvalidName
+    pub fn open(&mut self, name: &str) -> Result<String> {
+        validate_name(name)?;
+        let (_, info) = self.command(&format!("OPEN {}", name))?;
+        Ok(info)
+    }
+
+    /// Opens the database with the specified `name` like [`open`], closing it again when the returned guard drops. The
+    /// guard dereferences to the client, so that it can be used while the database is open.
     ///
-    /// let query = client.query("count(/polygon/*)")?.without_info()?;
-    /// let mut result = String::new();
-    /// let mut response = query.execute()?;
-    /// response.read_to_string(&mut result)?;
-    /// assert_eq!(result, "3");
+    /// Closing on drop is best-effort, as described by [`OpenDbGuard`].
     ///
-    /// let mut query = response.close()?;
-    /// query.close()?;
+    /// # Example
+    ///
+    /// ```
+    /// # use basex::{Client, Result};
+    /// # fn main() -> Result<()> {
+    /// let mut client = Client::connect("localhost", 1984, "admin", "admin")?;
+    /// client.create("nebula")?.with_input("<nebula/>")?;
+    /// {
+    ///     let mut nebula = client.open_scoped("nebula")?;
+    ///     let (count, _) = nebula.run_command("XQUERY count(//nebula)")?;
+    ///     assert_eq!("1", count);
+    /// }
     /// # Ok(())
     /// # }
     /// ```
     ///
-    /// [`with_info`]: self::QueryWithOptionalInfo::with_info
-    /// [`without_info`]: self::QueryWithOptionalInfo::without_info
-    pub fn query<'a, R: AsResource<'a>>(self, query: R) -> Result<QueryWithOptionalInfo<'a, T, R>> {
-        Ok(QueryWithOptionalInfo::new(self, query))
+    /// [`open`]: self::Client::open
+    /// [`OpenDbGuard`]: crate::OpenDbGuard
+    pub fn open_scoped(&mut self, name: &str) -> Result<OpenDbGuard<'_, T>> {
+        self.open(name)?;
+        Ok(OpenDbGuard {
+            client: self,
+            open: true,
+        })
+    }
+
+    /// Opens the database with the given `name` for reading only, until [`end_readonly`] closes it.
+    ///
+    /// BaseX has no snapshots or transactions spanning more than one command, as it only locks the databases for the
+    /// duration of each command or query. This is therefore a best-effort read view, with these limitations:
+    ///
+    /// * Each command or query sees a consistent state of the database, but updates committed by other sessions in
+    ///   between are seen by the next one. Reads that have to agree with each other should be run as a single query.
+    /// * This client refuses to change the database meanwhile. Adding, replacing, storing and renaming resources,
+    ///   optimizing, creating databases and running the typed commands that change the opened database fail with
+    ///   [`ReadOnly`] without contacting the server. Raw commands and queries, such as those passed to [`execute`],
+    ///   [`run_command`] or [`query`], are sent as they are.
+    /// * Starting another read view ends the current one first.
+    ///
+    /// * Fails with [`InvalidName`] without contacting the server when the `name` is not
+    ///   [valid database name](http://docs.basex.org/wiki/Commands#Valid_Names).
+    /// * Fails with [`CommandFailed`] when no database with the given `name` exists.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use basex::{Client, Result};
+    /// # fn main() -> Result<()> {
+    /// let mut client = Client::connect("localhost", 1984, "admin", "admin")?;
+    /// client.create("quasar")?.with_input("<quasar/>")?;
+    ///
+    /// client.begin_readonly("quasar")?;
+    /// let (counts, _) = client.run_command("XQUERY count(//quasar) || ' of ' || count(//node())")?;
+    /// assert!(client.add("pulsar.xml", "<pulsar/>").is_err());
+    /// client.end_readonly()?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// [`CommandFailed`]: crate::ClientError::CommandFailed
+    /// [`end_readonly`]: self::Client::end_readonly
+    /// [`execute`]: self::Client::execute
+    /// [`InvalidName`]: crate::ClientError::InvalidName
+    /// [`query`]: self::Client::query
+    /// [`ReadOnly`]: crate::ClientError::ReadOnly
+    /// [`run_command`]: self::Client::run_command
+    pub fn begin_readonly(&mut self, name: &str) -> Result<()> {
+        validate_name(name)?;
+        self.end_readonly()?;
+
+        self.open(name)?;
+        self.readonly = Some(name.to_owned());
+
+        Ok(())
+    }
+
+    /// Closes the database opened by [`begin_readonly`], allowing changes again. Does nothing when there is no read
+    /// view.
+    ///
+    /// [`begin_readonly`]: self::Client::begin_readonly
+    pub fn end_readonly(&mut self) -> Result<()> {
+        if self.readonly.take().is_some() {
+            self.command(&Command::Close.to_string())?;
+        }
+
+        Ok(())
+    }
+
+    /// Fails with [`ReadOnly`] while a read view begun by [`begin_readonly`] lasts.
+    ///
+    /// [`begin_readonly`]: self::Client::begin_readonly
+    /// [`ReadOnly`]: crate::ClientError::ReadOnly
+    fn ensure_writable(&self) -> Result<()> {
+        match &self.readonly {
+            Some(name) => Err(ClientError::ReadOnly(name.to_owned())),
+            None => Ok(()),
+        }
+    }
+
+    /// Copies the database named `source` to a new database named `target`, returning the server info message.
+    ///
+    /// * Fails with [`InvalidName`] without contacting the server when either name is not
+    ///   [valid database name](http://docs.basex.org/wiki/Commands#Valid_Names).
+    /// * Fails with [`CommandFailed`] when no database named `source` exists or a database named `target` already
+    ///   exists.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use basex::{Client, Result};
+    /// # fn main() -> Result<()> {
+    /// let mut client = Client::connect("localhost", 1984, "admin", "admin")?;
+    /// client.create("pulsar")?.with_input("<pulsar/>")?;
+    /// let info = client.copy_database("pulsar", "pulsar-copy")?;
+    /// assert!(info.starts_with("Database 'pulsar' was copied"));
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// [`CommandFailed`]: crate::ClientError::CommandFailed
+    /// [`InvalidName`]: crate::ClientError::InvalidName
+    pub fn copy_database(&mut self, source: &str, target: &str) -> Result<String> {
+        validate_name(source)?;
+        validate_name(target)?;
+        let (_, info) = self.command(&Command::Copy(source.to_owned(), target.to_owned()).to_string())?;
+        Ok(info)
+    }
+
+    /// Drops the database with the specified `name`, returning the server info message.
+    ///
+    /// * Fails with [`InvalidName`] without contacting the server when the `name` is not
+    ///   [valid database name](http://docs.basex.org/wiki/Commands#Valid_Names).
+    /// * Fails with [`CommandFailed`] when no database with the given `name` exists.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use basex::{Client, Result};
+    /// # fn main() -> Result<()> {
+    /// let mut client = Client::connect("localhost", 1984, "admin", "admin")?;
+    /// client.create("meteor")?.without_input()?;
+    /// let info = client.drop_database("meteor")?;
+    /// assert!(info.starts_with("Database 'meteor' was dropped"));
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// [`InvalidName`]: crate::ClientError::InvalidName
+    /// [`CommandFailed`]: crate::ClientError::CommandFailed
+    pub fn drop_database(&mut self, name: &str) -> Result<String> {
+        self.drop(DropTarget::Database(name.to_owned()))
+    }
+
+    /// Drops the `target` database, backup, user or installed module, returning the server info message.
+    ///
+    /// * Fails with [`InvalidName`] without contacting the server when the name of a database, backup or user is not
+    ///   [valid](http://docs.basex.org/wiki/Commands#Valid_Names), or the module name contains whitespace or a
+    ///   semicolon.
+    /// * Fails with [`CommandFailed`] when the target does not exist.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use basex::{Client, DropTarget, Result};
+    /// # fn main() -> Result<()> {
+    /// let mut client = Client::connect("localhost", 1984, "admin", "admin")?;
+    /// client.create("comet")?.without_input()?;
+    /// let info = client.drop(DropTarget::Database("comet".to_owned()))?;
+    /// assert!(info.starts_with("Database 'comet' was dropped"));
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// [`CommandFailed`]: crate::ClientError::CommandFailed
+    /// [`InvalidName`]: crate::ClientError::InvalidName
+    pub fn drop(&mut self, target: DropTarget) -> Result<String> {
+        target.validate()?;
+        let (_, info) = self.command(&Command::Drop(target).to_string())?;
+        Ok(info)
+    }
+
+    /// Optimizes the index structures and statistics of the currently opened database, returning the time it took. When
+    /// `all` is set, the database and its index structures are rebuilt from scratch.
+    ///
+    /// Fails with [`CommandFailed`] when no database is opened.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use basex::{Client, Result};
+    /// # fn main() -> Result<()> {
+    /// let mut client = Client::connect("localhost", 1984, "admin", "admin")?;
+    /// client.create("pulsar")?.with_input("<pulsar/>")?;
+    /// let duration = client.optimize(true)?;
+    /// println!("Optimized in {:?}", duration);
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// [`CommandFailed`]: crate::ClientError::CommandFailed
+    pub fn optimize(&mut self, all: bool) -> Result<Duration> {
+        self.ensure_writable()?;
+        let command = match all {
+            true => Command::OptimizeAll,
+            false => Command::Optimize,
+        };
+        let (_, info) = self.command(&command.to_string())?;
+        elapsed_from_info(info)
+    }
+
+    /// Flushes the buffers of the currently opened database to disk.
+    ///
+    /// Fails with [`CommandFailed`] when no database is opened.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use basex::{Client, Result};
+    /// # fn main() -> Result<()> {
+    /// let mut client = Client::connect("localhost", 1984, "admin", "admin")?;
+    /// client.create("magnetar")?.with_input("<magnetar/>")?;
+    /// client.flush()?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// [`CommandFailed`]: crate::ClientError::CommandFailed
+    pub fn flush(&mut self) -> Result<()> {
+        self.command(&Command::Flush.to_string())?;
+        Ok(())
+    }
+
+    /// Backs up the database with the given `name`, returning the server info message.
+    ///
+    /// * Fails with [`InvalidName`] without contacting the server when the `name` is not
+    ///   [valid database name](http://docs.basex.org/wiki/Commands#Valid_Names), as glob patterns would back up every
+    ///   matching database.
+    /// * Fails with [`CommandFailed`] when no database with the given `name` exists.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use basex::{Client, Result};
+    /// # fn main() -> Result<()> {
+    /// let mut client = Client::connect("localhost", 1984, "admin", "admin")?;
+    /// client.create("blazar")?.with_input("<blazar/>")?;
+    /// let info = client.backup("blazar")?;
+    /// println!("{}", info);
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// [`CommandFailed`]: crate::ClientError::CommandFailed
+    /// [`InvalidName`]: crate::ClientError::InvalidName
+    pub fn backup(&mut self, name: &str) -> Result<String> {
+        validate_name(name)?;
+        let (_, info) = self.command(&Command::CreateBackup(name.to_owned()).to_string())?;
+        Ok(info)
+    }
+
+    /// Restores a database from a backup, returning the server info message. The `name_with_timestamp` is either the
+    /// database name, which picks the latest backup, or the full backup name as listed by [`list_backups`].
+    ///
+    /// * Fails with [`InvalidName`] without contacting the server when the `name_with_timestamp` is not
+    ///   [valid database name](http://docs.basex.org/wiki/Commands#Valid_Names), as glob patterns are not accepted.
+    /// * Fails with [`CommandFailed`] when no backup with the given name exists.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use basex::{Client, Result};
+    /// # fn main() -> Result<()> {
+    /// let mut client = Client::connect("localhost", 1984, "admin", "admin")?;
+    /// client.create("quasar")?.with_input("<quasar/>")?;
+    /// client.backup("quasar")?;
+    /// let info = client.restore("quasar")?;
+    /// println!("{}", info);
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// [`CommandFailed`]: crate::ClientError::CommandFailed
+    /// [`InvalidName`]: crate::ClientError::InvalidName
+    /// [`list_backups`]: crate::Client::list_backups
+    pub fn restore(&mut self, name_with_timestamp: &str) -> Result<String> {
+        validate_name(name_with_timestamp)?;
+        let (_, info) = self.command(&Command::Restore(name_with_timestamp.to_owned()).to_string())?;
+        Ok(info)
+    }
+
+    /// Lists the names of all backups available on the server.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use basex::{Client, Result};
+    /// # fn main() -> Result<()> {
+    /// let mut client = Client::connect("localhost", 1984, "admin", "admin")?;
+    /// for backup in client.list_backups()? {
+    ///     println!("{}", backup);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn list_backups(&mut self) -> Result<Vec<String>> {
+        let (table, _) = self.command(&Command::ShowBackups.to_string())?;
+        Ok(names_from_table(&table))
+    }
+
+    /// Lists all databases available on the server.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use basex::{Client, Result};
+    /// # fn main() -> Result<()> {
+    /// let mut client = Client::connect("localhost", 1984, "admin", "admin")?;
+    /// client.create("galaxy")?.without_input()?;
+    /// let databases = client.list()?;
+    /// assert!(databases.iter().any(|database| database.name() == "galaxy"));
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn list(&mut self) -> Result<Vec<DatabaseInfo>> {
+        let (list, _) = self.command("LIST")?;
+        Ok(DatabaseInfo::from_list(&list))
+    }
+
+    /// Lists the resources stored in the database with the given `name`.
+    ///
+    /// Fails with [`InvalidName`] when the name is not a [valid name](https://docs.basex.org/wiki/Commands#Valid_Names).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use basex::{Client, ResourceKind, Result};
+    /// # fn main() -> Result<()> {
+    /// let mut client = Client::connect("localhost", 1984, "admin", "admin")?;
+    /// client.create("meteor")?.with_input("<meteor/>")?;
+    /// let resources = client.list_resources("meteor")?;
+    /// assert!(resources.iter().all(|resource| resource.kind() == ResourceKind::Xml));
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// [`InvalidName`]: crate::ClientError::InvalidName
+    pub fn list_resources(&mut self, name: &str) -> Result<Vec<ResourceEntry>> {
+        validate_name(name)?;
+        let (list, _) = self.command(&format!("LIST {}", name))?;
+        Ok(ResourceEntry::from_list(&list))
+    }
+
+    /// Reports the server version, memory usage and the global and local options using the
+    /// [`INFO`](https://docs.basex.org/wiki/Commands#INFO) command.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use basex::{Client, Result, ServerInfo};
+    /// # fn main() -> Result<()> {
+    /// let mut client = Client::connect("localhost", 1984, "admin", "admin")?;
+    /// let info = client.info()?;
+    /// println!("Running BaseX {}", info.version().unwrap_or_default());
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn info(&mut self) -> Result<impl ServerInfo> {
+        let (info, _) = self.command("INFO")?;
+        Ok(RawServerInfo::new(info))
+    }
+
+    /// Reports the version of the server, as told by the [`INFO`](https://docs.basex.org/wiki/Commands#INFO) command.
+    /// The version is only asked for once and then kept by the client and its clones, until it reconnects.
+    ///
+    /// Both the authentication methods of the servers before and since BaseX 8.0 are supported without knowing the
+    /// version, as the server challenge tells them apart.
+    ///
+    /// Fails with [`ClientError::Protocol`] when the server does not report a version in a known format.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use basex::{Client, Result, Version};
+    /// # fn main() -> Result<()> {
+    /// let mut client = Client::connect("localhost", 1984, "admin", "admin")?;
+    /// let version = client.server_version()?;
+    /// assert!(version >= Version::parse("9.0").unwrap());
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// [`ClientError::Protocol`]: crate::ClientError::Protocol
+    pub fn server_version(&mut self) -> Result<Version> {
+        if let Some(version) = &self.version {
+            return Ok(version.clone());
+        }
+
+        let info = self.info()?;
+        let reported = info.version().unwrap_or_default();
+        let version = Version::parse(&reported)
+            .ok_or_else(|| ClientError::Protocol(format!("unrecognized server version {:?}", reported)))?;
+        self.version = Some(version.clone());
+
+        Ok(version)
+    }
+
+    /// Reports the statistics of the currently opened database using the
+    /// [`INFO DB`](https://docs.basex.org/wiki/Commands#INFO_DB) command.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use basex::{Client, Result};
+    /// # fn main() -> Result<()> {
+    /// let mut client = Client::connect("localhost", 1984, "admin", "admin")?;
+    /// client.create("quasar")?.with_input("<quasar/>")?;
+    ///
+    /// let stats = client.info_database()?;
+    /// assert_eq!(1, stats.documents());
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn info_database(&mut self) -> Result<DatabaseStats> {
+        let (info, _) = self.command("INFO DB")?;
+        DatabaseStats::from_info(info)
+    }
+
+    /// Replaces resources in the currently opened database, addressed by `path`, with the XML document read from
+    /// `input`, or adds new documents if no resource exists at the specified path.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use basex::{Client, Result};
+    /// # fn main() -> Result<()> {
+    /// let mut client = Client::connect("localhost", 1984, "admin", "admin")?;
+    /// client.create("bell")?.without_input()?;
+    /// client.replace("bogdanoff", "<wojak pink_index=\"69\"></wojak>")?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn replace<'a>(&mut self, path: &str, input: impl AsResource<'a>) -> Result<String> {
+        self.ensure_writable()?;
+        let mut input = input.try_into_read()?;
+        self.connection.send_cmd(CommandCode::Replace as u8)?;
+        self.connection.send_arg(&mut path.as_bytes())?;
+        self.connection.send_arg(&mut input)?;
+        self.connection.get_response()
+    }
+
+    /// Stores a binary file from `input` in the currently opened database under `path`. Overwrites existing resource.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use basex::{Client, Result};
+    /// # fn main() -> Result<()> {
+    /// let mut client = Client::connect("localhost", 1984, "admin", "admin")?;
+    /// client.create("asylum")?.without_input()?;
+    /// client.store("bogdanoff", vec![0u8, 1, 2, 3])?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn store<'a>(&mut self, path: &str, input: impl AsResource<'a>) -> Result<String> {
+        self.ensure_writable()?;
+        let mut input = input.try_into_read()?;
+        self.connection.send_cmd(CommandCode::Store as u8)?;
+        self.connection.send_arg(&mut path.as_bytes())?;
+        self.connection.send_arg(&mut input)?;
+        self.connection.get_response()
+    }
+
+    /// Adds an XML resource to the currently opened database under the specified `path`.
+    ///
+    /// * Keeps multiple documents with the same `path`. If this is unwanted, use `Client::replace`.
+    /// * On the server-side if the stream is too large to be added in one go, its data structures will be cached to
+    ///   disk first. Caching can be enforced by turning the `ADDCACHE` option on.
+    /// * The `input` is a stream with valid XML.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use basex::{Client, Result};
+    /// # fn main() -> Result<()> {
+    /// let mut client = Client::connect("localhost", 1984, "admin", "admin")?;
+    /// client.create("taurus")?.without_input()?;
+    /// client.add("bogdanoff", &mut "<wojak pink_index=\"69\"></wojak>".as_bytes())?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn add<'a>(&mut self, path: &str, input: impl AsResource<'a>) -> Result<String> {
+        self.ensure_writable()?;
+        let mut input = input.try_into_read()?;
+        self.connection.send_cmd(CommandCode::Add as u8)?;
+        self.connection.send_arg(&mut path.as_bytes())?;
+        self.connection.send_arg(&mut input)?;
+        self.connection.get_response()
+    }
+
+    /// Adds an XML resource to the currently opened database under the specified `path`, like [`add`], with the
+    /// document written by `write` instead of read from an input. The bytes are escaped and sent as they are written,
+    /// so the document is never held in memory as a whole.
+    ///
+    /// The server cannot be told to drop a document it has started receiving. So when `write` fails, the document is
+    /// ended by an unfinished tag, which the XML parser rejects, and the error of `write` is returned as
+    /// [`ClientError::Io`] once the server has answered. The connection is then ready for the next command. With a
+    /// `PARSER` other than the XML one, the part written before the failure may get added.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use basex::{Client, Result};
+    /// # use std::io::Write;
+    /// # fn main() -> Result<()> {
+    /// let mut client = Client::connect("localhost", 1984, "admin", "admin")?;
+    /// client.create("merope")?.without_input()?;
+    /// client.add_with("stars.xml", |out| {
+    ///     out.write_all(b"<stars>")?;
+    ///     for magnitude in 1..=3 {
+    ///         write!(out, "<star magnitude=\"{}\"/>", magnitude)?;
+    ///     }
+    ///     out.write_all(b"</stars>")
+    /// })?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// [`add`]: crate::Client::add
+    /// [`ClientError::Io`]: crate::ClientError::Io
+    pub fn add_with<F>(&mut self, path: &str, write: F) -> Result<String>
+    where
+        F: FnOnce(&mut dyn Write) -> io::Result<()>,
+    {
+        self.ensure_writable()?;
+        self.connection.send_cmd(CommandCode::Add as u8)?;
+        self.connection.send_arg(&mut path.as_bytes())?;
+        let written = self.connection.send_arg_with(write, b"<")?;
+        let response = self.connection.get_response();
+
+        match written {
+            Ok(()) => response,
+            Err(error) => Err(error.into()),
+        }
+    }
+
+    /// Adds each of the `items` as an XML resource to the currently opened database under its path, like [`add`]
+    /// called for every one of them, returning the info messages in the same order.
+    ///
+    /// All the resources are sent before any of the responses is read, saving a round-trip per resource. The adds are
+    /// best-effort: one rejected by the server does not stop the others from being added, and all the responses are
+    /// read to keep the connection usable. The first rejection is then returned as [`CommandFailed`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use basex::{Client, Result};
+    /// # fn main() -> Result<()> {
+    /// let mut client = Client::connect("localhost", 1984, "admin", "admin")?;
+    /// client.create("pleiades")?.without_input()?;
+    /// let infos = client.add_many(vec![
+    ///     ("alcyone.xml".to_owned(), "<star/>"),
+    ///     ("atlas.xml".to_owned(), "<star/>"),
+    /// ])?;
+    /// assert_eq!(2, infos.len());
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// [`add`]: crate::Client::add
+    /// [`CommandFailed`]: crate::ClientError::CommandFailed
+    pub fn add_many<'a, R, I>(&mut self, items: I) -> Result<Vec<String>>
+    where
+        R: AsResource<'a>,
+        I: IntoIterator<Item = (String, R)>,
+    {
+        self.ensure_writable()?;
+        let items = items
+            .into_iter()
+            .map(|(path, input)| Ok((path, input.try_into_read()?)))
+            .collect::<Result<Vec<_>>>()?;
+        let mut sent = 0;
+        for (path, mut input) in items {
+            self.connection.send_cmd(CommandCode::Add as u8)?;
+            self.connection.send_arg(&mut path.as_bytes())?;
+            self.connection.send_arg(&mut input)?;
+            sent += 1;
+        }
+
+        let mut infos = Vec::with_capacity(sent);
+        let mut first_error = None;
+        for _ in 0..sent {
+            match self.connection.get_response() {
+                Ok(info) => infos.push(info),
+                Err(error @ ClientError::CommandFailed { .. }) => {
+                    first_error.get_or_insert(error);
+                }
+                Err(error) => return Err(error),
+            }
+        }
+
+        match first_error {
+            Some(error) => Err(error),
+            None => Ok(infos),
+        }
+    }
+
+    /// Replaces each of the `items` in the currently opened database with the XML document read from its input, or adds
+    /// it if no resource exists at its path. As opposed to calling [`replace`] for every one of them, either all the
+    /// documents are replaced or none is.
+    ///
+    /// The commands of the server protocol are not transactional, so the replaces are run as a single updating query
+    /// calling `db:replace` for every item, which the server applies at once. The paths and inputs are bound as
+    /// external variables, so they are never parsed as code. Every input is parsed as XML before anything is replaced,
+    /// and a malformed one fails the whole batch with [`QueryFailed`].
+    ///
+    /// The name of the opened database is looked up first, so this fails with [`CommandFailed`] when none is opened.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use basex::{Client, Result};
+    /// # fn main() -> Result<()> {
+    /// let mut client = Client::connect("localhost", 1984, "admin", "admin")?;
+    /// client.create("hyades")?.without_input()?;
+    /// client.replace_many(vec![
+    ///     ("ain.xml".to_owned(), "<star/>"),
+    ///     ("chamukuy.xml".to_owned(), "<star/>"),
+    /// ])?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// [`replace`]: crate::Client::replace
+    /// [`QueryFailed`]: crate::ClientError::QueryFailed
+    /// [`CommandFailed`]: crate::ClientError::CommandFailed
+    pub fn replace_many<'a, R, I>(&mut self, items: I) -> Result<()>
+    where
+        R: AsResource<'a>,
+        I: IntoIterator<Item = (String, R)>,
+    {
+        self.ensure_writable()?;
+        let items = items
+            .into_iter()
+            .map(|(path, input)| Ok((path, input.try_into_read()?)))
+            .collect::<Result<Vec<_>>>()?;
+        if items.is_empty() {
+            return Ok(());
+        }
+
+        let database = self.info_database()?.name().to_owned();
+        let code = replace_many_query(items.len());
+        let mut query = self.clone().query(code.as_str())?.without_info()?;
+        query.bind("db")?.with_value(database)?;
+        for (i, (path, mut input)) in items.into_iter().enumerate() {
+            query.bind(&format!("path{}", i))?.with_value(path)?;
+            query.bind(&format!("input{}", i))?.with_resource(&mut input)?;
+        }
+
+        query.execute()?.close()?.close()?;
+        Ok(())
+    }
+
+    /// Renames the resources at `source_path` to `target_path` in the currently opened database, returning the server
+    /// info message. Renaming a directory moves all the resources in it.
+    ///
+    /// * Fails with [`InvalidName`] without contacting the server when either path is empty or contains whitespace or a
+    ///   semicolon.
+    /// * Fails with [`CommandFailed`] when no database is opened.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use basex::{Client, Result};
+    /// # fn main() -> Result<()> {
+    /// let mut client = Client::connect("localhost", 1984, "admin", "admin")?;
+    /// client.create("orbit")?.without_input()?;
+    /// client.add("lunar.xml", "<lunar/>")?;
+    /// let info = client.rename("lunar.xml", "moons/lunar.xml")?;
+    /// assert!(info.contains("renamed"));
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// [`CommandFailed`]: crate::ClientError::CommandFailed
+    /// [`InvalidName`]: crate::ClientError::InvalidName
+    pub fn rename(&mut self, source_path: &str, target_path: &str) -> Result<String> {
+        let command = Command::Rename(source_path.to_owned(), target_path.to_owned());
+        command.validate()?;
+        self.ensure_writable()?;
+        let (_, info) = self.command(&command.to_string())?;
+        Ok(info)
+    }
+
+    /// Retrieves the binary resource at `path` from the currently opened database.
+    ///
+    /// * Fails with [`InvalidName`] without contacting the server when the `path` is empty or contains whitespace or a
+    ///   semicolon.
+    /// * Fails with [`CommandFailed`] when no database is opened or no binary resource exists at the `path`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use basex::{Client, Result};
+    /// # fn main() -> Result<()> {
+    /// let mut client = Client::connect("localhost", 1984, "admin", "admin")?;
+    /// client.create("asylum")?.without_input()?;
+    /// client.store("bogdanoff", vec![0u8, 1, 2, 3])?;
+    ///
+    /// assert_eq!(vec![0u8, 1, 2, 3], client.retrieve("bogdanoff")?);
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// [`CommandFailed`]: crate::ClientError::CommandFailed
+    /// [`InvalidName`]: crate::ClientError::InvalidName
+    pub fn retrieve(&mut self, path: &str) -> Result<Vec<u8>> {
+        let command = Command::Retrieve(path.to_owned());
+        command.validate()?;
+        self.connection.send_arg(&mut command.to_string().as_bytes())?;
+        let bytes = self.connection.read_escaped()?;
+        self.connection.get_response()?;
+        Ok(bytes)
+    }
+
+    /// Serializes the documents at `path` and copies them into `out`, returning the number of bytes written. The `path`
+    /// addresses a database, optionally followed by the path of the resources in it, e.g. `factbook/countries`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use basex::{Client, Result};
+    /// # use std::fs::File;
+    /// # fn main() -> Result<()> {
+    /// let mut client = Client::connect("localhost", 1984, "admin", "admin")?;
+    /// client.create("nova")?.with_input("<nova/>")?;
+    ///
+    /// let mut file = File::create(std::env::temp_dir().join("nova.xml"))?;
+    /// let size = client.export_to("nova", &mut file)?;
+    /// assert_eq!(7, size);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn export_to<W: Write>(&mut self, path: &str, out: &mut W) -> Result<u64> {
+        self.connection
+            .send_arg(&mut format!("XQUERY collection({})", escape_xquery_string(path)).as_bytes())?;
+        let size = self.connection.copy_escaped(out)?;
+        self.connection.get_response()?;
+        Ok(size)
+    }
+
+    /// Creates a new `query` from given XQuery code.
+    ///
+    /// You then need to make a statement about collecting compiler info using either [`with_info`] or [`without_info`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use basex::{Client, Result};
+    /// # use std::io::Read;
+    /// # fn main() -> Result<()> {
+    /// let mut client = Client::connect("localhost", 1984, "admin", "admin")?;
+    ///
+    /// let info = client.create("triangle")?
+    ///     .with_input("<polygon><line></line><line></line><line></line></polygon>")?;
+    /// assert!(info.starts_with("Database 'triangle' created"));
+    ///
+    /// let query = client.query("count(/polygon/*)")?.without_info()?;
+    /// let mut result = String::new();
+    /// let mut response = query.execute()?;
+    /// response.read_to_string(&mut result)?;
+    /// assert_eq!(result, "3");
+    ///
+    /// let mut query = response.close()?;
+    /// query.close()?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// [`with_info`]: self::QueryWithOptionalInfo::with_info
+    /// [`without_info`]: self::QueryWithOptionalInfo::without_info
+    pub fn query<'a, R: AsResource<'a>>(self, query: R) -> Result<QueryWithOptionalInfo<'a, T, R>> {
+        Ok(QueryWithOptionalInfo::new(self, query))
+    }
+
+    /// Executes a [`command`](https://docs.basex.org/wiki/Commands) that produces no streamed result, returning its
+    /// result and info as strings. Retries read-only commands whose sending failed when reconnecting is on.
+    pub(crate) fn command(&mut self, command: &str) -> Result<(String, String)> {
+        match self.send_command(command) {
+            Err(ClientError::Io(error))
+                if self.retries > 0 && is_broken_connection(&error) && is_read_only(command) =>
+            {
+                self.reconnect(error)?;
+                self.send_command(command)?;
+            }
+            sent => sent?,
+        }
+        self.read_command_response()
+    }
+
+    fn try_command(&mut self, command: &str) -> Result<(String, String)> {
+        self.send_command(command)?;
+        self.read_command_response()
+    }
+
+    /// Writes the `command` out to the stream, so that a broken connection is told apart before any response is read.
+    fn send_command(&mut self, command: &str) -> Result<()> {
+        self.connection.send_arg(&mut command.as_bytes())?;
+        Ok(self.connection.flush_pending()?)
+    }
+
+    fn read_command_response(&mut self) -> Result<(String, String)> {
+        let result = self.connection.read_string()?;
+        let info = self.connection.get_response()?;
+        Ok((result, info))
+    }
+
+    /// Replaces the broken connection with a newly dialed one, failing with the last error if all attempts fail.
+    fn reconnect(&mut self, error: io::Error) -> Result<()> {
+        let dialer = match &self.dialer {
+            Some(dialer) => dialer.clone(),
+            None => return Err(error.into()),
+        };
+        let mut last_error = error.into();
+
+        for _ in 0..self.retries {
+            match dialer.dial() {
+                Ok(connection) => {
+                    self.connection = connection;
+                    self.queries.ids().clear();
+                    self.readonly = None;
+                    self.version = None;
+                    return Ok(());
+                }
+                Err(error) => last_error = error,
+            }
+        }
+
+        Err(last_error)
+    }
+}
+
+impl<T: DatabaseStream> Clone for Client<T> {
+    fn clone(&self) -> Self {
+        Self {
+            connection: self.connection.try_clone().unwrap(),
+            dialer: self.dialer.clone(),
+            retries: self.retries,
+            observer: self.observer.clone(),
+            queries: self.queries.clone(),
+            readonly: self.readonly.clone(),
+            version: self.version.clone(),
+        }
+    }
+}
+
+impl<T: DatabaseStream> Borrow<Connection<T, Authenticated>> for Client<T> {
+    fn borrow(&self) -> &Connection<T, Authenticated> {
+        &self.connection
+    }
+}
+
+impl<T: DatabaseStream> BorrowMut<Connection<T, Authenticated>> for Client<T> {
+    fn borrow_mut(&mut self) -> &mut Connection<T, Authenticated> {
+        &mut self.connection
+    }
+}
+
+pub struct QueryWithOptionalInfo<'a, T, R>
+where
+    T: DatabaseStream,
+    R: AsResource<'a>,
+{
+    phantom: PhantomData<&'a ()>,
+    client: Client<T>,
+    query: R,
+}
+
+impl<'a, T, R> QueryWithOptionalInfo<'a, T, R>
+where
+    T: DatabaseStream,
+    R: AsResource<'a>,
+{
+    fn new(client: Client<T>, query: R) -> Self {
+        Self {
+            phantom: Default::default(),
+            client,
+            query,
+        }
+    }
+
+    pub fn with_info(self) -> Result<Query<T, WithInfo>> {
+        let mut client = self.client;
+        client.set_option(ServerOption::QueryInfo, "true")?;
+        let id = Self::query(&mut client, self.query)?;
+        Ok(Query::with_info(id, client))
+    }
+
+    pub fn without_info(self) -> Result<Query<T, WithoutInfo>> {
+        let mut client = self.client;
+        client.set_option(ServerOption::QueryInfo, "false")?;
+        let id = Self::query(&mut client, self.query)?;
+        Ok(Query::without_info(id, client))
+    }
+
+    fn query(client: &mut Client<T>, query: R) -> Result<String> {
+        let mut query = query.try_into_read()?;
+        client.connection.send_cmd(CommandCode::Query as u8)?;
+        client.connection.send_arg(&mut query)?;
+        client.connection.get_response()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::client::database::tests::{BACKUPS, INFO_DB, LIST, RESOURCES};
+    use crate::client::server::tests::SERVER_INFO;
+    use crate::client::ResourceKind;
+    #[cfg(unix)]
+    use crate::tests::spawn_unix_server_replying;
+    use crate::tests::{spawn_server, spawn_server_at, spawn_server_replying, ChunkedReader, MockStream};
+    use std::io::Read;
+    use std::net::TcpListener;
+    use test_case::test_case;
+
+    impl<T> Client<T>
+    where
+        T: DatabaseStream,
+    {
+        pub(crate) fn into_inner(self) -> Connection<T, Authenticated> {
+            self.connection
+        }
+    }
+
+    #[test]
+    fn test_formats_as_debug() {
+        let _ = format!("{:?}", Client::new(Connection::failing()));
+    }
+
+    #[test]
+    fn test_connecting_fails_when_connection_is_refused() {
+        let address = TcpListener::bind("127.0.0.1:0").unwrap().local_addr().unwrap();
+
+        let actual_error = Client::connect_with_timeout(
+            "127.0.0.1",
+            address.port(),
+            "admin",
+            "admin",
+            Duration::from_millis(100),
+        )
+        .expect_err("Operation must fail");
+
+        assert!(matches!(actual_error, ClientError::Io(_)));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_connects_over_unix_socket() {
+        let path = std::env::temp_dir().join(format!("basex-client-{}.sock", std::process::id()));
+        let server = spawn_unix_server_replying(&path, vec![(1, b"\0Database 'lunar' was opened.\0\0")]);
+
+        let mut client = Client::connect_unix(&path, "admin", "admin").unwrap();
+        let info = client.open("lunar");
+        server.join().unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!("Database 'lunar' was opened.", info.unwrap());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_connecting_over_unix_socket_fails_when_socket_does_not_exist() {
+        let path = std::env::temp_dir().join("basex-client-that-does-not-exist.sock");
+
+        let actual_error = Client::connect_unix(&path, "admin", "admin").expect_err("Operation must fail");
+
+        assert!(matches!(actual_error, ClientError::Io(_)));
+    }
+
+    #[test]
+    fn test_connects_with_tee_logging_both_directions() {
+        struct SharedLog(Arc<Mutex<Vec<u8>>>);
+
+        impl Write for SharedLog {
+            fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+                self.0.lock().unwrap().write(buf)
+            }
+
+            fn flush(&mut self) -> io::Result<()> {
+                Ok(())
+            }
+        }
+
+        let (address, server) = spawn_server_replying(vec![(1, b"\0Database 'lunar' was opened.\0\0")]);
+        let log = Arc::new(Mutex::new(vec![]));
+
+        let mut client = Client::connect_with_tee(
+            "127.0.0.1",
+            address.port(),
+            "admin",
+            "admin",
+            SharedLog(Arc::clone(&log)),
+        )
+        .unwrap();
+        client.open("lunar").unwrap();
+        let _ = server.join().unwrap();
+
+        let log = String::from_utf8(log.lock().unwrap().clone()).unwrap();
+        let logged =
+            |direction: &str| -> String { log.lines().filter_map(|line| line.strip_prefix(direction)).collect() };
+        let hex = |bytes: &[u8]| -> String { bytes.iter().map(|byte| format!("{:02x}", byte)).collect() };
+        assert_eq!(
+            hex(b"admin\0af13b20af0e0b0e3517a406c42622d3d\0OPEN lunar\0"),
+            logged("> ")
+        );
+        assert_eq!(
+            hex(b"BaseX:19501915960728\0\0\0Database 'lunar' was opened.\0\0"),
+            logged("< ")
+        );
+    }
+
+    #[test]
+    fn test_reading_fails_when_read_timeout_elapses() {
+        let (address, server) = spawn_server();
+        let mut client = Client::connect("127.0.0.1", address.port(), "admin", "admin").unwrap();
+
+        client.set_read_timeout(Some(Duration::from_millis(50))).unwrap();
+        let actual_error = client.list().expect_err("Operation must fail");
+        let _ = server.join().unwrap();
+
+        assert!(matches!(
+            actual_error,
+            ClientError::Io(e) if matches!(e.kind(), ErrorKind::WouldBlock | ErrorKind::TimedOut)
+        ));
+    }
+
+    #[test]
+    fn test_connects_to_socket_address() {
+        let (address, server) = spawn_server();
+
+        let client = Client::connect_addr(address, "admin", "admin").unwrap();
+        let _ = server.join().unwrap();
+
+        assert_eq!(address, client.peer_addr().unwrap());
+    }
+
+    #[test]
+    fn test_connects_to_bracketed_ipv6_address() {
+        let (address, server) = spawn_server_at("[::1]:0", vec![]);
+
+        let client = Client::connect_addr(format!("[::1]:{}", address.port()), "admin", "admin").unwrap();
+        let _ = server.join().unwrap();
+
+        assert_eq!(address, client.peer_addr().unwrap());
+    }
+
+    #[allow(clippy::unused_unit)]
+    #[test_case("::1"; "bare")]
+    #[test_case("[::1]"; "bracketed")]
+    fn test_connects_to_ipv6_host(host: &str) {
+        let (address, server) = spawn_server_at("[::1]:0", vec![]);
+
+        let client = Client::connect(host, address.port(), "admin", "admin").unwrap();
+        let _ = server.join().unwrap();
+
+        assert_eq!(address, client.peer_addr().unwrap());
+    }
+
+    #[test]
+    fn test_addresses_match_server_socket() {
+        let (address, server) = spawn_server();
+        let client = Client::connect("127.0.0.1", address.port(), "admin", "admin").unwrap();
+
+        let server_stream = server.join().unwrap();
+
+        assert_eq!(address, client.peer_addr().unwrap());
+        assert_eq!(server_stream.peer_addr().unwrap(), client.local_addr().unwrap());
+    }
+
+    #[test]
+    fn test_write_timeout_is_set() {
+        let (address, server) = spawn_server();
+        let client = Client::connect("127.0.0.1", address.port(), "admin", "admin").unwrap();
+
+        client.set_write_timeout(Some(Duration::from_secs(1))).unwrap();
+        let _ = server.join().unwrap();
+
+        let connection: &Connection<TcpStream, Authenticated> = client.borrow();
+        assert_eq!(
+            Some(Duration::from_secs(1)),
+            connection.stream().write_timeout().unwrap()
+        );
+    }
+
+    #[test]
+    fn test_clones() {
+        let _ = Client::new(Connection::from_str("")).clone();
+    }
+
+    #[test]
+    fn test_borrows_as_connection() {
+        let _: &Connection<MockStream, Authenticated> = Client::new(Connection::from_str("test")).borrow();
+    }
+
+    #[test]
+    fn test_typed_command_is_run() {
+        let client = Client::new(Connection::from_str("\0Database 'lunar' was opened.\0\0"));
+
+        let (client, info) = client.run(Command::Open("lunar".to_owned())).unwrap().close().unwrap();
+
+        assert_eq!(
+            client.into_inner().into_inner().to_string(),
+            "OPEN lunar\u{0}".to_owned()
+        );
+        assert_eq!("Database 'lunar' was opened.", info);
+    }
+
+    #[test]
+    fn test_typed_command_fails_to_run_with_invalid_name() {
+        let stream = MockStream::from_bytes(&[]);
+        let client = Client::new(Connection::authenticated(stream.try_clone().unwrap()));
+
+        let actual_error = client
+            .run(Command::Drop(DropTarget::Database("*".to_owned())))
+            .err()
+            .unwrap();
+
+        assert!(matches!(actual_error, ClientError::InvalidName(name) if name == "*"));
+        assert!(stream.written().is_empty());
+    }
+
+    #[test]
+    fn test_typed_command_fails_to_run_with_failing_stream() {
+        let client = Client::new(Connection::failing());
+
+        let actual_error = client.run(Command::Flush).err().unwrap();
+
+        assert!(matches!(actual_error, ClientError::Io(_)));
+    }
+
+    #[test]
+    fn test_database_is_created_with_input() {
+        let mut client = Client::new(Connection::from_str("test\0"));
+
+        let info = client
+            .create("boy_sminem")
+            .unwrap()
+            .with_input("<wojak><pink_index>69</pink_index></wojak>")
+            .unwrap();
+
+        assert_eq!(
+            client.into_inner().into_inner().to_string(),
+            "\u{8}boy_sminem\u{0}<wojak><pink_index>69</pink_index></wojak>\u{0}".to_owned()
+        );
+        assert_eq!("test", info);
+    }
+
+    #[test]
+    fn test_database_is_created_without_input() {
+        let mut client = Client::new(Connection::from_str("test\0"));
+
+        let info = client.create("boy_sminem").unwrap().without_input().unwrap();
+
+        assert_eq!(
+            client.into_inner().into_inner().to_string(),
+            "\u{8}boy_sminem\u{0}\u{0}".to_owned()
+        );
+        assert_eq!("test", info);
+    }
+
+    #[test]
+    fn test_database_is_created_with_parsed_outcome() {
+        let mut client = Client::new(Connection::from_str("Database 'boy_sminem' created in 5.5 ms.\0"));
+
+        let outcome = client.create("boy_sminem").unwrap().without_input_parsed().unwrap();
+
+        assert_eq!(
+            client.into_inner().into_inner().to_string(),
+            "\u{8}boy_sminem\u{0}\u{0}".to_owned()
+        );
+        assert_eq!("boy_sminem", outcome.name());
+        assert_eq!(Duration::from_micros(5500), outcome.duration());
+    }
+
+    #[test]
+    fn test_database_is_created_with_input_and_parsed_outcome() {
+        let mut client = Client::new(Connection::from_str("Database 'boy_sminem' created in 1 s.\0"));
+
+        let outcome = client
+            .create("boy_sminem")
+            .unwrap()
+            .with_input_parsed("<wojak/>")
+            .unwrap();
+
+        assert_eq!("boy_sminem", outcome.name());
+        assert_eq!(Duration::from_secs(1), outcome.duration());
+    }
+
+    #[test]
+    fn test_database_fails_to_create_with_failing_stream() {
+        let mut client = Client::new(Connection::failing());
+
+        let actual_error = client
+            .create("boy_sminem")
+            .and_then(|command| command.without_input())
+            .expect_err("Operation must fail");
+
+        assert!(matches!(actual_error, ClientError::Io(_)));
+    }
+
+    #[test]
+    fn test_database_fails_to_create_with_invalid_name() {
+        let mut client = Client::new(Connection::from_str(""));
+
+        let actual_error = client.create("boy sminem").err().expect("Operation must fail");
+
+        assert!(matches!(actual_error, ClientError::InvalidName(name) if name == "boy sminem"));
+        assert_eq!("", client.into_inner().into_inner().to_string());
+    }
+
+    #[test]
+    fn test_database_is_opened() {
+        let mut client = Client::new(Connection::from_str("\0Database 'boy_sminem' was opened in 1.2 ms.\0"));
+
+        let info = client.open("boy_sminem").unwrap();
+
+        assert_eq!(
+            client.into_inner().into_inner().to_string(),
+            "OPEN boy_sminem\u{0}".to_owned()
+        );
+        assert_eq!("Database 'boy_sminem' was opened in 1.2 ms.", info);
+    }
+
+    #[test]
+    fn test_database_fails_to_open_when_it_does_not_exist() {
+        let mut client = Client::new(Connection::from_str("\0Database 'boy_sminem' was not found.\0\u{1}"));
+
+        let actual_error = client.open("boy_sminem").expect_err("Operation must fail");
+
+        assert!(matches!(
+            actual_error,
+            ClientError::CommandFailed { message } if message == "Database 'boy_sminem' was not found."
+        ));
+    }
+
+    #[test]
+    fn test_readonly_database_is_opened_and_closed() {
+        let mut client = Client::new(Connection::from_str("\0Database opened.\0\0\0Database closed.\0"));
+
+        client.begin_readonly("lunar").unwrap();
+        client.end_readonly().unwrap();
+
+        assert_eq!("OPEN lunar\0CLOSE\0", client.into_inner().into_inner().to_string());
+    }
+
+    #[test]
+    fn test_readonly_database_refuses_changes_until_ended() {
+        let mut client = Client::new(Connection::from_str(
+            "\0Database opened.\0\0\0Database closed.\0\0test\0",
+        ));
+
+        client.begin_readonly("lunar").unwrap();
+        let add_error = client.add("base.xml", "<base/>").expect_err("Operation must fail");
+        let rename_error = client.rename("lunar.xml", "base.xml").expect_err("Operation must fail");
+        let optimize_error = client.optimize(false).expect_err("Operation must fail");
+        let delete_error = client
+            .run_outcome(Command::Delete("lunar.xml".to_owned()))
+            .expect_err("Operation must fail");
+        client.end_readonly().unwrap();
+        client.add("base.xml", "<base/>").unwrap();
+
+        for error in [add_error, rename_error, optimize_error, delete_error] {
+            assert!(matches!(error, ClientError::ReadOnly(name) if name == "lunar"));
+        }
+        assert_eq!(
+            "OPEN lunar\0CLOSE\0\u{9}base.xml\0<base/>\0",
+            client.into_inner().into_inner().to_string()
+        );
+    }
+
+    #[test]
+    fn test_readonly_database_allows_reading() {
+        let mut client = Client::new(Connection::from_str("\0Database opened.\0\0lunar\0\0"));
+
+        client.begin_readonly("lunar").unwrap();
+        let outcome = client.run_outcome(Command::List).unwrap();
+
+        assert_eq!(Some("lunar"), outcome.result());
+    }
+
+    #[test]
+    fn test_readonly_database_is_not_read_only_when_it_fails_to_open() {
+        let mut client = Client::new(Connection::from_str("\0Database 'lunar' was not found.\0\u{1}test\0"));
+
+        let actual_error = client.begin_readonly("lunar").expect_err("Operation must fail");
+        client.end_readonly().unwrap();
+        client.store("blob", "b").unwrap();
+
+        assert!(matches!(actual_error, ClientError::CommandFailed { .. }));
+        assert_eq!(
+            "OPEN lunar\0\u{d}blob\0b\0",
+            client.into_inner().into_inner().to_string()
+        );
+    }
+
+    #[test]
+    fn test_readonly_database_fails_to_begin_with_invalid_name() {
+        let mut client = Client::new(Connection::failing());
+
+        let actual_error = client
+            .begin_readonly("lunar; DROP DB lunar")
+            .expect_err("Operation must fail");
+
+        assert!(matches!(actual_error, ClientError::InvalidName(_)));
+    }
+
+    #[test]
+    fn test_readonly_database_refuses_changes_in_clone() {
+        let mut client = Client::new(Connection::from_str("\0Database opened.\0\0"));
+
+        client.begin_readonly("lunar").unwrap();
+        let actual_error = client
+            .clone()
+            .add("base.xml", "<base/>")
+            .expect_err("Operation must fail");
+
+        assert!(matches!(actual_error, ClientError::ReadOnly(name) if name == "lunar"));
+    }
+
+    #[test]
+    fn test_readonly_database_is_not_read_only_after_reconnecting() {
+        let mut client = Client::new(Connection::authenticated(MockStream::broken()));
+        client.dialer = Some(Dialer(Arc::new(|| Ok(Connection::from_str("\0\0\0\0")))));
+        client.retries = 1;
+        client.readonly = Some("lunar".to_owned());
+
+        client.list().unwrap();
+        client.add("base.xml", "<base/>").unwrap();
+
+        assert_eq!(
+            "LIST\0\u{9}base.xml\0<base/>\0",
+            client.into_inner().into_inner().to_string()
+        );
+    }
+
+    #[test]
+    fn test_ending_readonly_without_read_view_does_nothing() {
+        let mut client = Client::new(Connection::failing());
+
+        client.end_readonly().unwrap();
+    }
+
+    #[allow(clippy::unused_unit)]
+    #[test_case("lunar base"; "space")]
+    #[test_case("lunar;DROP DB lunar"; "semicolon")]
+    #[test_case(""; "empty")]
+    fn test_database_fails_to_open_with_invalid_name(name: &str) {
+        let mut client = Client::new(Connection::from_str(""));
+
+        let actual_error = client.open(name).expect_err("Operation must fail");
+
+        assert!(matches!(actual_error, ClientError::InvalidName(n) if n == name));
+        assert_eq!("", client.into_inner().into_inner().to_string());
+    }
+
+    #[test]
+    fn test_database_fails_to_open_with_failing_stream() {
+        let mut client = Client::new(Connection::failing());
+
+        let actual_error = client.open("boy_sminem").expect_err("Operation must fail");
+
+        assert!(matches!(actual_error, ClientError::Io(_)));
+    }
+
+    #[test]
+    fn test_resource_is_renamed() {
+        let mut client = Client::new(Connection::from_str("\u{0}1 resource(s) renamed in 0.51 ms.\0"));
+
+        let info = client.rename("lunar.xml", "moons/lunar.xml").unwrap();
+
+        assert_eq!(
+            "RENAME lunar.xml moons/lunar.xml\0",
+            client.into_inner().into_inner().to_string()
+        );
+        assert_eq!("1 resource(s) renamed in 0.51 ms.", info);
+    }
+
+    #[allow(clippy::unused_unit)]
+    #[test_case("", "moons/lunar.xml"; "empty source")]
+    #[test_case("lunar.xml", " "; "blank target")]
+    #[test_case("lunar.xml", "moons/lunar base.xml"; "whitespace in target")]
+    fn test_resource_fails_to_rename_with_invalid_path(source_path: &str, target_path: &str) {
+        let mut client = Client::new(Connection::from_str(""));
+
+        let actual_error = client
+            .rename(source_path, target_path)
+            .expect_err("Operation must fail");
+
+        assert!(matches!(actual_error, ClientError::InvalidName(_)));
+        assert_eq!("", client.into_inner().into_inner().to_string());
+    }
+
+    #[test]
+    fn test_resource_fails_to_rename_without_opened_database() {
+        let mut client = Client::new(Connection::from_str("\0No database opened.\0\u{1}"));
+
+        let actual_error = client
+            .rename("lunar.xml", "moons/lunar.xml")
+            .expect_err("Operation must fail");
+
+        assert!(matches!(
+            actual_error,
+            ClientError::CommandFailed { message } if message == "No database opened."
+        ));
+    }
+
+    #[test]
+    fn test_scoped_database_is_closed_on_drop() {
+        let mut client = Client::new(Connection::from_str(concat!(
+            "\0Database 'lunar' was opened.\0\0",
+            "1\0\0\0",
+            "\0Database 'lunar' was closed.\0",
+        )));
+
+        {
+            let mut lunar = client.open_scoped("lunar").unwrap();
+            lunar.run_command("XQUERY 1").unwrap();
+        }
+
+        assert_eq!(
+            "OPEN lunar\0XQUERY 1\0CLOSE\0",
+            client.into_inner().into_inner().to_string()
+        );
+    }
+
+    #[test]
+    fn test_scoped_database_is_closed_once() {
+        let mut client = Client::new(Connection::from_str(
+            "\0Database 'lunar' was opened.\0\0\0Database 'lunar' was closed.\0",
+        ));
+
+        let info = client.open_scoped("lunar").unwrap().close().unwrap();
+
+        assert_eq!("Database 'lunar' was closed.", info);
+        assert_eq!("OPEN lunar\0CLOSE\0", client.into_inner().into_inner().to_string());
+    }
+
+    #[test]
+    fn test_scoped_database_fails_to_open() {
+        let mut client = Client::new(Connection::from_str("\0Database 'lunar' was not found.\0\u{1}"));
+
+        let actual_error = client.open_scoped("lunar").expect_err("Operation must fail");
+
+        assert!(matches!(actual_error, ClientError::CommandFailed { .. }));
+        assert_eq!("OPEN lunar\0", client.into_inner().into_inner().to_string());
+    }
+
+    #[test]
+    fn test_database_is_copied() {
+        let mut client = Client::new(Connection::from_str(
+            "\0Database 'lunar' was copied to 'lunar-copy' in 2.05 ms.\0",
+        ));
+
+        let info = client.copy_database("lunar", "lunar-copy").unwrap();
+
+        assert_eq!("COPY lunar lunar-copy\0", client.into_inner().into_inner().to_string());
+        assert_eq!("Database 'lunar' was copied to 'lunar-copy' in 2.05 ms.", info);
+    }
+
+    #[allow(clippy::unused_unit)]
+    #[test_case("\0Database 'lunar' was not found.\0\u{1}"; "missing source")]
+    #[test_case("\0Database 'lunar-copy' already exists.\0\u{1}"; "existing target")]
+    fn test_database_fails_to_copy(response: &str) {
+        let mut client = Client::new(Connection::from_str(response));
+
+        let actual_error = client
+            .copy_database("lunar", "lunar-copy")
+            .expect_err("Operation must fail");
+
+        assert!(matches!(actual_error, ClientError::CommandFailed { .. }));
+    }
+
+    #[test]
+    fn test_database_fails_to_copy_with_invalid_name() {
+        let mut client = Client::new(Connection::from_str(""));
+
+        let actual_error = client
+            .copy_database("lunar", "lunar base")
+            .expect_err("Operation must fail");
+
+        assert!(matches!(actual_error, ClientError::InvalidName(name) if name == "lunar base"));
+        assert_eq!("", client.into_inner().into_inner().to_string());
+    }
+
+    #[test]
+    fn test_database_is_dropped() {
+        let mut client = Client::new(Connection::from_str("\0Database 'boy_sminem' was dropped.\0"));
+
+        let info = client.drop_database("boy_sminem").unwrap();
+
+        assert_eq!(
+            client.into_inner().into_inner().to_string(),
+            "DROP DB boy_sminem\u{0}".to_owned()
+        );
+        assert_eq!("Database 'boy_sminem' was dropped.", info);
+    }
+
+    #[test]
+    fn test_database_fails_to_drop_when_it_does_not_exist() {
+        let mut client = Client::new(Connection::from_str("\0Database 'boy_sminem' was not found.\0\u{1}"));
+
+        let actual_error = client.drop_database("boy_sminem").expect_err("Operation must fail");
+
+        assert!(matches!(
+            actual_error,
+            ClientError::CommandFailed { message } if message == "Database 'boy_sminem' was not found."
+        ));
+    }
+
+    #[test]
+    fn test_database_fails_to_drop_with_invalid_name() {
+        let mut client = Client::new(Connection::from_str(""));
+
+        let actual_error = client.drop_database("lunar/base").expect_err("Operation must fail");
+
+        assert!(matches!(actual_error, ClientError::InvalidName(name) if name == "lunar/base"));
+        assert_eq!("", client.into_inner().into_inner().to_string());
+    }
+
+    #[allow(clippy::unused_unit)]
+    #[test_case(DropTarget::Database("lunar".to_owned()), "DROP DB lunar\0")]
+    #[test_case(DropTarget::Backup("lunar-2021-03-12-10-11-05".to_owned()), "DROP BACKUP lunar-2021-03-12-10-11-05\0")]
+    #[test_case(DropTarget::User("wojak".to_owned()), "DROP USER wojak\0")]
+    #[test_case(DropTarget::Module("http://expath.org/ns/crypto".to_owned()), "REPO DELETE http://expath.org/ns/crypto\0")]
+    fn test_target_is_dropped(target: DropTarget, expected_buffer: &str) {
+        let mut client = Client::new(Connection::from_str("\0Dropped.\0"));
+
+        let info = client.drop(target).unwrap();
+
+        assert_eq!(expected_buffer, client.into_inner().into_inner().to_string());
+        assert_eq!("Dropped.", info);
+    }
+
+    #[test]
+    fn test_target_fails_to_drop_with_invalid_name() {
+        let mut client = Client::new(Connection::from_str(""));
+
+        let actual_error = client
+            .drop(DropTarget::User("wojak; DROP DB lunar".to_owned()))
+            .expect_err("Operation must fail");
+
+        assert!(matches!(actual_error, ClientError::InvalidName(name) if name == "wojak; DROP DB lunar"));
+        assert_eq!("", client.into_inner().into_inner().to_string());
+    }
+
+    #[test]
+    fn test_target_fails_to_drop_when_it_does_not_exist() {
+        let mut client = Client::new(Connection::from_str("\0User 'wojak' was not found.\0\u{1}"));
+
+        let actual_error = client
+            .drop(DropTarget::User("wojak".to_owned()))
+            .expect_err("Operation must fail");
+
+        assert!(matches!(
+            actual_error,
+            ClientError::CommandFailed { message } if message == "User 'wojak' was not found."
+        ));
+    }
+
+    #[test]
+    fn test_database_is_optimized() {
+        let mut client = Client::new(Connection::from_str("\0Database 'lunar' optimized in 5.0 ms.\0\0"));
+
+        let duration = client.optimize(false).unwrap();
+
+        assert_eq!(client.into_inner().into_inner().to_string(), "OPTIMIZE\u{0}".to_owned());
+        assert_eq!(Duration::from_millis(5), duration);
+    }
+
+    #[test]
+    fn test_database_is_optimized_fully() {
+        let mut client = Client::new(Connection::from_str("\0Database 'lunar' optimized in 5.0 ms.\0\0"));
+
+        client.optimize(true).unwrap();
+
+        assert_eq!(
+            client.into_inner().into_inner().to_string(),
+            "OPTIMIZE ALL\u{0}".to_owned()
+        );
+    }
+
+    #[test]
+    fn test_database_fails_to_optimize_when_none_is_opened() {
+        let mut client = Client::new(Connection::from_str("\0No database opened.\0\u{1}"));
+
+        let actual_error = client.optimize(false).expect_err("Operation must fail");
+
+        assert!(matches!(actual_error, ClientError::CommandFailed { message } if message == "No database opened."));
+    }
+
+    #[test]
+    fn test_database_is_flushed() {
+        let mut client = Client::new(Connection::from_str("\0Database 'lunar' was flushed.\0\0"));
+
+        client.flush().unwrap();
+
+        assert_eq!(client.into_inner().into_inner().to_string(), "FLUSH\u{0}".to_owned());
+    }
+
+    #[test]
+    fn test_database_fails_to_flush_when_none_is_opened() {
+        let mut client = Client::new(Connection::from_str("\0No database opened.\0\u{1}"));
+
+        let actual_error = client.flush().expect_err("Operation must fail");
+
+        assert!(matches!(actual_error, ClientError::CommandFailed { message } if message == "No database opened."));
+    }
+
+    #[test]
+    fn test_database_is_backed_up() {
+        let mut client = Client::new(Connection::from_str("\0Database 'lunar' was backed up in 5.0 ms.\0\0"));
+
+        let info = client.backup("lunar").unwrap();
+
+        assert_eq!(
+            client.into_inner().into_inner().to_string(),
+            "CREATE BACKUP lunar\u{0}".to_owned()
+        );
+        assert_eq!("Database 'lunar' was backed up in 5.0 ms.", info);
+    }
+
+    #[test]
+    fn test_database_fails_to_back_up_when_it_does_not_exist() {
+        let mut client = Client::new(Connection::from_str("\0Database 'lunar' was not found.\0\u{1}"));
+
+        let actual_error = client.backup("lunar").expect_err("Operation must fail");
+
+        assert!(matches!(actual_error, ClientError::CommandFailed { .. }));
+    }
+
+    #[allow(clippy::unused_unit)]
+    #[test_case("*"; "glob")]
+    #[test_case("lunar base"; "whitespace")]
+    #[test_case(""; "empty")]
+    fn test_database_fails_to_back_up_with_invalid_name(name: &str) {
+        let mut client = Client::new(Connection::from_str(""));
+
+        let actual_error = client.backup(name).expect_err("Operation must fail");
+
+        assert!(matches!(actual_error, ClientError::InvalidName(actual) if actual == name));
+        assert_eq!("", client.into_inner().into_inner().to_string());
+    }
+
+    #[test]
+    fn test_database_is_restored() {
+        let mut client = Client::new(Connection::from_str(
+            "\0'lunar-2021-03-12-10-11-05' restored in 5.0 ms.\0\0",
+        ));
+
+        let info = client.restore("lunar-2021-03-12-10-11-05").unwrap();
+
+        assert_eq!(
+            client.into_inner().into_inner().to_string(),
+            "RESTORE lunar-2021-03-12-10-11-05\u{0}".to_owned()
+        );
+        assert_eq!("'lunar-2021-03-12-10-11-05' restored in 5.0 ms.", info);
+    }
+
+    #[test]
+    fn test_database_fails_to_restore_without_backup() {
+        let mut client = Client::new(Connection::from_str("\0No backups found for 'lunar'.\0\u{1}"));
+
+        let actual_error = client.restore("lunar").expect_err("Operation must fail");
+
+        assert!(matches!(actual_error, ClientError::CommandFailed { .. }));
+    }
+
+    #[allow(clippy::unused_unit)]
+    #[test_case("*"; "glob")]
+    #[test_case("lunar base"; "whitespace")]
+    #[test_case(""; "empty")]
+    fn test_database_fails_to_restore_with_invalid_name(name: &str) {
+        let mut client = Client::new(Connection::from_str(""));
+
+        let actual_error = client.restore(name).expect_err("Operation must fail");
+
+        assert!(matches!(actual_error, ClientError::InvalidName(actual) if actual == name));
+        assert_eq!("", client.into_inner().into_inner().to_string());
+    }
+
+    #[test]
+    fn test_backups_are_listed() {
+        let mut client = Client::new(Connection::from_str(format!("{}\0\0", BACKUPS)));
+
+        let backups = client.list_backups().unwrap();
+
+        assert_eq!(
+            client.into_inner().into_inner().to_string(),
+            "SHOW BACKUPS\u{0}".to_owned()
+        );
+        assert_eq!(
+            vec!["factbook-2021-03-12-10-11-05", "lambada-2021-03-13-08-00-00"],
+            backups
+        );
+    }
+
+    #[test]
+    fn test_databases_are_listed() {
+        let mut client = Client::new(Connection::from_str(format!("{}\0\0", LIST)));
+
+        let databases = client.list().unwrap();
+
+        assert_eq!(client.into_inner().into_inner().to_string(), "LIST\u{0}".to_owned());
+        assert_eq!(
+            vec!["factbook", "lambada"],
+            databases.iter().map(|d| d.name()).collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn test_resources_are_listed() {
+        let mut client = Client::new(Connection::from_str(format!("{}\0\0", RESOURCES)));
+
+        let resources = client.list_resources("factbook").unwrap();
+
+        assert_eq!(
+            client.into_inner().into_inner().to_string(),
+            "LIST factbook\u{0}".to_owned()
+        );
+        assert_eq!(
+            vec![ResourceKind::Xml, ResourceKind::Binary],
+            resources.iter().map(|r| r.kind()).collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn test_resources_fail_to_list_with_invalid_name() {
+        let mut client = Client::new(Connection::failing());
+
+        let actual_error = client
+            .list_resources("factbook; DROP DB factbook")
+            .expect_err("Operation must fail");
+
+        assert!(matches!(actual_error, ClientError::InvalidName(_)));
+    }
+
+    #[test]
+    fn test_command_runs_with_result() {
+        let mut client = Client::new(Connection::from_str(format!("{}\0\0", LIST)));
+
+        let (result, info) = client.run_command("LIST").unwrap();
+
+        assert_eq!(client.into_inner().into_inner().to_string(), "LIST\u{0}".to_owned());
+        assert_eq!(LIST, result);
+        assert_eq!("", info);
+    }
+
+    #[test]
+    fn test_command_runs_with_info() {
+        let mut client = Client::new(Connection::from_str("\0Database 'lunar' was closed.\0"));
+
+        let (result, info) = client.run_command("CLOSE").unwrap();
+
+        assert_eq!(client.into_inner().into_inner().to_string(), "CLOSE\u{0}".to_owned());
+        assert_eq!("", result);
+        assert_eq!("Database 'lunar' was closed.", info);
+    }
+
+    #[test]
+    fn test_command_fails_to_run_when_rejected_by_server() {
+        let mut client = Client::new(Connection::from_str("\0Unknown command: LISTT.\0\u{1}"));
+
+        let actual_error = client.run_command("LISTT").expect_err("Operation must fail");
+
+        assert!(matches!(
+            actual_error,
+            ClientError::CommandFailed { message } if message == "Unknown command: LISTT."
+        ));
+    }
+
+    #[test]
+    fn test_command_runs_with_body_outcome() {
+        let mut client = Client::new(Connection::from_str(format!("{}\0\0", LIST)));
+
+        let outcome = client.run_outcome(Command::List).unwrap();
+
+        assert_eq!(client.into_inner().into_inner().to_string(), "LIST\u{0}".to_owned());
+        assert_eq!(
+            CommandOutcome::Body {
+                result: LIST.to_owned(),
+                info: "".to_owned()
+            },
+            outcome
+        );
+    }
+
+    #[test]
+    fn test_command_fails_to_run_with_outcome_with_invalid_name() {
+        let mut client = Client::new(Connection::from_str(""));
+
+        let actual_error = client
+            .run_outcome(Command::Drop(DropTarget::Database("*".to_owned())))
+            .expect_err("Operation must fail");
+
+        assert!(matches!(actual_error, ClientError::InvalidName(name) if name == "*"));
+        assert_eq!("", client.into_inner().into_inner().to_string());
+    }
+
+    #[test]
+    fn test_command_runs_with_status_outcome() {
+        let mut client = Client::new(Connection::from_str("\0Database 'lunar' was opened.\0"));
+
+        let outcome = client.run_outcome(Command::Open("lunar".to_owned())).unwrap();
+
+        assert_eq!(
+            client.into_inner().into_inner().to_string(),
+            "OPEN lunar\u{0}".to_owned()
+        );
+        assert_eq!(
+            CommandOutcome::Status("Database 'lunar' was opened.".to_owned()),
+            outcome
+        );
+    }
+
+    #[test]
+    fn test_option_is_set() {
+        let mut client = Client::new(Connection::from_str("\0\0"));
+
+        client.set_option(ServerOption::Serializer, "indent=yes").unwrap();
+
+        assert_eq!(
+            client.into_inner().into_inner().to_string(),
+            "SET SERIALIZER indent=yes\u{0}".to_owned()
+        );
+    }
+
+    #[allow(clippy::unused_unit)]
+    #[test_case(ServerOption::Other("chop true;DROP DB lunar".to_owned()), "true"; "option name")]
+    #[test_case(ServerOption::Chop, "true;DROP DB lunar"; "semicolon in value")]
+    #[test_case(ServerOption::Chop, "true\nDROP DB lunar"; "line break in value")]
+    fn test_option_fails_to_set_when_invalid(option: ServerOption, value: &str) {
+        let mut client = Client::new(Connection::from_str(""));
+
+        let actual_error = client.set_option(option, value).expect_err("Operation must fail");
+
+        assert!(matches!(actual_error, ClientError::InvalidName(_)));
+        assert_eq!("", client.into_inner().into_inner().to_string());
+    }
+
+    #[test]
+    fn test_create_options_fail_to_apply_when_invalid() {
+        let mut client = Client::new(Connection::from_str(""));
+        let options = CreateOptions::new()
+            .chop(false)
+            .set(ServerOption::Other("stripns".to_owned()), "true\rDROP DB lunar");
+
+        let actual_error = client
+            .with_create_options(&options, |client| client.create("lunar")?.without_input())
+            .expect_err("Operation must fail");
+
+        assert!(matches!(actual_error, ClientError::InvalidName(_)));
+        assert_eq!("", client.into_inner().into_inner().to_string());
+    }
+
+    #[test]
+    fn test_option_is_got() {
+        let mut client = Client::new(Connection::from_str("CHOP: true\0\0"));
+
+        let value = client.get_option(ServerOption::Chop).unwrap();
+
+        assert_eq!(client.into_inner().into_inner().to_string(), "GET CHOP\u{0}".to_owned());
+        assert_eq!("true", value);
+    }
+
+    #[test]
+    fn test_option_fails_to_get_without_value() {
+        let mut client = Client::new(Connection::from_str("CHOP\0\0"));
+
+        let actual_error = client.get_option(ServerOption::Chop).expect_err("Operation must fail");
+
+        assert!(matches!(actual_error, ClientError::Protocol(_)));
+    }
+
+    #[test]
+    fn test_option_fails_to_set_when_unknown() {
+        let mut client = Client::new(Connection::from_str("\0Unknown option 'LUNAR'.\0\u{1}"));
+
+        let actual_error = client
+            .set_option(ServerOption::Other("lunar".to_owned()), "true")
+            .expect_err("Operation must fail");
+
+        assert!(matches!(
+            actual_error,
+            ClientError::CommandFailed { message } if message == "Unknown option 'LUNAR'."
+        ));
+    }
+
+    #[test]
+    fn test_create_options_are_applied_around_create_and_restored() {
+        let mut client = Client::new(Connection::from_str(
+            "CHOP: true\0\0\0\0\0\0FTINDEX: false\0\0\0\0\0\0Database 'lunar' created.\0\0\0\0\0\0\0",
+        ));
+        let options = CreateOptions::new().chop(false).full_text_index(true);
+
+        let info = client
+            .with_create_options(&options, |client| client.create("lunar")?.with_input("<x/>"))
+            .unwrap();
+
+        assert_eq!(
+            client.into_inner().into_inner().to_string(),
+            "GET CHOP\0SET CHOP false\0GET FTINDEX\0SET FTINDEX true\0\
+            \u{8}lunar\0<x/>\0\
+            SET FTINDEX false\0SET CHOP true\0"
+                .to_owned()
+        );
+        assert_eq!("Database 'lunar' created.", info);
+    }
+
+    #[test]
+    fn test_create_options_are_restored_when_run_fails() {
+        let mut client = Client::new(Connection::from_str("CHOP: true\0\0\0\0\0\0Invalid XML.\0\u{1}\0\0"));
+        let options = CreateOptions::new().chop(false);
+
+        let actual_error = client
+            .with_create_options(&options, |client| client.create("lunar")?.with_input("<x"))
+            .expect_err("Operation must fail");
+
+        assert!(matches!(actual_error, ClientError::CommandFailed { message } if message == "Invalid XML."));
+        assert!(client
+            .into_inner()
+            .into_inner()
+            .to_string()
+            .ends_with("SET CHOP true\0"));
+    }
+
+    #[test]
+    fn test_create_options_set_before_failure_are_restored() {
+        let mut client = Client::new(Connection::from_str(
+            "CHOP: true\0\0\0\0\0\0\0Unknown option 'LUNAR'.\0\u{1}\0\0",
+        ));
+        let options = CreateOptions::new()
+            .chop(false)
+            .set(ServerOption::Other("lunar".to_owned()), "true");
+
+        let actual_error = client
+            .with_create_options(&options, |_| Ok(()))
+            .expect_err("Operation must fail");
+
+        assert!(matches!(actual_error, ClientError::CommandFailed { .. }));
+        assert_eq!(
+            client.into_inner().into_inner().to_string(),
+            "GET CHOP\0SET CHOP false\0GET LUNAR\0SET CHOP true\0".to_owned()
+        );
+    }
+
+    #[test]
+    fn test_pings() {
+        let mut client = Client::new(Connection::from_str("\0Query executed in 0.1 ms.\0"));
+
+        client.ping().unwrap();
+
+        assert_eq!(
+            client.into_inner().into_inner().to_string(),
+            "XQUERY ()\u{0}".to_owned()
+        );
+    }
+
+    #[test]
+    fn test_ping_fails_with_failing_stream() {
+        let mut client = Client::new(Connection::failing());
+
+        let actual_error = client.ping().expect_err("Operation must fail");
+
+        assert!(matches!(actual_error, ClientError::Io(_)));
+    }
+
+    #[test]
+    fn test_ping_does_not_reconnect() {
+        let mut client = Client::new(Connection::from_str(""));
+        client.dialer = Some(Dialer(Arc::new(|| Ok(Connection::from_str("\0\0")))));
+        client.retries = 1;
+
+        let actual_error = client.ping().expect_err("Operation must fail");
+
+        assert!(matches!(actual_error, ClientError::Io(e) if e.kind() == ErrorKind::UnexpectedEof));
+    }
+
+    #[test]
+    fn test_closes_all_queries() {
+        let client = Client::new(Connection::from_str("\0\0\0query_1\0\0\0\0\0query_2\0\0\0\0\0\0"));
+        let first = client.query("1").unwrap().without_info().unwrap();
+        let first_client: &Client<MockStream> = first.borrow();
+        let second = first_client.clone().query("2").unwrap().without_info().unwrap();
+        let second_client: &Client<MockStream> = second.borrow();
+        let mut client = second_client.clone();
+
+        client.close_all_queries().unwrap();
+
+        assert!(client
+            .into_inner()
+            .into_inner()
+            .to_string()
+            .ends_with("\u{2}query_1\u{0}\u{2}query_2\u{0}"));
+        assert!(!second_client.forget_query("query_2"));
+    }
+
+    #[test]
+    fn test_closed_query_is_not_closed_again() {
+        let client = Client::new(Connection::from_str("\0\0\0query_1\0\0\0\0"));
+        let query = client.query("1").unwrap().without_info().unwrap();
+        let mut client = query.close().unwrap();
+
+        client.close_all_queries().unwrap();
+
+        assert_eq!(
+            "SET QUERYINFO false\u{0}\u{0}1\u{0}\u{2}query_1\u{0}",
+            client.into_inner().into_inner().to_string()
+        );
+    }
+
+    #[test]
+    fn test_databases_fail_to_list_with_failing_stream() {
+        let mut client = Client::new(Connection::failing());
+
+        let actual_error = client.list().expect_err("Operation must fail");
+
+        assert!(matches!(actual_error, ClientError::Io(_)));
     }
-}
 
-impl<T: DatabaseStream> Clone for Client<T> {
-    fn clone(&self) -> Self {
-        Self {
-            connection: self.connection.try_clone().unwrap(),
-        }
+    #[test]
+    fn test_command_is_retried_after_reconnecting() {
+        let mut client = Client::new(Connection::authenticated(MockStream::broken()));
+        client.dialer = Some(Dialer(Arc::new(|| Ok(Connection::from_str("\0\0\0")))));
+        client.retries = 1;
+
+        let databases = client.list().unwrap();
+
+        assert_eq!(client.into_inner().into_inner().to_string(), "LIST\u{0}".to_owned());
+        assert!(databases.is_empty());
     }
-}
 
-impl<T: DatabaseStream> Borrow<Connection<T, Authenticated>> for Client<T> {
-    fn borrow(&self) -> &Connection<T, Authenticated> {
-        &self.connection
+    #[test]
+    fn test_command_is_not_retried_without_reconnect() {
+        let mut client = Client::new(Connection::authenticated(MockStream::broken()));
+        client.dialer = Some(Dialer(Arc::new(|| Ok(Connection::from_str("\0\0\0")))));
+
+        let actual_error = client.list().expect_err("Operation must fail");
+
+        assert!(matches!(actual_error, ClientError::Io(e) if e.kind() == ErrorKind::BrokenPipe));
     }
-}
 
-impl<T: DatabaseStream> BorrowMut<Connection<T, Authenticated>> for Client<T> {
-    fn borrow_mut(&mut self) -> &mut Connection<T, Authenticated> {
-        &mut self.connection
+    #[test]
+    fn test_command_is_not_retried_when_connection_is_not_broken() {
+        let mut client = Client::new(Connection::failing());
+        client.dialer = Some(Dialer(Arc::new(|| Ok(Connection::failing()))));
+        client.retries = 1;
+
+        let actual_error = client.list().expect_err("Operation must fail");
+
+        assert!(matches!(actual_error, ClientError::Io(e) if e.kind() == ErrorKind::Other));
     }
-}
 
-pub struct QueryWithOptionalInfo<'a, T, R>
-where
-    T: DatabaseStream,
-    R: AsResource<'a>,
-{
-    phantom: PhantomData<&'a ()>,
-    client: Client<T>,
-    query: R,
-}
+    #[test]
+    fn test_command_is_not_retried_once_response_fails() {
+        let mut client = Client::new(Connection::from_str(""));
+        client.dialer = Some(Dialer(Arc::new(|| Ok(Connection::from_str("\0\0\0")))));
+        client.retries = 1;
 
-impl<'a, T, R> QueryWithOptionalInfo<'a, T, R>
-where
-    T: DatabaseStream,
-    R: AsResource<'a>,
-{
-    fn new(client: Client<T>, query: R) -> Self {
-        Self {
-            phantom: Default::default(),
-            client,
-            query,
-        }
+        let actual_error = client.list().expect_err("Operation must fail");
+
+        assert!(matches!(actual_error, ClientError::Io(e) if e.kind() == ErrorKind::UnexpectedEof));
     }
 
-    pub fn with_info(self) -> Result<Query<T, WithInfo>> {
-        let (mut client, _) = self.client.execute("SET QUERYINFO true")?.close()?;
-        let id = Self::query(&mut client, self.query)?;
-        Ok(Query::with_info(id, client))
+    #[allow(clippy::unused_unit)]
+    #[test_case("OPEN lunar"; "session state")]
+    #[test_case("DROP DB lunar"; "not idempotent")]
+    #[test_case("XQUERY db:drop('lunar')"; "updating query")]
+    #[test_case("INFO DB"; "opened database")]
+    #[test_case("GET CHOP"; "session option")]
+    fn test_command_changing_or_depending_on_session_is_not_retried(command: &str) {
+        let mut client = Client::new(Connection::authenticated(MockStream::broken()));
+        client.dialer = Some(Dialer(Arc::new(|| Ok(Connection::from_str("\0\0\0")))));
+        client.retries = 1;
+
+        let actual_error = client.run_command(command).expect_err("Operation must fail");
+
+        assert!(matches!(actual_error, ClientError::Io(e) if e.kind() == ErrorKind::BrokenPipe));
     }
 
-    pub fn without_info(self) -> Result<Query<T, WithoutInfo>> {
-        let (mut client, _) = self.client.execute("SET QUERYINFO false")?.close()?;
-        let id = Self::query(&mut client, self.query)?;
-        Ok(Query::without_info(id, client))
+    #[allow(clippy::unused_unit)]
+    #[test_case("LIST", true)]
+    #[test_case("list lunar", true)]
+    #[test_case("INFO", true)]
+    #[test_case("SHOW BACKUPS", true)]
+    #[test_case("INFO DB", false)]
+    #[test_case("BACKUP lunar", false)]
+    #[test_case("RENAME a.xml b.xml", false)]
+    fn test_tells_read_only_commands(command: &str, expected: bool) {
+        assert_eq!(expected, is_read_only(command));
     }
 
-    fn query(client: &mut Client<T>, query: R) -> Result<String> {
-        client.connection.send_cmd(Command::Query as u8)?;
-        client.connection.send_arg(&mut query.into_read())?;
-        client.connection.get_response()
+    #[test]
+    fn test_reconnecting_fails_when_all_dials_fail() {
+        let mut client = Client::new(Connection::authenticated(MockStream::broken()));
+        client.dialer = Some(Dialer(Arc::new(|| {
+            Err(io::Error::from(ErrorKind::ConnectionRefused).into())
+        })));
+        client.retries = 3;
+
+        let actual_error = client.list().expect_err("Operation must fail");
+
+        assert!(matches!(actual_error, ClientError::Io(e) if e.kind() == ErrorKind::ConnectionRefused));
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use crate::tests::MockStream;
-    use crate::ClientError;
+    #[test]
+    fn test_reconnecting_fails_without_dialer() {
+        let mut client = Client::new(Connection::authenticated(MockStream::broken()));
+        client.retries = 1;
 
-    impl<T> Client<T>
-    where
-        T: DatabaseStream,
-    {
-        pub(crate) fn into_inner(self) -> Connection<T, Authenticated> {
-            self.connection
-        }
+        let actual_error = client.list().expect_err("Operation must fail");
+
+        assert!(matches!(actual_error, ClientError::Io(e) if e.kind() == ErrorKind::BrokenPipe));
     }
 
     #[test]
-    fn test_formats_as_debug() {
-        format!("{:?}", Client::new(Connection::failing()));
+    fn test_server_info_is_read() {
+        let mut client = Client::new(Connection::from_str(format!("{}\0\0\0", SERVER_INFO)));
+
+        let info = client.info().unwrap();
+
+        assert_eq!(client.into_inner().into_inner().to_string(), "INFO\u{0}".to_owned());
+        assert_eq!(Some("9.6.4".to_owned()), info.version());
     }
 
     #[test]
-    fn test_clones() {
-        let _ = Client::new(Connection::from_str("")).clone();
+    fn test_server_version_is_parsed_from_info_once() {
+        let info = "General Information\n Version: 10.7\n Used Memory: 62 MB\n";
+        let mut client = Client::new(Connection::from_str(format!("{}\0\0\0", info)));
+
+        let version = client.server_version().unwrap();
+        let cached_version = client.clone().server_version().unwrap();
+
+        assert_eq!(Version::parse("10.7"), Some(version.clone()));
+        assert_eq!(version, cached_version);
+        assert_eq!("INFO\u{0}", client.into_inner().into_inner().to_string());
     }
 
     #[test]
-    fn test_borrows_as_connection() {
-        let _: &Connection<MockStream, Authenticated> = Client::new(Connection::from_str("test")).borrow();
+    fn test_server_version_fails_when_not_recognized() {
+        let mut client = Client::new(Connection::from_str("General Information\n Version: unknown\n\0\0\0"));
+
+        let actual_error = client.server_version().expect_err("Operation must fail");
+
+        assert!(matches!(actual_error, ClientError::Protocol(_)));
     }
 
     #[test]
-    fn test_database_is_created_with_input() {
-        let mut client = Client::new(Connection::from_str("test\0"));
+    fn test_server_info_fails_with_failing_stream() {
+        let mut client = Client::new(Connection::failing());
 
-        let info = client
-            .create("boy_sminem")
-            .unwrap()
-            .with_input("<wojak><pink_index>69</pink_index></wojak>")
-            .unwrap();
+        let actual_error = client.info().expect_err("Operation must fail");
 
-        assert_eq!(
-            client.into_inner().into_inner().to_string(),
-            "\u{8}boy_sminem\u{0}<wojak><pink_index>69</pink_index></wojak>\u{0}".to_owned()
-        );
-        assert_eq!("test", info);
+        assert!(matches!(actual_error, ClientError::Io(_)));
     }
 
     #[test]
-    fn test_database_is_created_without_input() {
-        let mut client = Client::new(Connection::from_str("test\0"));
+    fn test_database_stats_are_read() {
+        let mut client = Client::new(Connection::from_str(format!("{}\0\0\0", INFO_DB)));
 
-        let info = client.create("boy_sminem").unwrap().without_input().unwrap();
+        let stats = client.info_database().unwrap();
 
-        assert_eq!(
-            client.into_inner().into_inner().to_string(),
-            "\u{8}boy_sminem\u{0}\u{0}".to_owned()
-        );
-        assert_eq!("test", info);
+        assert_eq!(client.into_inner().into_inner().to_string(), "INFO DB\u{0}".to_owned());
+        assert_eq!("factbook", stats.name());
     }
 
     #[test]
-    fn test_database_fails_to_create_with_failing_stream() {
-        let mut client = Client::new(Connection::failing());
+    fn test_database_stats_fail_without_open_database() {
+        let mut client = Client::new(Connection::from_str("\0No database opened.\0\u{1}"));
 
-        let actual_error = client.create("boy_sminem").err().expect("Operation must fail");
+        let actual_error = client.info_database().expect_err("Operation must fail");
 
-        assert!(matches!(actual_error, ClientError::Io(_)));
+        assert!(matches!(actual_error, ClientError::CommandFailed { message } if message == "No database opened."));
     }
 
     #[test]
@@ -466,6 +3211,171 @@ mod tests {
         assert!(matches!(actual_error, ClientError::Io(_)));
     }
 
+    #[test]
+    fn test_resource_is_stored_from_owned_bytes() {
+        let mut client = Client::new(Connection::from_str("test\0"));
+
+        let info = client.store("blob", vec![0u8, 1, 2, 0xFF]).unwrap();
+
+        assert_eq!(
+            b"\x0dblob\0\xFF\0\x01\x02\xFF\xFF\0".to_vec(),
+            client.into_inner().into_inner().written()
+        );
+        assert_eq!("test", info);
+    }
+
+    #[test]
+    fn test_resource_is_stored_from_borrowed_bytes() {
+        let mut client = Client::new(Connection::from_str("test\0"));
+        let blob = vec![0u8, 1, 2, 0xFF];
+
+        let info = client.store("blob", &blob).unwrap();
+
+        assert_eq!(
+            b"\x0dblob\0\xFF\0\x01\x02\xFF\xFF\0".to_vec(),
+            client.into_inner().into_inner().written()
+        );
+        assert_eq!("test", info);
+    }
+
+    #[test]
+    fn test_resource_fails_to_store_from_missing_file_without_sending_anything() {
+        let mut client = Client::new(Connection::from_str("\0Database 'lunar' was opened.\0"));
+        let path = std::env::temp_dir().join("basex-resource-that-does-not-exist.bin");
+
+        let actual_error = client.store("blob", path.as_path()).expect_err("Operation must fail");
+        client.open("lunar").unwrap();
+
+        assert!(matches!(actual_error, ClientError::Io(e) if e.kind() == ErrorKind::NotFound));
+        assert_eq!("OPEN lunar\u{0}", client.into_inner().into_inner().to_string());
+    }
+
+    #[test]
+    fn test_resources_fail_to_add_from_missing_file_without_sending_anything() {
+        let mut client = Client::new(Connection::from_str(""));
+        let items = vec![
+            ("lunar.xml".to_owned(), std::path::PathBuf::from(file!())),
+            (
+                "base.xml".to_owned(),
+                std::env::temp_dir().join("basex-resource-that-does-not-exist.xml"),
+            ),
+        ];
+
+        let actual_error = client.add_many(items).expect_err("Operation must fail");
+
+        assert!(matches!(actual_error, ClientError::Io(e) if e.kind() == ErrorKind::NotFound));
+        assert_eq!("", client.into_inner().into_inner().to_string());
+    }
+
+    #[test]
+    fn test_database_fails_to_create_from_missing_file_with_rejected_input() {
+        let mut client = Client::new(Connection::from_str("\0\u{1}"));
+        let path = std::env::temp_dir().join("basex-resource-that-does-not-exist.xml");
+
+        let actual_error = client
+            .create("lunar")
+            .unwrap()
+            .with_input(path)
+            .expect_err("Operation must fail");
+
+        assert!(matches!(actual_error, ClientError::Io(e) if e.kind() == ErrorKind::NotFound));
+        assert_eq!("\u{8}lunar\u{0}<\u{0}", client.into_inner().into_inner().to_string());
+    }
+
+    #[cfg(feature = "bytes")]
+    #[test]
+    fn test_resource_is_stored_from_bytes() {
+        let mut client = Client::new(Connection::from_str("test\0"));
+
+        let info = client
+            .store("blob", bytes::Bytes::from_static(&[0u8, 1, 2, 0xFF]))
+            .unwrap();
+
+        assert_eq!(
+            b"\x0dblob\0\xFF\0\x01\x02\xFF\xFF\0".to_vec(),
+            client.into_inner().into_inner().written()
+        );
+        assert_eq!("test", info);
+    }
+
+    #[test]
+    fn test_resource_is_retrieved() {
+        let mut client = Client::new(Connection::from_bytes(
+            b"\xFF\0\x01\x02\x03\xFF\xFF\xFF\0\0Query executed in 0.1 ms.\0\0",
+        ));
+
+        let bytes = client.retrieve("blob").unwrap();
+
+        assert_eq!(
+            client.into_inner().into_inner().to_string(),
+            "RETRIEVE blob\u{0}".to_owned()
+        );
+        assert_eq!(vec![0u8, 1, 2, 3, 0xFF, 0], bytes);
+    }
+
+    #[test]
+    fn test_stored_resource_round_trips_when_retrieved() {
+        let blob = vec![0u8, 1, 2, 3, 0xFF, 0];
+        let mut client = Client::new(Connection::from_str("\0\0"));
+        client.store("blob", &blob).unwrap();
+        let written = client.into_inner().into_inner().written();
+        let escaped = &written["\x0dblob\0".len()..];
+
+        let mut response = escaped.to_vec();
+        response.extend_from_slice(b"Query executed in 0.1 ms.\0\0");
+        let mut client = Client::new(Connection::from_bytes(&response));
+
+        assert_eq!(blob, client.retrieve("blob").unwrap());
+    }
+
+    #[test]
+    fn test_resource_fails_to_retrieve_when_it_does_not_exist() {
+        let mut client = Client::new(Connection::from_str("\0Cannot retrieve blob.\0\u{1}"));
+
+        let actual_error = client.retrieve("blob").expect_err("Operation must fail");
+
+        assert!(matches!(actual_error, ClientError::CommandFailed { message } if message == "Cannot retrieve blob."));
+    }
+
+    #[allow(clippy::unused_unit)]
+    #[test_case(" "; "blank")]
+    #[test_case("blob;DROP DB lunar"; "semicolon")]
+    #[test_case("blob\nDROP DB lunar"; "line break")]
+    fn test_resource_fails_to_retrieve_with_invalid_path(path: &str) {
+        let mut client = Client::new(Connection::from_str(""));
+
+        let actual_error = client.retrieve(path).expect_err("Operation must fail");
+
+        assert!(matches!(actual_error, ClientError::InvalidName(p) if p == path));
+        assert_eq!("", client.into_inner().into_inner().to_string());
+    }
+
+    #[test]
+    fn test_database_is_exported() {
+        let mut client = Client::new(Connection::from_bytes(
+            b"<wojak>\xFF\xFF</wojak>\0Query executed in 0.1 ms.\0\0",
+        ));
+        let mut out: Vec<u8> = vec![];
+
+        let size = client.export_to("lunar/it's", &mut out).unwrap();
+
+        assert_eq!(
+            client.into_inner().into_inner().to_string(),
+            "XQUERY collection(\"lunar/it's\")\u{0}".to_owned()
+        );
+        assert_eq!(b"<wojak>\xFF</wojak>".to_vec(), out);
+        assert_eq!(out.len() as u64, size);
+    }
+
+    #[test]
+    fn test_database_fails_to_export_when_it_does_not_exist() {
+        let mut client = Client::new(Connection::from_str("\0Database 'lunar' was not found.\0\u{1}"));
+
+        let actual_error = client.export_to("lunar", &mut vec![]).expect_err("Operation must fail");
+
+        assert!(matches!(actual_error, ClientError::CommandFailed { .. }));
+    }
+
     #[test]
     fn test_resource_is_added() {
         let mut client = Client::new(Connection::from_str("test\0"));
@@ -481,6 +3391,146 @@ mod tests {
         assert_eq!("test", info);
     }
 
+    #[test]
+    fn test_resources_are_added_back_to_back() {
+        let mut client = Client::new(Connection::from_str("one\0\0two\0\0three\0"));
+
+        let infos = client
+            .add_many(vec![
+                ("alcyone".to_owned(), "<star/>"),
+                ("atlas".to_owned(), "<star/>"),
+                ("maia".to_owned(), "<star/>"),
+            ])
+            .unwrap();
+
+        assert_eq!(
+            client.into_inner().into_inner().to_string(),
+            "\u{9}alcyone\u{0}<star/>\u{0}\u{9}atlas\u{0}<star/>\u{0}\u{9}maia\u{0}<star/>\u{0}".to_owned()
+        );
+        assert_eq!(vec!["one", "two", "three"], infos);
+    }
+
+    #[test]
+    fn test_resources_fail_to_add_when_one_is_rejected() {
+        let mut client = Client::new(Connection::from_str("one\0\0Invalid XML.\0\u{1}three\0\0\0\0"));
+
+        let actual_error = client
+            .add_many(vec![
+                ("alcyone".to_owned(), "<star/>"),
+                ("atlas".to_owned(), "<star"),
+                ("maia".to_owned(), "<star/>"),
+            ])
+            .expect_err("Operation must fail");
+
+        assert!(matches!(actual_error, ClientError::CommandFailed { message } if message == "Invalid XML."));
+        client.ping().unwrap();
+    }
+
+    #[test]
+    fn test_replace_many_query_replaces_every_bound_document() {
+        let expected_query = "declare variable $db external;
+declare variable $path0 external;
+declare variable $input0 external;
+declare variable $path1 external;
+declare variable $input1 external;
+db:replace($db, $path0, parse-xml($input0)),
+db:replace($db, $path1, parse-xml($input1))";
+
+        assert_eq!(expected_query, replace_many_query(2));
+    }
+
+    #[test]
+    fn test_resources_are_replaced_in_one_query() {
+        let responses = format!("{}\0\0\0", INFO_DB) + "\0\0\0" + "1\0\0" + &"\0\0".repeat(5) + "\0\0" + "\0\0";
+        let mut client = Client::new(Connection::from_str(responses));
+
+        client
+            .replace_many(vec![("alcyone".to_owned(), "<star/>"), ("atlas".to_owned(), "<moon/>")])
+            .unwrap();
+
+        let expected_buffer = "INFO DB\0SET QUERYINFO false\0".to_owned()
+            + "\0"
+            + &replace_many_query(2)
+            + "\0\u{3}1\0db\0factbook\0xs:string\0"
+            + "\u{3}1\0path0\0alcyone\0xs:string\0\u{3}1\0input0\0<star/>\0xs:string\0"
+            + "\u{3}1\0path1\0atlas\0xs:string\0\u{3}1\0input1\0<moon/>\0xs:string\0"
+            + "\u{5}1\0\u{2}1\0";
+        assert_eq!(expected_buffer, client.into_inner().into_inner().to_string());
+    }
+
+    #[test]
+    fn test_replacing_no_resources_sends_nothing() {
+        let mut client = Client::new(Connection::from_str("test_response"));
+
+        client.replace_many(Vec::<(String, &str)>::new()).unwrap();
+
+        assert_eq!("", client.into_inner().into_inner().to_string());
+    }
+
+    #[test]
+    fn test_resource_is_added_from_chunks_written() {
+        let mut client = Client::new(Connection::from_str("test\0"));
+
+        let info = client
+            .add_with("stars", |out| {
+                out.write_all(b"<stars>")?;
+                for i in 0..3 {
+                    write!(out, "<star i=\"{}\"/>", i)?;
+                }
+                out.write_all(&[0xFF, 0])?;
+                out.write_all(b"</stars>")
+            })
+            .unwrap();
+
+        let mut expected_buffer = b"\x09stars\0<stars><star i=\"0\"/><star i=\"1\"/><star i=\"2\"/>".to_vec();
+        expected_buffer.extend([0xFF, 0xFF, 0xFF, 0]);
+        expected_buffer.extend(b"</stars>\0");
+        assert_eq!(expected_buffer, client.into_inner().into_inner().written());
+        assert_eq!("test", info);
+    }
+
+    #[test]
+    fn test_adding_written_resource_fails_with_error_of_writer() {
+        let mut client = Client::new(Connection::from_str("Malformed XML.\0\u{1}next\0"));
+
+        let actual_error = client
+            .add_with("stars", |out| {
+                out.write_all(b"<stars>")?;
+                Err(io::Error::new(ErrorKind::InvalidData, "ran out of stars"))
+            })
+            .expect_err("Operation must fail");
+
+        assert!(matches!(actual_error, ClientError::Io(e) if e.kind() == ErrorKind::InvalidData));
+        let mut connection = client.into_inner();
+        assert_eq!("next", connection.read_string().unwrap());
+        assert_eq!("\u{9}stars\0<stars><\0", connection.into_inner().to_string());
+    }
+
+    #[test]
+    fn test_adding_written_resource_fails_with_failing_stream() {
+        let mut client = Client::new(Connection::failing());
+
+        let actual_error = client
+            .add_with("stars", |out| out.write_all(b"<stars/>"))
+            .expect_err("Operation must fail");
+
+        assert!(matches!(actual_error, ClientError::Io(_)));
+    }
+
+    #[test]
+    fn test_resource_is_added_from_reader_yielding_single_bytes() {
+        let mut client = Client::new(Connection::from_str("test\0"));
+        let bytes = [b'<', 0xFF, 0, b'a', 0, 0xFF, b'>'];
+        let mut reader = ChunkedReader::new(&bytes, &[1]);
+        let reader: &mut dyn Read = &mut reader;
+
+        client.add("boy_sminem", reader).unwrap();
+
+        let mut expected_buffer = b"\x09boy_sminem\0".to_vec();
+        expected_buffer.extend([b'<', 0xFF, 0xFF, 0xFF, 0, b'a', 0xFF, 0, 0xFF, 0xFF, b'>', 0]);
+        assert_eq!(expected_buffer, client.into_inner().into_inner().written());
+    }
+
     #[test]
     fn test_resource_fails_to_add_with_failing_stream() {
         let mut client = Client::new(Connection::failing());
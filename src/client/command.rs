@@ -0,0 +1,319 @@
+use crate::client::database::validate_name;
+use crate::{ClientError, Result};
+use std::fmt::{Display, Formatter};
+
+/// Server [commands](https://docs.basex.org/wiki/Commands) that can be run by [`Client::run`] without composing the
+/// command strings by hand.
+///
+/// # Example
+/// ```
+/// # use basex::{Client, Command, Result};
+/// # fn main() -> Result<()> {
+/// let client = Client::connect("localhost", 1984, "admin", "admin")?;
+/// let (client, info) = client.run(Command::Open("factbook".to_owned()))?.close()?;
+/// println!("{}", info);
+/// # Ok(())
+/// # }
+/// ```
+///
+/// [`Client::run`]: crate::Client::run
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Command {
+    /// Lists all databases.
+    List,
+    /// Opens the database with the given name.
+    Open(String),
+    /// Closes the currently opened database.
+    Close,
+    /// Drops the target.
+    Drop(DropTarget),
+    /// Copies the database with the first name to a new database with the second name.
+    Copy(String, String),
+    /// Shows the general information about the server.
+    Info,
+    /// Shows the information about the currently opened database.
+    InfoDatabase,
+    /// Flushes the buffers of the currently opened database to disk.
+    Flush,
+    /// Optimizes the index structures and statistics of the currently opened database.
+    Optimize,
+    /// Rebuilds the currently opened database and its index structures from scratch.
+    OptimizeAll,
+    /// Retrieves the binary resource at the given path from the currently opened database.
+    Retrieve(String),
+    /// Deletes the resources at the given path from the currently opened database.
+    Delete(String),
+    /// Renames the resources at the first path to the second path in the currently opened database.
+    Rename(String, String),
+    /// Backs up the database with the given name.
+    CreateBackup(String),
+    /// Restores the database from the backup with the given name, optionally suffixed with its timestamp.
+    Restore(String),
+    /// Lists all backups.
+    ShowBackups,
+}
+
+impl Command {
+    /// Whether the command produces a result, as opposed to only the info message about what it did.
+    pub fn has_body(&self) -> bool {
+        matches!(
+            self,
+            Command::List | Command::Info | Command::InfoDatabase | Command::Retrieve(_) | Command::ShowBackups
+        )
+    }
+
+    /// Whether the command changes the currently opened database.
+    pub(crate) fn updates_opened_database(&self) -> bool {
+        matches!(
+            self,
+            Command::Delete(_) | Command::Rename(..) | Command::Optimize | Command::OptimizeAll
+        )
+    }
+
+    /// Fails with [`ClientError::InvalidName`] when a database or backup name is not valid, the drop target is not
+    /// valid as told by [`DropTarget::validate`], or a resource path is empty or would be read as more than one argument
+    /// or command, i.e. contains whitespace or a semicolon. Glob patterns are never valid, so a command only acts on what
+    /// it names.
+    pub(crate) fn validate(&self) -> Result<()> {
+        match self {
+            Command::Open(name) | Command::CreateBackup(name) | Command::Restore(name) => validate_name(name),
+            Command::Copy(source, target) => validate_name(source).and_then(|_| validate_name(target)),
+            Command::Drop(target) => target.validate(),
+            Command::Retrieve(path) | Command::Delete(path) => validate_path(path),
+            Command::Rename(source, target) => validate_path(source).and_then(|_| validate_path(target)),
+            Command::List
+            | Command::Close
+            | Command::Info
+            | Command::InfoDatabase
+            | Command::Flush
+            | Command::Optimize
+            | Command::OptimizeAll
+            | Command::ShowBackups => Ok(()),
+        }
+    }
+}
+
+fn validate_path(path: &str) -> Result<()> {
+    match path.is_empty() || path.chars().any(|c| c.is_whitespace() || c == ';') {
+        true => Err(ClientError::InvalidName(path.to_owned())),
+        false => Ok(()),
+    }
+}
+
+impl Display for Command {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Command::List => write!(f, "LIST"),
+            Command::Open(name) => write!(f, "OPEN {}", name),
+            Command::Close => write!(f, "CLOSE"),
+            Command::Drop(target) => write!(f, "{}", target),
+            Command::Copy(source, target) => write!(f, "COPY {} {}", source, target),
+            Command::Info => write!(f, "INFO"),
+            Command::InfoDatabase => write!(f, "INFO DB"),
+            Command::Flush => write!(f, "FLUSH"),
+            Command::Optimize => write!(f, "OPTIMIZE"),
+            Command::OptimizeAll => write!(f, "OPTIMIZE ALL"),
+            Command::Retrieve(path) => write!(f, "RETRIEVE {}", path),
+            Command::Delete(path) => write!(f, "DELETE {}", path),
+            Command::Rename(source, target) => write!(f, "RENAME {} {}", source, target),
+            Command::CreateBackup(name) => write!(f, "CREATE BACKUP {}", name),
+            Command::Restore(name) => write!(f, "RESTORE {}", name),
+            Command::ShowBackups => write!(f, "SHOW BACKUPS"),
+        }
+    }
+}
+
+/// What running a [`Command`] with [`Client::run_outcome`] produced, as told apart by [`Command::has_body`].
+///
+/// [`Client::run_outcome`]: crate::Client::run_outcome
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CommandOutcome {
+    /// The result of a command producing one, e.g. `LIST`, along with the info message.
+    Body { result: String, info: String },
+    /// The info message of a command producing no result, e.g. `OPEN`.
+    Status(String),
+}
+
+impl CommandOutcome {
+    /// The result, or `None` for a command that produces only the info message.
+    pub fn result(&self) -> Option<&str> {
+        match self {
+            CommandOutcome::Body { result, .. } => Some(result),
+            CommandOutcome::Status(_) => None,
+        }
+    }
+
+    /// The info message.
+    pub fn info(&self) -> &str {
+        match self {
+            CommandOutcome::Body { info, .. } | CommandOutcome::Status(info) => info,
+        }
+    }
+}
+
+/// What the [`Command::Drop`] removes.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DropTarget {
+    /// The database with the given name.
+    Database(String),
+    /// The backup with the given name.
+    Backup(String),
+    /// The user with the given name.
+    User(String),
+    /// The installed XQuery module or package with the given name, which the server removes from its repository.
+    Module(String),
+}
+
+impl DropTarget {
+    fn name(&self) -> &str {
+        match self {
+            DropTarget::Database(name)
+            | DropTarget::Backup(name)
+            | DropTarget::User(name)
+            | DropTarget::Module(name) => name,
+        }
+    }
+
+    /// Fails with [`ClientError::InvalidName`] when the name of a database, backup or user is not valid. Module names
+    /// are URIs, so they are only required to be non-empty and not be read as more than one command argument, i.e. not
+    /// contain whitespace or a semicolon.
+    pub(crate) fn validate(&self) -> Result<()> {
+        match self {
+            DropTarget::Module(name) if name.is_empty() || name.chars().any(|c| c.is_whitespace() || c == ';') => {
+                Err(ClientError::InvalidName(name.to_owned()))
+            }
+            DropTarget::Module(_) => Ok(()),
+            target => validate_name(target.name()),
+        }
+    }
+}
+
+impl Display for DropTarget {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DropTarget::Database(name) => write!(f, "DROP DB {}", name),
+            DropTarget::Backup(name) => write!(f, "DROP BACKUP {}", name),
+            DropTarget::User(name) => write!(f, "DROP USER {}", name),
+            DropTarget::Module(name) => write!(f, "REPO DELETE {}", name),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_case::test_case;
+
+    #[allow(clippy::unused_unit)]
+    #[test_case(Command::List, "LIST")]
+    #[test_case(Command::Open("lunar".to_owned()), "OPEN lunar")]
+    #[test_case(Command::Close, "CLOSE")]
+    #[test_case(Command::Drop(DropTarget::Database("lunar".to_owned())), "DROP DB lunar")]
+    #[test_case(Command::Drop(DropTarget::Backup("lunar-2021".to_owned())), "DROP BACKUP lunar-2021")]
+    #[test_case(Command::Drop(DropTarget::User("wojak".to_owned())), "DROP USER wojak")]
+    #[test_case(Command::Drop(DropTarget::Module("http://expath.org/ns/crypto".to_owned())), "REPO DELETE http://expath.org/ns/crypto")]
+    #[test_case(Command::Copy("lunar".to_owned(), "lunar-copy".to_owned()), "COPY lunar lunar-copy")]
+    #[test_case(Command::Info, "INFO")]
+    #[test_case(Command::InfoDatabase, "INFO DB")]
+    #[test_case(Command::Flush, "FLUSH")]
+    #[test_case(Command::Optimize, "OPTIMIZE")]
+    #[test_case(Command::OptimizeAll, "OPTIMIZE ALL")]
+    #[test_case(Command::Retrieve("blob".to_owned()), "RETRIEVE blob")]
+    #[test_case(Command::Delete("boy_sminem".to_owned()), "DELETE boy_sminem")]
+    #[test_case(Command::Rename("lunar.xml".to_owned(), "base/lunar.xml".to_owned()), "RENAME lunar.xml base/lunar.xml")]
+    #[test_case(Command::CreateBackup("lunar".to_owned()), "CREATE BACKUP lunar")]
+    #[test_case(Command::Restore("lunar-2021-03-12-10-11-05".to_owned()), "RESTORE lunar-2021-03-12-10-11-05")]
+    #[test_case(Command::ShowBackups, "SHOW BACKUPS")]
+    fn test_renders_command(command: Command, expected: &str) {
+        assert_eq!(expected, command.to_string());
+    }
+
+    #[allow(clippy::unused_unit)]
+    #[test_case(Command::List, true)]
+    #[test_case(Command::Info, true)]
+    #[test_case(Command::Retrieve("blob".to_owned()), true)]
+    #[test_case(Command::Open("lunar".to_owned()), false)]
+    #[test_case(Command::Close, false)]
+    #[test_case(Command::Flush, false)]
+    fn test_tells_whether_command_has_body(command: Command, expected: bool) {
+        assert_eq!(expected, command.has_body());
+    }
+
+    #[allow(clippy::unused_unit)]
+    #[test_case(Command::List)]
+    #[test_case(Command::Open("lunar".to_owned()))]
+    #[test_case(Command::Drop(DropTarget::Module("http://expath.org/ns/crypto".to_owned())))]
+    #[test_case(Command::Copy("lunar".to_owned(), "lunar-copy".to_owned()))]
+    #[test_case(Command::Delete("base/lunar.xml".to_owned()))]
+    #[test_case(Command::Rename("lunar.xml".to_owned(), "base/lunar.xml".to_owned()))]
+    #[test_case(Command::Restore("lunar-2021-03-12-10-11-05".to_owned()))]
+    fn test_accepts_command(command: Command) {
+        command.validate().unwrap();
+    }
+
+    #[allow(clippy::unused_unit)]
+    #[test_case(Command::Open("*".to_owned()); "open glob")]
+    #[test_case(Command::Drop(DropTarget::Database("*".to_owned())); "drop glob")]
+    #[test_case(Command::Copy("lunar".to_owned(), "lunar copy".to_owned()); "copy whitespace")]
+    #[test_case(Command::CreateBackup("lunar*".to_owned()); "backup glob")]
+    #[test_case(Command::Restore("".to_owned()); "restore empty")]
+    #[test_case(Command::Retrieve(" ".to_owned()); "retrieve blank")]
+    #[test_case(Command::Delete("lunar.xml; DROP DB lunar".to_owned()); "delete semicolon")]
+    #[test_case(Command::Rename("lunar.xml".to_owned(), "base.xml\nDROP DB lunar".to_owned()); "rename line break")]
+    #[test_case(Command::Rename("lunar.xml".to_owned(), "base/lunar base.xml".to_owned()); "rename whitespace")]
+    fn test_rejects_command(command: Command) {
+        let actual_error = command.validate().expect_err("Validation must fail");
+
+        assert!(matches!(actual_error, ClientError::InvalidName(_)));
+    }
+
+    #[test]
+    fn test_body_outcome_has_result_and_info() {
+        let outcome = CommandOutcome::Body {
+            result: "lunar".to_owned(),
+            info: "Done.".to_owned(),
+        };
+
+        assert_eq!(Some("lunar"), outcome.result());
+        assert_eq!("Done.", outcome.info());
+    }
+
+    #[test]
+    fn test_status_outcome_has_only_info() {
+        let outcome = CommandOutcome::Status("Database 'lunar' was opened.".to_owned());
+
+        assert_eq!(None, outcome.result());
+        assert_eq!("Database 'lunar' was opened.", outcome.info());
+    }
+
+    #[allow(clippy::unused_unit)]
+    #[test_case(DropTarget::Database("lunar".to_owned()))]
+    #[test_case(DropTarget::Backup("lunar-2021-03-12-10-11-05".to_owned()))]
+    #[test_case(DropTarget::User("wojak".to_owned()))]
+    #[test_case(DropTarget::Module("http://expath.org/ns/crypto".to_owned()))]
+    fn test_accepts_drop_target(target: DropTarget) {
+        target.validate().unwrap();
+    }
+
+    #[allow(clippy::unused_unit)]
+    #[test_case(DropTarget::Database("".to_owned()))]
+    #[test_case(DropTarget::Database("lunar; DROP USER admin".to_owned()))]
+    #[test_case(DropTarget::User("wo jak".to_owned()))]
+    #[test_case(DropTarget::Backup("lunar\n".to_owned()))]
+    fn test_rejects_drop_target(target: DropTarget) {
+        let actual_error = target.validate().expect_err("Validation must fail");
+
+        assert!(matches!(actual_error, ClientError::InvalidName(_)));
+    }
+
+    #[test]
+    fn test_formats_as_debug() {
+        let _ = format!("{:?}", Command::Drop(DropTarget::Database("lunar".to_owned())));
+    }
+
+    #[test]
+    fn test_clones() {
+        let command = Command::Open("lunar".to_owned());
+        assert_eq!(command, command.clone());
+    }
+}
@@ -0,0 +1,659 @@
+use crate::client::server::property;
+use crate::query::compiler::RawInfo;
+use crate::{ClientError, Result};
+use std::str::FromStr;
+use std::time::Duration;
+
+/// Summary of a database as listed by the [`LIST`](https://docs.basex.org/wiki/Commands#LIST) command.
+///
+/// # Example
+/// ```
+/// # use basex::{Client, Result};
+/// # fn main() -> Result<()> {
+/// let mut client = Client::connect("localhost", 1984, "admin", "admin")?;
+///
+/// for database in client.list()? {
+///     println!("{} has {} resource(s)", database.name(), database.resources());
+/// }
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct DatabaseInfo {
+    name: String,
+    resources: usize,
+    size: u64,
+    path: Option<String>,
+}
+
+impl DatabaseInfo {
+    /// Parses the tabular output of the `LIST` command. The header, separator and summary lines are skipped.
+    pub(crate) fn from_list(list: &str) -> Vec<Self> {
+        list.lines()
+            .skip_while(|line| !line.starts_with('-'))
+            .skip(1)
+            .take_while(|line| !line.trim().is_empty())
+            .filter_map(Self::from_row)
+            .collect()
+    }
+
+    fn from_row(row: &str) -> Option<Self> {
+        let (name, rest) = Self::column(row)?;
+        let (resources, rest) = Self::column(rest)?;
+        let (size, rest) = Self::column(rest)?;
+        let path = rest.trim();
+
+        Some(Self {
+            name: name.to_owned(),
+            resources: usize::from_str(resources).ok()?,
+            size: u64::from_str(size).ok()?,
+            path: match path.is_empty() {
+                true => None,
+                false => Some(path.to_owned()),
+            },
+        })
+    }
+
+    fn column(row: &str) -> Option<(&str, &str)> {
+        let row = row.trim_start();
+        match row.find(char::is_whitespace) {
+            Some(end) => Some((&row[..end], &row[end..])),
+            None if !row.is_empty() => Some((row, "")),
+            None => None,
+        }
+    }
+
+    /// Name of the database.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Number of resources stored in the database.
+    pub fn resources(&self) -> usize {
+        self.resources
+    }
+
+    /// Size of the database in bytes.
+    pub fn size(&self) -> u64 {
+        self.size
+    }
+
+    /// Path of the input the database was created from, if there was any.
+    pub fn path(&self) -> Option<&str> {
+        self.path.as_deref()
+    }
+}
+
+/// Kind of a resource stored in a database.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ResourceKind {
+    /// XML document.
+    Xml,
+    /// Binary resource stored as is.
+    Binary,
+}
+
+impl ResourceKind {
+    /// Parses the type column of the `LIST db` output, which names binary resources `raw` or `binary` depending on the
+    /// server version.
+    fn from_type(kind: &str) -> Option<Self> {
+        match kind {
+            "xml" => Some(ResourceKind::Xml),
+            "raw" | "binary" => Some(ResourceKind::Binary),
+            _ => None,
+        }
+    }
+}
+
+/// Resource stored in a database as listed by the [`LIST`](https://docs.basex.org/wiki/Commands#LIST) command given
+/// the database name.
+///
+/// # Example
+/// ```
+/// # use basex::{Client, Result};
+/// # fn main() -> Result<()> {
+/// let mut client = Client::connect("localhost", 1984, "admin", "admin")?;
+/// client.create("asteroid")?.with_input("<asteroid/>")?;
+///
+/// for resource in client.list_resources("asteroid")? {
+///     println!("{} ({:?}, {} bytes)", resource.path(), resource.kind(), resource.size());
+/// }
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct ResourceEntry {
+    path: String,
+    kind: ResourceKind,
+    size: u64,
+    content_type: Option<String>,
+}
+
+impl ResourceEntry {
+    /// Parses the tabular output of the `LIST db` command. The header, separator and summary lines are skipped.
+    pub(crate) fn from_list(list: &str) -> Vec<Self> {
+        list.lines()
+            .skip_while(|line| !line.starts_with('-'))
+            .skip(1)
+            .take_while(|line| !line.trim().is_empty())
+            .filter_map(Self::from_row)
+            .collect()
+    }
+
+    /// Reads the columns from the end of the `row`, as the path that comes first may contain whitespace.
+    fn from_row(row: &str) -> Option<Self> {
+        let (rest, size) = Self::last_column(row)?;
+        let (rest, column) = Self::last_column(rest)?;
+        let (path, kind, content_type) = match ResourceKind::from_type(column) {
+            Some(kind) => (rest, kind, None),
+            None => {
+                let (rest, kind) = Self::last_column(rest)?;
+                (rest, ResourceKind::from_type(kind)?, Some(column.to_owned()))
+            }
+        };
+        let path = path.trim();
+
+        match path.is_empty() {
+            true => None,
+            false => Some(Self {
+                path: path.to_owned(),
+                kind,
+                size: u64::from_str(size).ok()?,
+                content_type,
+            }),
+        }
+    }
+
+    fn last_column(row: &str) -> Option<(&str, &str)> {
+        let row = row.trim_end();
+        match row.rfind(char::is_whitespace) {
+            Some(start) => Some((&row[..start], &row[start + 1..])),
+            None => None,
+        }
+    }
+
+    /// Path of the resource within the database.
+    pub fn path(&self) -> &str {
+        &self.path
+    }
+
+    /// Whether the resource is an XML document or a binary one.
+    pub fn kind(&self) -> ResourceKind {
+        self.kind
+    }
+
+    /// Size of the resource, i.e. the number of nodes of an XML document or the number of bytes of a binary resource.
+    pub fn size(&self) -> u64 {
+        self.size
+    }
+
+    /// Content type of the resource, e.g. `application/xml`, if it was reported.
+    pub fn content_type(&self) -> Option<&str> {
+        self.content_type.as_deref()
+    }
+}
+
+/// Outcome of the database creation parsed from the server info message, e.g. `Database 'db' created in 12.3 ms.`
+///
+/// # Example
+/// ```
+/// # use basex::{Client, Result};
+/// # fn main() -> Result<()> {
+/// let mut client = Client::connect("localhost", 1984, "admin", "admin")?;
+///
+/// let outcome = client.create("comet")?.without_input_parsed()?;
+/// assert_eq!("comet", outcome.name());
+/// println!("Created in {:?}", outcome.duration());
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct CreateOutcome {
+    name: String,
+    duration: Duration,
+}
+
+impl CreateOutcome {
+    /// Parses the info message. Fails with [`CommandFailed`] carrying the original message when it does not match.
+    ///
+    /// [`CommandFailed`]: crate::ClientError::CommandFailed
+    pub(crate) fn from_info(info: String) -> Result<Self> {
+        match Self::parse(&info) {
+            Some(outcome) => Ok(outcome),
+            None => Err(ClientError::CommandFailed { message: info }),
+        }
+    }
+
+    fn parse(info: &str) -> Option<Self> {
+        let (name, duration) = info.strip_prefix("Database '")?.split_once("' created in ")?;
+
+        Some(Self {
+            name: name.to_owned(),
+            duration: RawInfo::parse_duration(duration)?,
+        })
+    }
+
+    /// Name of the created database.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Time it took to create the database.
+    pub fn duration(&self) -> Duration {
+        self.duration
+    }
+}
+
+/// Parses the first column of the tabular output of commands like `SHOW BACKUPS`. The header, separator and summary
+/// lines are skipped.
+pub(crate) fn names_from_table(table: &str) -> Vec<String> {
+    table
+        .lines()
+        .skip_while(|line| !line.starts_with('-'))
+        .skip(1)
+        .take_while(|line| !line.trim().is_empty())
+        .filter_map(|line| line.split_whitespace().next())
+        .map(|name| name.to_owned())
+        .collect()
+}
+
+/// Parses the time a command took from its info message, e.g. `Database 'db' optimized in 5.0 ms.` Fails with
+/// [`CommandFailed`] carrying the original message when there is none.
+///
+/// [`CommandFailed`]: crate::ClientError::CommandFailed
+pub(crate) fn elapsed_from_info(info: String) -> Result<Duration> {
+    let duration = info
+        .rsplit_once(" in ")
+        .and_then(|(_, duration)| RawInfo::parse_duration(duration));
+
+    match duration {
+        Some(duration) => Ok(duration),
+        None => Err(ClientError::CommandFailed { message: info }),
+    }
+}
+
+/// Statistics of the currently opened database as reported by the
+/// [`INFO DB`](https://docs.basex.org/wiki/Commands#INFO_DB) command.
+///
+/// # Example
+/// ```
+/// # use basex::{Client, Result};
+/// # fn main() -> Result<()> {
+/// let mut client = Client::connect("localhost", 1984, "admin", "admin")?;
+/// client.create("nebula")?.with_input("<nebula/>")?;
+///
+/// let stats = client.info_database()?;
+/// println!("{} has {} document(s) taking {}", stats.name(), stats.documents(), stats.size());
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct DatabaseStats {
+    name: String,
+    size: String,
+    nodes: usize,
+    documents: usize,
+    binaries: usize,
+    timestamp: String,
+    encoding: Option<String>,
+    up_to_date: bool,
+    text_index: bool,
+    attribute_index: bool,
+    token_index: bool,
+    fulltext_index: bool,
+}
+
+impl DatabaseStats {
+    /// Parses the output of the `INFO DB` command. Fails with [`CommandFailed`] carrying the original output when the
+    /// database properties are missing.
+    ///
+    /// [`CommandFailed`]: crate::ClientError::CommandFailed
+    pub(crate) fn from_info(info: String) -> Result<Self> {
+        match Self::parse(&info) {
+            Some(stats) => Ok(stats),
+            None => Err(ClientError::CommandFailed { message: info }),
+        }
+    }
+
+    fn parse(info: &str) -> Option<Self> {
+        let usize_from = |header| usize::from_str(property(info, header)?).ok();
+        let bool_from = |header| property(info, header) == Some("true");
+
+        Some(Self {
+            name: property(info, "Name")?.to_owned(),
+            size: property(info, "Size")?.to_owned(),
+            nodes: usize_from("Nodes")?,
+            documents: usize_from("Documents")?,
+            binaries: usize_from("Binaries").unwrap_or(0),
+            timestamp: property(info, "Timestamp")?.to_owned(),
+            encoding: property(info, "Encoding").map(|encoding| encoding.to_owned()),
+            up_to_date: bool_from("Up-to-date"),
+            text_index: bool_from("TEXTINDEX"),
+            attribute_index: bool_from("ATTRINDEX"),
+            token_index: bool_from("TOKENINDEX"),
+            fulltext_index: bool_from("FTINDEX"),
+        })
+    }
+
+    /// Name of the database.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Size of the database as formatted by the server, e.g. `1660 KB`.
+    pub fn size(&self) -> &str {
+        &self.size
+    }
+
+    /// Number of nodes stored in the database.
+    pub fn nodes(&self) -> usize {
+        self.nodes
+    }
+
+    /// Number of XML documents stored in the database.
+    pub fn documents(&self) -> usize {
+        self.documents
+    }
+
+    /// Number of binary resources stored in the database.
+    pub fn binaries(&self) -> usize {
+        self.binaries
+    }
+
+    /// Time of the last modification as formatted by the server, e.g. `2021-03-12T10:11:05.000Z`.
+    pub fn timestamp(&self) -> &str {
+        &self.timestamp
+    }
+
+    /// Encoding of the input the database was created from, if it was reported.
+    pub fn encoding(&self) -> Option<&str> {
+        self.encoding.as_deref()
+    }
+
+    /// Whether the indexes reflect the latest updates.
+    pub fn indexes_up_to_date(&self) -> bool {
+        self.up_to_date
+    }
+
+    /// Whether the text index is available.
+    pub fn text_index(&self) -> bool {
+        self.text_index
+    }
+
+    /// Whether the attribute index is available.
+    pub fn attribute_index(&self) -> bool {
+        self.attribute_index
+    }
+
+    /// Whether the token index is available.
+    pub fn token_index(&self) -> bool {
+        self.token_index
+    }
+
+    /// Whether the full-text index is available.
+    pub fn fulltext_index(&self) -> bool {
+        self.fulltext_index
+    }
+}
+
+/// Longest name accepted, as the server stores each database in a directory of the same name.
+const MAX_NAME_LENGTH: usize = 255;
+
+/// Checks the `name` of a database, backup or user against the
+/// [valid names](https://docs.basex.org/wiki/Commands#Valid_Names) rules before it is sent: at most 255 ASCII letters,
+/// digits, underscores, dashes and dots, not starting with a dot. Fails with [`InvalidName`] otherwise.
+///
+/// Resource paths are not names, as they may contain slashes.
+///
+/// [`InvalidName`]: crate::ClientError::InvalidName
+pub(crate) fn validate_name(name: &str) -> Result<()> {
+    let valid = !name.is_empty()
+        && name.len() <= MAX_NAME_LENGTH
+        && !name.starts_with('.')
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '_' | '-' | '.'));
+
+    match valid {
+        true => Ok(()),
+        false => Err(ClientError::InvalidName(name.to_owned())),
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use test_case::test_case;
+
+    pub static LIST: &str = "Name     Resources  Size   Input Path\n\
+        ---------------------------------------------------------\n\
+        factbook 1          77192  /srv/basex/My Files/factbook.xml\n\
+        lambada  0          4570   \n\
+        \n\
+        2 database(s).\n";
+
+    pub static RESOURCES: &str = "Input Path             Type  Content-Type              Size\n\
+        -------------------------------------------------------------\n\
+        countries/factbook.xml xml   application/xml           77192\n\
+        My Files/blob.bin      raw   application/octet-stream  4\n\
+        \n\
+        2 Resource(s).\n";
+
+    pub static INFO_DB: &str = "Database Properties
+ Name: factbook
+ Size: 1660 KB
+ Nodes: 77192
+ Documents: 1
+ Binaries: 0
+ Timestamp: 2021-03-12T10:11:05.000Z
+
+Resource Properties
+ Timestamp: 2021-03-12T10:11:04.000Z
+ Encoding: UTF-8
+ CHOP: true
+
+Indexes
+ Up-to-date: true
+ TEXTINDEX: true
+ ATTRINDEX: true
+ TOKENINDEX: false
+ FTINDEX: false
+";
+
+    #[test]
+    fn test_parses_rows_with_correct_values() {
+        let actual_databases = DatabaseInfo::from_list(LIST);
+
+        assert_eq!(2, actual_databases.len());
+        assert_eq!("factbook", actual_databases[0].name());
+        assert_eq!(1, actual_databases[0].resources());
+        assert_eq!(77192, actual_databases[0].size());
+        assert_eq!(Some("/srv/basex/My Files/factbook.xml"), actual_databases[0].path());
+        assert_eq!("lambada", actual_databases[1].name());
+        assert_eq!(0, actual_databases[1].resources());
+        assert_eq!(4570, actual_databases[1].size());
+        assert_eq!(None, actual_databases[1].path());
+    }
+
+    #[test]
+    fn test_parses_empty_list() {
+        let list = "Name  Resources  Size  Input Path\n----------------------------------\n\n0 database(s).\n";
+
+        assert!(DatabaseInfo::from_list(list).is_empty());
+    }
+
+    #[test]
+    fn test_skips_malformed_rows() {
+        let list = "Name  Resources  Size  Input Path\n------------------\nbroken x y\nok 1 2\n";
+
+        let actual_databases = DatabaseInfo::from_list(list);
+
+        assert_eq!(1, actual_databases.len());
+        assert_eq!("ok", actual_databases[0].name());
+    }
+
+    #[test]
+    fn test_formats_as_debug() {
+        let _ = format!("{:?}", DatabaseInfo::from_list(LIST));
+    }
+
+    #[test]
+    fn test_parses_resources_with_correct_values() {
+        let actual_resources = ResourceEntry::from_list(RESOURCES);
+
+        assert_eq!(2, actual_resources.len());
+        assert_eq!("countries/factbook.xml", actual_resources[0].path());
+        assert_eq!(ResourceKind::Xml, actual_resources[0].kind());
+        assert_eq!(77192, actual_resources[0].size());
+        assert_eq!(Some("application/xml"), actual_resources[0].content_type());
+        assert_eq!("My Files/blob.bin", actual_resources[1].path());
+        assert_eq!(ResourceKind::Binary, actual_resources[1].kind());
+        assert_eq!(4, actual_resources[1].size());
+        assert_eq!(Some("application/octet-stream"), actual_resources[1].content_type());
+    }
+
+    #[test]
+    fn test_parses_resources_without_content_type() {
+        let list = "Input Path  Type  Size\n----------------------\nblob.bin  binary  4\n";
+
+        let actual_resources = ResourceEntry::from_list(list);
+
+        assert_eq!(1, actual_resources.len());
+        assert_eq!(ResourceKind::Binary, actual_resources[0].kind());
+        assert_eq!(None, actual_resources[0].content_type());
+    }
+
+    #[test]
+    fn test_skips_malformed_resource_rows() {
+        let list =
+            "Input Path  Type  Size\n----------------------\nbroken.xml xml x\nunknown.txt value 1\n4\nok.xml xml 2\n";
+
+        let actual_resources = ResourceEntry::from_list(list);
+
+        assert_eq!(1, actual_resources.len());
+        assert_eq!("ok.xml", actual_resources[0].path());
+    }
+
+    #[test]
+    fn test_create_outcome_parses_with_correct_values() {
+        let outcome = CreateOutcome::from_info("Database 'boy_sminem' created in 12.3 ms.".to_owned()).unwrap();
+
+        assert_eq!("boy_sminem", outcome.name());
+        assert_eq!(Duration::from_micros(12300), outcome.duration());
+    }
+
+    #[test]
+    fn test_create_outcome_fails_on_unexpected_message() {
+        let actual_error = CreateOutcome::from_info("Unexpected".to_owned()).expect_err("Parsing must fail");
+
+        assert!(matches!(actual_error, ClientError::CommandFailed { message } if message == "Unexpected"));
+    }
+
+    #[test]
+    fn test_clones() {
+        let databases = DatabaseInfo::from_list(LIST);
+        assert_eq!(databases, databases.clone());
+    }
+
+    pub static BACKUPS: &str = "Name                          Size   \n\
+        -----------------------------------------\n\
+        factbook-2021-03-12-10-11-05  24576  \n\
+        lambada-2021-03-13-08-00-00   1024   \n\
+        \n\
+        2 Backup(s).\n";
+
+    #[test]
+    fn test_names_parse_from_table() {
+        assert_eq!(
+            vec!["factbook-2021-03-12-10-11-05", "lambada-2021-03-13-08-00-00"],
+            names_from_table(BACKUPS)
+        );
+    }
+
+    #[test]
+    fn test_names_parse_from_empty_table() {
+        assert!(names_from_table("Name  Size\n----------\n\n0 Backup(s).\n").is_empty());
+    }
+
+    #[test]
+    fn test_elapsed_time_parses_from_info() {
+        let duration = elapsed_from_info("Database 'factbook' optimized in 5.0 ms.".to_owned()).unwrap();
+
+        assert_eq!(Duration::from_millis(5), duration);
+    }
+
+    #[test]
+    fn test_elapsed_time_fails_to_parse_without_duration() {
+        let actual_error = elapsed_from_info("Unexpected".to_owned()).expect_err("Parsing must fail");
+
+        assert!(matches!(actual_error, ClientError::CommandFailed { message } if message == "Unexpected"));
+    }
+
+    #[test]
+    fn test_database_stats_parse_with_correct_values() {
+        let stats = DatabaseStats::from_info(INFO_DB.to_owned()).unwrap();
+
+        assert_eq!("factbook", stats.name());
+        assert_eq!("1660 KB", stats.size());
+        assert_eq!(77192, stats.nodes());
+        assert_eq!(1, stats.documents());
+        assert_eq!(0, stats.binaries());
+        assert_eq!("2021-03-12T10:11:05.000Z", stats.timestamp());
+        assert_eq!(Some("UTF-8"), stats.encoding());
+        assert!(stats.indexes_up_to_date());
+        assert!(stats.text_index());
+        assert!(stats.attribute_index());
+        assert!(!stats.token_index());
+        assert!(!stats.fulltext_index());
+    }
+
+    #[test]
+    fn test_database_stats_without_indexes_have_none_available() {
+        let info = INFO_DB[..INFO_DB.find("Indexes").unwrap()].to_owned();
+
+        let stats = DatabaseStats::from_info(info).unwrap();
+
+        assert!(!stats.indexes_up_to_date());
+        assert!(!stats.text_index());
+        assert!(!stats.attribute_index());
+        assert!(!stats.token_index());
+        assert!(!stats.fulltext_index());
+    }
+
+    #[test]
+    fn test_database_stats_fail_on_unexpected_output() {
+        let actual_error = DatabaseStats::from_info("Unexpected".to_owned()).expect_err("Parsing must fail");
+
+        assert!(matches!(actual_error, ClientError::CommandFailed { message } if message == "Unexpected"));
+    }
+
+    #[allow(clippy::unused_unit)]
+    #[test_case("lunar")]
+    #[test_case("boy_sminem")]
+    #[test_case("lunar-2021-03-12-10-11-05")]
+    #[test_case("factbook.v2")]
+    #[test_case("L")]
+    fn test_accepts_valid_name(name: &str) {
+        validate_name(name).unwrap();
+    }
+
+    #[allow(clippy::unused_unit)]
+    #[test_case(""; "empty")]
+    #[test_case("lunar base"; "space")]
+    #[test_case("lunar/base"; "slash")]
+    #[test_case("lunar\\base"; "backslash")]
+    #[test_case("lunar;DROP"; "semicolon")]
+    #[test_case(".hidden"; "leading dot")]
+    #[test_case("měsíc"; "non ascii")]
+    fn test_rejects_invalid_name(name: &str) {
+        let actual_error = validate_name(name).expect_err("Validation must fail");
+
+        assert!(matches!(actual_error, ClientError::InvalidName(n) if n == name));
+    }
+
+    #[test]
+    fn test_rejects_too_long_name() {
+        validate_name(&"a".repeat(MAX_NAME_LENGTH)).unwrap();
+        validate_name(&"a".repeat(MAX_NAME_LENGTH + 1)).expect_err("Validation must fail");
+    }
+}
@@ -1,6 +1,20 @@
+mod builder;
 #[allow(clippy::module_inception)]
 mod client;
+mod command;
+mod database;
+mod observer;
+mod option;
 mod response;
+mod server;
 
-pub use self::client::Client;
+pub use self::builder::ClientBuilder;
+pub use self::client::{Client, OpenDbGuard};
+pub use self::command::{Command, CommandOutcome, DropTarget};
+pub use self::database::{CreateOutcome, DatabaseInfo, DatabaseStats, ResourceEntry, ResourceKind};
+pub(crate) use self::observer::Observer;
+pub use self::observer::QueryObserver;
+pub use self::option::{CreateOptions, ServerOption};
 pub use self::response::Response;
+pub(crate) use self::server::RawServerInfo;
+pub use self::server::{ServerInfo, Version};
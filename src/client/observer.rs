@@ -0,0 +1,69 @@
+use crate::ClientError;
+use std::fmt::{Debug, Formatter};
+use std::result;
+use std::sync::Arc;
+use std::time::Duration;
+
+/// Gets notified about the queries run by a [`Client`], e.g. to collect their latency.
+///
+/// Both callbacks default to doing nothing. The observer is shared by all the clones of the client.
+///
+/// # Example
+/// ```
+/// # use basex::{Client, ClientError, QueryObserver};
+/// # use std::time::Duration;
+/// struct LatencyLogger;
+///
+/// impl QueryObserver for LatencyLogger {
+///     fn on_query_end(&self, id: &str, elapsed: Duration, result: Result<(), &ClientError>) {
+///         println!("Query {} took {:?}, succeeded: {}", id, elapsed, result.is_ok());
+///     }
+/// }
+///
+/// # fn main() -> Result<(), ClientError> {
+/// let mut client = Client::connect("localhost", 1984, "admin", "admin")?;
+/// client.set_observer(Box::new(LatencyLogger));
+/// # Ok(())
+/// # }
+/// ```
+///
+/// [`Client`]: crate::Client
+pub trait QueryObserver: Send + Sync {
+    /// Called when the query with the given `id` starts executing.
+    fn on_query_start(&self, _id: &str) {}
+
+    /// Called when the response of the query with the given `id` is closed, with the time `elapsed` since the start
+    /// and the `result` of reading the response.
+    fn on_query_end(&self, _id: &str, _elapsed: Duration, _result: result::Result<(), &ClientError>) {}
+}
+
+/// Observer that ignores everything.
+struct NoopObserver;
+
+impl QueryObserver for NoopObserver {}
+
+/// Holds the [`QueryObserver`] of a client.
+#[derive(Clone)]
+pub(crate) struct Observer(Arc<dyn QueryObserver>);
+
+impl Observer {
+    pub(crate) fn new(observer: Box<dyn QueryObserver>) -> Self {
+        Self(Arc::from(observer))
+    }
+
+    pub(crate) fn get(&self) -> &dyn QueryObserver {
+        self.0.as_ref()
+    }
+}
+
+impl Default for Observer {
+    fn default() -> Self {
+        Self(Arc::new(NoopObserver))
+    }
+}
+
+impl Debug for Observer {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str("Observer")
+    }
+}
@@ -0,0 +1,315 @@
+use crate::{ClientError, Result};
+use std::fmt::{Display, Formatter};
+
+/// Server [options](https://docs.basex.org/wiki/Options) that can be read by [`Client::get_option`] and changed by
+/// [`Client::set_option`].
+///
+/// Options that are not listed can be addressed by their name using [`ServerOption::Other`].
+///
+/// # Example
+/// ```
+/// # use basex::{Client, Result, ServerOption};
+/// # fn main() -> Result<()> {
+/// let mut client = Client::connect("localhost", 1984, "admin", "admin")?;
+/// client.set_option(ServerOption::Chop, "false")?;
+/// assert_eq!("false", client.get_option(ServerOption::Chop)?);
+/// # Ok(())
+/// # }
+/// ```
+///
+/// [`Client::get_option`]: crate::Client::get_option
+/// [`Client::set_option`]: crate::Client::set_option
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ServerOption {
+    /// Whether query compilation and profiling info is collected, i.e. `QUERYINFO`.
+    QueryInfo,
+    /// Parameters of the result serialization, i.e. `SERIALIZER`.
+    Serializer,
+    /// Whether whitespace is chopped from text nodes of new documents, i.e. `CHOP`.
+    Chop,
+    /// Whether new databases are created in main memory, i.e. `MAINMEM`.
+    MainMemory,
+    /// Whether added documents are cached to disk first, i.e. `ADDCACHE`.
+    AddCache,
+    /// Parser used for importing new documents, i.e. `PARSER`.
+    Parser,
+    /// Whether the internal XML parser is used instead of the Java one, i.e. `INTPARSE`.
+    InternalParser,
+    /// Glob patterns of the files added when a directory is imported, i.e. `CREATEFILTER`.
+    CreateFilter,
+    /// Whether the text index is created for new databases, i.e. `TEXTINDEX`.
+    TextIndex,
+    /// Whether the attribute index is created for new databases, i.e. `ATTRINDEX`.
+    AttributeIndex,
+    /// Whether the full-text index is created for new databases, i.e. `FTINDEX`.
+    FullTextIndex,
+    /// Whether the indexes are updated incrementally, i.e. `UPDINDEX`.
+    UpdateIndex,
+    /// The option with the given name.
+    Other(String),
+}
+
+impl Display for ServerOption {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ServerOption::QueryInfo => write!(f, "QUERYINFO"),
+            ServerOption::Serializer => write!(f, "SERIALIZER"),
+            ServerOption::Chop => write!(f, "CHOP"),
+            ServerOption::MainMemory => write!(f, "MAINMEM"),
+            ServerOption::AddCache => write!(f, "ADDCACHE"),
+            ServerOption::Parser => write!(f, "PARSER"),
+            ServerOption::InternalParser => write!(f, "INTPARSE"),
+            ServerOption::CreateFilter => write!(f, "CREATEFILTER"),
+            ServerOption::TextIndex => write!(f, "TEXTINDEX"),
+            ServerOption::AttributeIndex => write!(f, "ATTRINDEX"),
+            ServerOption::FullTextIndex => write!(f, "FTINDEX"),
+            ServerOption::UpdateIndex => write!(f, "UPDINDEX"),
+            ServerOption::Other(name) => write!(f, "{}", name.to_uppercase()),
+        }
+    }
+}
+
+impl ServerOption {
+    /// Fails with [`ClientError::InvalidName`] when the name of [`ServerOption::Other`] is not made of ASCII letters
+    /// and digits only, so that it is never read as more than the option name.
+    pub(crate) fn validate(&self) -> Result<()> {
+        match self {
+            ServerOption::Other(name) if name.is_empty() || !name.chars().all(|c| c.is_ascii_alphanumeric()) => {
+                Err(ClientError::InvalidName(name.to_owned()))
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+/// Fails with [`ClientError::InvalidName`] when the option `value` would be read as more than one command, i.e. contains
+/// a semicolon or a line break.
+pub(crate) fn validate_value(value: &str) -> Result<()> {
+    match value.contains([';', '\n', '\r']) {
+        true => Err(ClientError::InvalidName(value.to_owned())),
+        false => Ok(()),
+    }
+}
+
+/// Gathers the [create options](https://docs.basex.org/wiki/Options#Create_Options) that
+/// [`Client::with_create_options`] applies for the duration of a `create` or `add` call.
+///
+/// # Example
+///
+/// ```
+/// # use basex::{Client, CreateOptions, Result};
+/// # fn main() -> Result<()> {
+/// let mut client = Client::connect("localhost", 1984, "admin", "admin")?;
+/// let options = CreateOptions::new().chop(false).full_text_index(true);
+/// client.with_create_options(&options, |client| {
+///     client.create("lunar")?.with_input("<wojak> pink_index </wojak>")
+/// })?;
+/// # Ok(())
+/// # }
+/// ```
+///
+/// [`Client::with_create_options`]: crate::Client::with_create_options
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CreateOptions {
+    options: Vec<(ServerOption, String)>,
+}
+
+impl CreateOptions {
+    /// Creates options that change nothing.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the `option` to the `value`, replacing the value set before.
+    pub fn set(mut self, option: ServerOption, value: &str) -> Self {
+        match self.options.iter_mut().find(|(o, _)| *o == option) {
+            Some((_, previous)) => *previous = value.to_owned(),
+            None => self.options.push((option, value.to_owned())),
+        }
+        self
+    }
+
+    /// Sets whether whitespace is chopped from text nodes.
+    pub fn chop(self, chop: bool) -> Self {
+        self.set(ServerOption::Chop, &chop.to_string())
+    }
+
+    /// Sets whether the internal XML parser is used.
+    pub fn internal_parser(self, internal: bool) -> Self {
+        self.set(ServerOption::InternalParser, &internal.to_string())
+    }
+
+    /// Sets the `parser` used for importing documents, e.g. `json` or `csv`.
+    pub fn parser(self, parser: &str) -> Self {
+        self.set(ServerOption::Parser, parser)
+    }
+
+    /// Sets the glob patterns of the files added when a directory is imported.
+    pub fn create_filter(self, filter: &str) -> Self {
+        self.set(ServerOption::CreateFilter, filter)
+    }
+
+    /// Sets whether the documents are cached to disk before being added.
+    pub fn add_cache(self, cache: bool) -> Self {
+        self.set(ServerOption::AddCache, &cache.to_string())
+    }
+
+    /// Sets whether the text index is created.
+    pub fn text_index(self, index: bool) -> Self {
+        self.set(ServerOption::TextIndex, &index.to_string())
+    }
+
+    /// Sets whether the attribute index is created.
+    pub fn attribute_index(self, index: bool) -> Self {
+        self.set(ServerOption::AttributeIndex, &index.to_string())
+    }
+
+    /// Sets whether the full-text index is created.
+    pub fn full_text_index(self, index: bool) -> Self {
+        self.set(ServerOption::FullTextIndex, &index.to_string())
+    }
+
+    /// Sets whether the indexes are updated incrementally.
+    pub fn update_index(self, update: bool) -> Self {
+        self.set(ServerOption::UpdateIndex, &update.to_string())
+    }
+
+    /// The options with their values, in the order they were first set.
+    pub(crate) fn iter(&self) -> impl Iterator<Item = &(ServerOption, String)> {
+        self.options.iter()
+    }
+
+    /// Fails with [`ClientError::InvalidName`] when any of the options or values is not valid, as told by
+    /// [`ServerOption::validate`] and [`validate_value`].
+    pub(crate) fn validate(&self) -> Result<()> {
+        self.options
+            .iter()
+            .try_for_each(|(option, value)| option.validate().and_then(|_| validate_value(value)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_case::test_case;
+
+    #[allow(clippy::unused_unit)]
+    #[test_case(ServerOption::QueryInfo, "QUERYINFO")]
+    #[test_case(ServerOption::Serializer, "SERIALIZER")]
+    #[test_case(ServerOption::Chop, "CHOP")]
+    #[test_case(ServerOption::MainMemory, "MAINMEM")]
+    #[test_case(ServerOption::AddCache, "ADDCACHE")]
+    #[test_case(ServerOption::Parser, "PARSER")]
+    #[test_case(ServerOption::InternalParser, "INTPARSE")]
+    #[test_case(ServerOption::CreateFilter, "CREATEFILTER")]
+    #[test_case(ServerOption::TextIndex, "TEXTINDEX")]
+    #[test_case(ServerOption::AttributeIndex, "ATTRINDEX")]
+    #[test_case(ServerOption::FullTextIndex, "FTINDEX")]
+    #[test_case(ServerOption::UpdateIndex, "UPDINDEX")]
+    #[test_case(ServerOption::Other("stripns".to_owned()), "STRIPNS")]
+    fn test_renders_option_name(option: ServerOption, expected: &str) {
+        assert_eq!(expected, option.to_string());
+    }
+
+    #[allow(clippy::unused_unit)]
+    #[test_case(ServerOption::Chop)]
+    #[test_case(ServerOption::Other("stripns".to_owned()))]
+    #[test_case(ServerOption::Other("CSV2".to_owned()))]
+    fn test_accepts_option(option: ServerOption) {
+        option.validate().unwrap();
+    }
+
+    #[allow(clippy::unused_unit)]
+    #[test_case(""; "empty")]
+    #[test_case("strip ns"; "whitespace")]
+    #[test_case("chop true;DROP DB lunar"; "semicolon")]
+    #[test_case("chop\nDROP DB lunar"; "line break")]
+    fn test_rejects_option(name: &str) {
+        let actual_error = ServerOption::Other(name.to_owned())
+            .validate()
+            .expect_err("Validation must fail");
+
+        assert!(matches!(actual_error, ClientError::InvalidName(n) if n == name));
+    }
+
+    #[allow(clippy::unused_unit)]
+    #[test_case("indent=yes,method=xml"; "parameters")]
+    #[test_case(""; "empty")]
+    #[test_case("*.xml *.json"; "whitespace")]
+    fn test_accepts_value(value: &str) {
+        validate_value(value).unwrap();
+    }
+
+    #[allow(clippy::unused_unit)]
+    #[test_case("true;DROP DB lunar"; "semicolon")]
+    #[test_case("true\nDROP DB lunar"; "line feed")]
+    #[test_case("true\rDROP DB lunar"; "carriage return")]
+    fn test_rejects_value(value: &str) {
+        let actual_error = validate_value(value).expect_err("Validation must fail");
+
+        assert!(matches!(actual_error, ClientError::InvalidName(v) if v == value));
+    }
+
+    #[test]
+    fn test_create_options_reject_invalid_value() {
+        let options = CreateOptions::new().chop(false).parser("xml;DROP DB lunar");
+
+        let actual_error = options.validate().expect_err("Validation must fail");
+
+        assert!(matches!(actual_error, ClientError::InvalidName(_)));
+    }
+
+    #[test]
+    fn test_formats_as_debug() {
+        let _ = format!("{:?}", ServerOption::QueryInfo);
+    }
+
+    #[test]
+    fn test_create_options_keep_order_and_last_value() {
+        let options = CreateOptions::new()
+            .chop(false)
+            .full_text_index(true)
+            .set(ServerOption::Other("stripns".to_owned()), "true")
+            .chop(true);
+
+        assert_eq!(
+            vec![
+                (ServerOption::Chop, "true".to_owned()),
+                (ServerOption::FullTextIndex, "true".to_owned()),
+                (ServerOption::Other("stripns".to_owned()), "true".to_owned()),
+            ],
+            options.iter().cloned().collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn test_create_options_typed_setters_set_options() {
+        let options = CreateOptions::new()
+            .internal_parser(true)
+            .parser("json")
+            .create_filter("*.xml")
+            .add_cache(true)
+            .text_index(false)
+            .attribute_index(false)
+            .update_index(true);
+
+        assert_eq!(
+            vec![
+                (ServerOption::InternalParser, "true".to_owned()),
+                (ServerOption::Parser, "json".to_owned()),
+                (ServerOption::CreateFilter, "*.xml".to_owned()),
+                (ServerOption::AddCache, "true".to_owned()),
+                (ServerOption::TextIndex, "false".to_owned()),
+                (ServerOption::AttributeIndex, "false".to_owned()),
+                (ServerOption::UpdateIndex, "true".to_owned()),
+            ],
+            options.iter().cloned().collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn test_clones() {
+        let option = ServerOption::Other("stripns".to_owned());
+        assert_eq!(option, option.clone());
+    }
+}
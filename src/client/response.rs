@@ -1,5 +1,8 @@
+use crate::connection::protocol::Unescaper;
 use crate::connection::Authenticated;
 use crate::errors::ClientError::CommandFailed;
+#[cfg(feature = "encoding_rs")]
+use crate::query::serializer::decode;
 use crate::{Client, Connection, DatabaseStream, Result};
 use std::borrow::BorrowMut;
 use std::io::Read;
@@ -38,9 +41,8 @@ where
     T: DatabaseStream,
 {
     client: Client<T>,
-    info_prefix: Option<Vec<u8>>,
-    info_complete: bool,
-    is_ok: bool,
+    unescaper: Unescaper,
+    result_complete: bool,
 }
 
 impl<T> Response<T>
@@ -50,16 +52,48 @@ where
     pub(crate) fn new(client: Client<T>) -> Self {
         Self {
             client,
-            info_prefix: None,
-            info_complete: false,
-            is_ok: false,
+            unescaper: Unescaper::new(),
+            result_complete: false,
         }
     }
 
+    /// Reads the rest of the result and decodes it from the `encoding`, for results not serialized in UTF-8. The
+    /// encoding the serializer uses is told by [`Options::encoding`].
+    ///
+    /// Fails with [`ClientError::Io`] of the [`InvalidData`] kind when the result is malformed in the `encoding`.
+    ///
+    /// # Example
+    /// ```
+    /// use basex::{Client, ClientError};
+    ///
+    /// # fn main() -> Result<(), ClientError> {
+    /// let client = Client::connect("localhost", 1984, "admin", "admin")?;
+    /// let mut query = client.query("/")?.without_info()?;
+    /// let mut options = query.options()?;
+    /// options.set_encoding("ISO-8859-1");
+    /// query.apply_options(&options)?;
+    ///
+    /// let mut response = query.close()?.execute("XQUERY 'café'")?;
+    /// let encoding = options.encoding().unwrap();
+    /// assert_eq!("café", response.read_to_string_with_encoding(encoding)?);
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// [`ClientError::Io`]: crate::ClientError::Io
+    /// [`InvalidData`]: std::io::ErrorKind::InvalidData
+    /// [`Options::encoding`]: crate::serializer::Options::encoding
+    #[cfg(feature = "encoding_rs")]
+    pub fn read_to_string_with_encoding(&mut self, encoding: &'static encoding_rs::Encoding) -> Result<String> {
+        let mut bytes = vec![];
+        self.read_to_end(&mut bytes)?;
+        decode(&bytes, encoding)
+    }
+
     /// Reads info and returns back client.
     ///
-    /// # Panics
-    /// Panics when the stream ends before result is fully streamed.
+    /// Fails with [`ClientError::Io`] of the [`UnexpectedEof`] kind when the stream ends before the result is fully
+    /// streamed.
     ///
     /// # Example
     /// ```
@@ -74,30 +108,16 @@ where
     /// # Ok(())
     /// # }
     /// ```
+    ///
+    /// [`ClientError::Io`]: crate::ClientError::Io
+    /// [`UnexpectedEof`]: std::io::ErrorKind::UnexpectedEof
     pub fn close(mut self) -> Result<(Client<T>, String)> {
-        let mut buf = [0u8; 40];
-
-        while self.info_prefix.is_none() && self.read(&mut buf)? > 0 {}
-
-        if self.info_prefix.is_none() {
-            panic!("Unexpected end of stream.");
-        }
-
-        let info_suffix = if !self.info_complete {
-            let info_suffx = self.connection().read_string()?;
-            self.is_ok = self.connection().is_ok()?;
-            Some(info_suffx)
-        } else {
-            None
-        };
+        let mut buf = [0u8; 4096];
 
-        let mut info = String::from_utf8(self.info_prefix.unwrap())?;
+        while self.read(&mut buf)? > 0 {}
 
-        if let Some(info_suffix) = info_suffix {
-            info.push_str(&info_suffix);
-        }
-
-        match self.is_ok {
+        let info = self.connection().read_string()?;
+        match self.connection().is_ok()? {
             true => Ok((self.client, info)),
             false => Err(CommandFailed { message: info }),
         }
@@ -112,51 +132,20 @@ impl<T> Read for Response<T>
 where
     T: DatabaseStream,
 {
+    /// Reads the result without the escape bytes, leaving the info and status that follow it for [`close`].
+    ///
+    /// [`close`]: Response::close
     fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
-        if self.info_prefix.is_some() {
+        if self.result_complete {
             return Ok(0);
         }
 
-        let size = self.connection().read(buf)?;
-        let mut escape = false;
-        let mut shift = 0usize;
-        let mut position: Option<usize> = None;
-
-        for i in 0..size {
-            if buf[i] == 0xFF && !escape {
-                escape = true;
-                shift += 1;
-                continue;
-            }
-            if buf[i] == 0 && !escape {
-                position = Some(i);
-                break;
-            }
-
-            escape = false;
-            buf[i - shift] = buf[i];
-        }
-
-        if let Some(position) = position {
-            if size > position + 1 {
-                self.info_prefix = match buf[position + 1..size].iter().position(|&b| b == 0) {
-                    Some(length) => {
-                        self.info_complete = true;
-                        self.is_ok = match buf[..size][position + 1 + length + 1] {
-                            0 => true,
-                            1 => false,
-                            other => panic!("Invalid status byte \"{}\"", other),
-                        };
-                        Some(buf[position + 1..position + 1 + length].to_vec())
-                    }
-                    None => Some(buf[position + 1..size].to_vec()),
-                };
-            }
+        let mut unescaper = std::mem::take(&mut self.unescaper);
+        let (size, terminated) = self.connection().read_escaped_into(buf, &mut unescaper)?;
+        self.unescaper = unescaper;
+        self.result_complete = terminated;
 
-            return Ok(position - shift);
-        }
-
-        Ok(size - shift)
+        Ok(size)
     }
 }
 
@@ -164,6 +153,8 @@ where
 mod tests {
     use super::*;
     use crate::ClientError;
+    use std::io::ErrorKind;
+    use test_case::test_case;
 
     #[test]
     fn test_closing_returns_info() {
@@ -190,7 +181,7 @@ mod tests {
 
     #[test]
     fn test_reading_result_from_response() {
-        let connection = Connection::from_str("result\0info\0\0".to_owned());
+        let connection = Connection::from_str("result\0info\0\0");
         let client = Client::new(connection);
         let mut response = Response::new(client);
         let mut actual_response = String::new();
@@ -200,6 +191,20 @@ mod tests {
         assert_eq!(expected_response, actual_response);
     }
 
+    #[cfg(feature = "encoding_rs")]
+    #[test]
+    fn test_reading_latin_1_result_from_response() {
+        let connection = Connection::from_bytes(b"caf\xE9\0info\0\0");
+        let mut response = Response::new(Client::new(connection));
+
+        let actual_response = response
+            .read_to_string_with_encoding(encoding_rs::WINDOWS_1252)
+            .unwrap();
+
+        assert_eq!("café", actual_response);
+        assert_eq!("info", response.close().unwrap().1);
+    }
+
     #[test]
     fn test_reading_result_from_response_on_multiple_read_calls() {
         let connection = Connection::from_str("result".repeat(10) + "\0info\0\0");
@@ -252,20 +257,55 @@ mod tests {
     }
 
     #[test]
-    #[should_panic]
-    fn test_reading_panics_on_invalid_status_byte() {
+    fn test_reading_fails_on_invalid_status_byte() {
         let connection = Connection::from_str("partial_result\0test_error\0\u{2}");
         let client = Client::new(connection);
 
-        let _ = Response::new(client).read(&mut [0u8; 27]);
+        let actual_error = Response::new(client).close().expect_err("Operation must fail");
+
+        assert!(matches!(actual_error, ClientError::Protocol(_)));
     }
 
     #[test]
-    #[should_panic]
-    fn test_reading_panics_on_incomplete_result() {
+    fn test_closing_fails_on_incomplete_result() {
         let connection = Connection::from_str("partial_result");
         let client = Client::new(connection);
 
-        let _ = Response::new(client).close();
+        let actual_error = Response::new(client).close().expect_err("Operation must fail");
+
+        assert!(matches!(actual_error, ClientError::Io(e) if e.kind() == ErrorKind::UnexpectedEof));
+    }
+
+    #[allow(clippy::unused_unit)]
+    #[test_case(1)]
+    #[test_case(2)]
+    #[test_case(4096)]
+    fn test_reading_result_with_escape_bytes_in_chunks(size: usize) {
+        let connection = Connection::from_bytes(b"res\xFF\0ult\xFF\xFF\0info\0");
+        let mut response = Response::new(Client::new(connection));
+        let mut actual_response = vec![];
+        let mut buf = vec![0u8; size];
+
+        loop {
+            match response.read(&mut buf).unwrap() {
+                0 => break,
+                read => actual_response.extend_from_slice(&buf[..read]),
+            }
+        }
+        let (_, actual_info) = response.close().unwrap();
+
+        assert_eq!(b"res\0ult\xFF".to_vec(), actual_response);
+        assert_eq!("info", actual_info);
+    }
+
+    #[test]
+    fn test_closing_leaves_next_response_unread() {
+        let connection = Connection::from_str("result\0info\0\0next");
+        let response = Response::new(Client::new(connection));
+
+        let (client, actual_info) = response.close().unwrap();
+
+        assert_eq!("info", actual_info);
+        assert_eq!("next", client.into_inner().read_string().unwrap());
     }
 }
@@ -0,0 +1,337 @@
+use std::cmp::Ordering;
+use std::fmt::{Debug, Display, Formatter};
+
+/// Provides information about the server and its options as reported by the
+/// [`INFO`](https://docs.basex.org/wiki/Commands#INFO) command.
+///
+/// Values the server did not report are returned as `None`.
+///
+/// # Example
+/// ```
+/// # use basex::{Client, Result, ServerInfo};
+/// # fn main() -> Result<()> {
+/// let mut client = Client::connect("localhost", 1984, "admin", "admin")?;
+/// let info = client.info()?;
+///
+/// println!("Version: {:?}", info.version());
+/// println!("Used Memory: {:?}", info.used_memory());
+/// println!("Max Memory: {:?}", info.max_memory());
+/// println!("Database Path: {:?}", info.database_path());
+/// println!("Main Memory: {:?}", info.main_memory());
+/// println!("Port: {:?}", info.option("PORT"));
+/// # Ok(())
+/// # }
+/// ```
+pub trait ServerInfo: Debug + Display + Clone + PartialEq {
+    /// Version of the server, e.g. `9.6.4`.
+    fn version(&self) -> Option<String>;
+
+    /// Memory currently used by the server, e.g. `62 MB`.
+    fn used_memory(&self) -> Option<String>;
+
+    /// Memory available to the server, e.g. `3 GB`.
+    fn max_memory(&self) -> Option<String>;
+
+    /// Directory where the databases are stored, i.e. the `DBPATH` option.
+    fn database_path(&self) -> Option<String>;
+
+    /// Directory where the XQuery modules are installed, i.e. the `REPOPATH` option.
+    fn repository_path(&self) -> Option<String>;
+
+    /// Directory where the logs are written, i.e. the `LOGPATH` option.
+    fn log_path(&self) -> Option<String>;
+
+    /// Whether new databases are created in main memory, i.e. the `MAINMEM` option.
+    fn main_memory(&self) -> Option<bool>;
+
+    /// Value of the global or local option `name`, e.g. `PORT`.
+    fn option(&self, name: &str) -> Option<String>;
+
+    /// The unparsed output of the command.
+    fn raw(&self) -> &str;
+}
+
+/// Finds the first indented line of the `raw` output starting with `header`, followed by a colon, and returns the rest
+/// of it.
+pub(crate) fn property<'a>(raw: &'a str, header: &str) -> Option<&'a str> {
+    raw.lines().find_map(|line| {
+        let (name, value) = line.trim_start().split_once(':')?;
+        match name == header {
+            true => Some(value.trim()),
+            false => None,
+        }
+    })
+}
+
+/// Version of the server as reported by [`Client::server_version`], e.g. `9.6.4` or `11.0 beta`.
+///
+/// Versions are ordered by their numbers, a pre-release coming before the release it leads to. Missing numbers are
+/// zero, so that `10.7` equals `10.7.0`.
+///
+/// # Example
+///
+/// ```
+/// # use basex::Version;
+/// let version = Version::parse("10.7").unwrap();
+/// assert_eq!((10, 7, 0), (version.major(), version.minor(), version.patch()));
+/// assert!(version > Version::parse("9.6.4").unwrap());
+/// assert!(version > Version::parse("10.7 beta").unwrap());
+/// ```
+///
+/// [`Client::server_version`]: crate::Client::server_version
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Version {
+    major: u32,
+    minor: u32,
+    patch: u32,
+    pre_release: Option<String>,
+}
+
+impl Version {
+    /// Parses the `version` reported by the server, i.e. up to three numbers separated by dots, optionally followed by
+    /// the pre-release label after a space or a dash. Returns `None` for a version in any other format.
+    pub fn parse(version: &str) -> Option<Self> {
+        let version = version.trim();
+        let (numbers, pre_release) = match version.find([' ', '-']) {
+            Some(index) => (&version[..index], Some(version[index + 1..].trim())),
+            None => (version, None),
+        };
+
+        let mut parts = numbers.split('.');
+        let mut numbers = [0u32; 3];
+        for (i, part) in parts.by_ref().take(3).enumerate() {
+            numbers[i] = part.parse().ok()?;
+        }
+        if parts.next().is_some() {
+            return None;
+        }
+
+        Some(Self {
+            major: numbers[0],
+            minor: numbers[1],
+            patch: numbers[2],
+            pre_release: pre_release.filter(|label| !label.is_empty()).map(str::to_owned),
+        })
+    }
+
+    /// The major version, e.g. `9` of `9.6.4`.
+    pub fn major(&self) -> u32 {
+        self.major
+    }
+
+    /// The minor version, e.g. `6` of `9.6.4`.
+    pub fn minor(&self) -> u32 {
+        self.minor
+    }
+
+    /// The patch version, e.g. `4` of `9.6.4`.
+    pub fn patch(&self) -> u32 {
+        self.patch
+    }
+
+    /// The pre-release label, e.g. `beta` of `11.0 beta`.
+    pub fn pre_release(&self) -> Option<&str> {
+        self.pre_release.as_deref()
+    }
+}
+
+impl Display for Version {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}.{}.{}", self.major, self.minor, self.patch)?;
+        match &self.pre_release {
+            Some(label) => write!(f, " {}", label),
+            None => Ok(()),
+        }
+    }
+}
+
+impl PartialOrd for Version {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Version {
+    fn cmp(&self, other: &Self) -> Ordering {
+        (self.major, self.minor, self.patch)
+            .cmp(&(other.major, other.minor, other.patch))
+            .then_with(|| match (&self.pre_release, &other.pre_release) {
+                (None, None) => Ordering::Equal,
+                (None, Some(_)) => Ordering::Greater,
+                (Some(_), None) => Ordering::Less,
+                (Some(this), Some(other)) => this.cmp(other),
+            })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct RawServerInfo {
+    raw: String,
+}
+
+impl Display for RawServerInfo {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.raw)
+    }
+}
+
+impl RawServerInfo {
+    pub fn new(raw: String) -> Self {
+        Self { raw }
+    }
+
+    fn string_from(&self, header: &str) -> Option<String> {
+        property(&self.raw, header).map(|value| value.to_owned())
+    }
+
+    fn bool_from(&self, header: &str) -> Option<bool> {
+        match self.string_from(header)?.as_str() {
+            "true" => Some(true),
+            "false" => Some(false),
+            _ => None,
+        }
+    }
+}
+
+impl ServerInfo for RawServerInfo {
+    fn version(&self) -> Option<String> {
+        self.string_from("Version")
+    }
+
+    fn used_memory(&self) -> Option<String> {
+        self.string_from("Used Memory")
+    }
+
+    fn max_memory(&self) -> Option<String> {
+        self.string_from("Max Memory")
+    }
+
+    fn database_path(&self) -> Option<String> {
+        self.string_from("DBPATH")
+    }
+
+    fn repository_path(&self) -> Option<String> {
+        self.string_from("REPOPATH")
+    }
+
+    fn log_path(&self) -> Option<String> {
+        self.string_from("LOGPATH")
+    }
+
+    fn main_memory(&self) -> Option<bool> {
+        self.bool_from("MAINMEM")
+    }
+
+    fn option(&self, name: &str) -> Option<String> {
+        self.string_from(&name.to_uppercase())
+    }
+
+    fn raw(&self) -> &str {
+        &self.raw
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use test_case::test_case;
+
+    pub static SERVER_INFO: &str = "General Information
+ Version: 9.6.4
+ Used Memory: 62 MB
+ Max Memory: 3 GB
+
+Global Options
+ DEBUG: false
+ DBPATH: /srv/basex/data
+ LOGPATH: .logs
+ REPOPATH: /srv/basex/repo
+ PORT: 1984
+
+Local Options
+ MAINMEM: false
+ ADDCACHE: false
+";
+
+    #[test]
+    fn test_parses_with_correct_values() {
+        let info = RawServerInfo::new(SERVER_INFO.to_owned());
+
+        assert_eq!(Some("9.6.4".to_owned()), info.version());
+        assert_eq!(Some("62 MB".to_owned()), info.used_memory());
+        assert_eq!(Some("3 GB".to_owned()), info.max_memory());
+        assert_eq!(Some("/srv/basex/data".to_owned()), info.database_path());
+        assert_eq!(Some("/srv/basex/repo".to_owned()), info.repository_path());
+        assert_eq!(Some(".logs".to_owned()), info.log_path());
+        assert_eq!(Some(false), info.main_memory());
+        assert_eq!(Some("1984".to_owned()), info.option("port"));
+        assert_eq!(SERVER_INFO, info.raw());
+    }
+
+    #[test]
+    fn test_missing_values_are_none() {
+        let info = RawServerInfo::new("General Information\n MAINMEM: maybe\n".to_owned());
+
+        assert_eq!(None, info.version());
+        assert_eq!(None, info.database_path());
+        assert_eq!(None, info.main_memory());
+        assert_eq!(None, info.option("PORT"));
+    }
+
+    #[allow(clippy::unused_unit)]
+    #[test_case("10.7", 10, 7, 0, None; "major and minor")]
+    #[test_case("9.6.4", 9, 6, 4, None; "patch")]
+    #[test_case("11.0 beta", 11, 0, 0, Some("beta"); "beta")]
+    #[test_case("9.5.2-SNAPSHOT", 9, 5, 2, Some("SNAPSHOT"); "snapshot")]
+    #[test_case(" 8 ", 8, 0, 0, None; "major only")]
+    fn test_parses_version(version: &str, major: u32, minor: u32, patch: u32, pre_release: Option<&str>) {
+        let version = Version::parse(version).unwrap();
+
+        assert_eq!(
+            (major, minor, patch, pre_release),
+            (version.major(), version.minor(), version.patch(), version.pre_release())
+        );
+    }
+
+    #[allow(clippy::unused_unit)]
+    #[test_case(""; "empty")]
+    #[test_case("ten"; "word")]
+    #[test_case("9..4"; "missing number")]
+    #[test_case("1.2.3.4"; "too many numbers")]
+    fn test_rejects_version(version: &str) {
+        assert_eq!(None, Version::parse(version));
+    }
+
+    #[test]
+    fn test_orders_versions() {
+        let versions: Vec<Version> = ["9.5.2", "9.6.4", "10.7 beta", "10.7", "11.0"]
+            .iter()
+            .map(|version| Version::parse(version).unwrap())
+            .collect();
+
+        assert!(versions.windows(2).all(|pair| pair[0] < pair[1]));
+        assert_eq!(Version::parse("10.7"), Version::parse("10.7.0"));
+    }
+
+    #[test]
+    fn test_formats_version_as_display() {
+        assert_eq!("10.7.0", Version::parse("10.7").unwrap().to_string());
+        assert_eq!("11.0.0 beta", Version::parse("11.0 beta").unwrap().to_string());
+    }
+
+    #[test]
+    fn test_formats_as_debug() {
+        let _ = format!("{:?}", RawServerInfo::new(SERVER_INFO.to_owned()));
+    }
+
+    #[test]
+    fn test_formats_as_display() {
+        assert_eq!(SERVER_INFO, RawServerInfo::new(SERVER_INFO.to_owned()).to_string());
+    }
+
+    #[test]
+    fn test_clones() {
+        let info = RawServerInfo::new(SERVER_INFO.to_owned());
+        assert_eq!(info, info.clone());
+    }
+}
@@ -1,7 +1,42 @@
 use crate::connection::escape_reader::EscapeReader;
+use crate::connection::escape_writer::EscapeWriter;
+use crate::connection::protocol::{status, Handshake, HandshakeStep, Unescaper};
+use crate::connection::read_buffer::ReadBuffer;
+use crate::connection::write_buffer::WriteBuffer;
 use crate::{ClientError, DatabaseStream, Result};
-use std::io::{copy, Read};
+use std::any::type_name;
+use std::fmt::{Debug, Formatter};
+use std::io;
+use std::io::{copy, Read, Write};
 use std::marker::PhantomData;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError};
+
+/// Emits a trace event with the given fields when the `tracing` feature is on. Otherwise the fields are only borrowed,
+/// so that the event compiles to nothing.
+macro_rules! trace {
+    ($message:literal $(, $field:ident = $value:expr)*) => {{
+        #[cfg(feature = "tracing")]
+        tracing::trace!($($field = $value,)* $message);
+        #[cfg(not(feature = "tracing"))]
+        {
+            $(let _ = &$value;)*
+        }
+    }};
+}
+
+/// Locks the read or write buffer. The buffer stays consistent even when a thread panicked while holding it.
+fn lock<B>(buffer: &Mutex<B>) -> MutexGuard<'_, B> {
+    buffer.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+/// Marks the connection `broken` when the `result` is an error, as a command or response may have then been cut short.
+fn watch<R, E>(broken: &AtomicBool, result: std::result::Result<R, E>) -> std::result::Result<R, E> {
+    if result.is_err() {
+        broken.store(true, Ordering::Relaxed);
+    }
+    result
+}
 
 #[derive(Debug)]
 pub struct Unauthenticated;
@@ -15,15 +50,41 @@ pub struct Authenticated;
 /// As opposed to the [`Client`] or [`Query`] can do, connection does not understand what commands do or how to parse
 /// responses. It can only send them, send arguments and be read like a stream.
 ///
+/// Reads from the stream are buffered. The buffer is shared with all the clones of the connection, so that they read
+/// the same data. Writes are held back until a response is about to be read, so that a command and its arguments reach
+/// the stream in one write. The held bytes are shared with the clones as well, so that a response is never awaited
+/// before the command is sent.
+///
+/// Once reading or writing fails part way, or the server answers with something the protocol does not allow, the
+/// connection and its clones are told broken, as the server and the client may no longer agree on where a command or
+/// response starts.
+///
 /// [`Client`]: crate::client::Client
 /// [`Query`]: crate::query::Query
-#[derive(Debug)]
 pub struct Connection<T, State = Unauthenticated>
 where
     T: DatabaseStream,
 {
     state: PhantomData<State>,
     stream: T,
+    buffer: Arc<Mutex<ReadBuffer>>,
+    pending: Arc<Mutex<WriteBuffer>>,
+    broken: Arc<AtomicBool>,
+}
+
+impl<T, State> Debug for Connection<T, State>
+where
+    T: DatabaseStream,
+{
+    /// Shows the authentication state only. The stream and the buffered bytes are left out, as they may carry
+    /// sensitive data.
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let state = type_name::<State>().rsplit("::").next().unwrap_or_default();
+
+        f.debug_struct("Connection")
+            .field("state", &format_args!("{}", state))
+            .finish_non_exhaustive()
+    }
 }
 
 impl<T> Connection<T, Unauthenticated>
@@ -33,8 +94,11 @@ where
     /// Creates a connection that communicates with the database via the provided `stream`.
     pub fn new(stream: T) -> Self {
         Self {
-            state: PhantomData::default(),
+            state: PhantomData,
             stream,
+            buffer: Default::default(),
+            pending: Default::default(),
+            broken: Default::default(),
         }
     }
 
@@ -45,29 +109,33 @@ where
     /// # Arguments
     /// * `user`: Username.
     /// * `password`: Password.
+    ///
+    /// Fails with [`ClientError::Auth`] telling why, when the credentials are refused, the server challenge is not
+    /// recognized or the server answers with an unknown status.
+    ///
+    /// [`ClientError::Auth`]: crate::ClientError::Auth
     pub fn authenticate(mut self, user: &str, password: &str) -> Result<Connection<T, Authenticated>> {
-        let response = self.read_string()?;
-
-        let challenge: Vec<&str> = response.split(':').collect();
-        let server_name = challenge[0];
-        let timestamp = challenge[1];
-
-        let first_digest = md5::compute(&format!("{}:{}:{}", user, server_name, password));
-        let second_digest = md5::compute(&format!("{:x}{}", first_digest, timestamp));
-
-        let auth_string = format!("{}\0{:x}\0", user, second_digest);
-        let mut control_byte: [u8; 1] = [0];
-
-        self.stream.write_all(auth_string.as_bytes())?;
-        self.stream.read_exact(&mut control_byte)?;
-
-        if control_byte[0] != 0 {
-            return Err(ClientError::Auth);
+        let mut handshake = Handshake::new(user, password);
+        let mut buffer = lock(&self.buffer);
+        loop {
+            let available = watch(&self.broken, buffer.fill(&mut self.stream))?;
+            let (consumed, step) = handshake.receive(available)?;
+            buffer.consume(consumed);
+
+            match step {
+                HandshakeStep::Receive => {}
+                HandshakeStep::Send(answer) => self.stream.write_all(&answer)?,
+                HandshakeStep::Authenticated => break,
+            }
         }
+        drop(buffer);
 
         Ok(Connection {
             state: Default::default(),
             stream: self.stream,
+            buffer: self.buffer,
+            pending: self.pending,
+            broken: self.broken,
         })
     }
 }
@@ -76,30 +144,96 @@ impl<T> Connection<T, Authenticated>
 where
     T: DatabaseStream,
 {
+    /// Creates a connection over a stream that needs no authentication, such as an in-memory buffer.
+    pub(crate) fn authenticated(stream: T) -> Self {
+        Self {
+            state: PhantomData,
+            stream,
+            buffer: Default::default(),
+            pending: Default::default(),
+            broken: Default::default(),
+        }
+    }
+
     pub(crate) fn send_cmd(&mut self, code: u8) -> Result<&mut Self> {
-        self.stream.write_all(&[code])?;
+        self.write_all(&[code])?;
+        trace!("sent command", code = code);
 
         Ok(self)
     }
 
+    /// Sends the `argument`, escaping its bytes. The argument contents are traced only with the `tracing-payloads`
+    /// feature, which reads the whole argument into memory first.
     pub(crate) fn send_arg(&mut self, argument: &mut impl Read) -> Result<&mut Self> {
-        copy(&mut EscapeReader::new(argument), &mut self.stream)?;
+        #[cfg(feature = "tracing-payloads")]
+        let payload = {
+            let mut payload = vec![];
+            argument.read_to_end(&mut payload)?;
+            trace!("sending argument", payload = String::from_utf8_lossy(&payload).as_ref());
+            payload
+        };
+        #[cfg(feature = "tracing-payloads")]
+        let argument = &mut payload.as_slice();
+
+        let mut pending = lock(&self.pending);
+        let length = watch(
+            &self.broken,
+            copy(&mut EscapeReader::new(argument), &mut pending.writer(&mut self.stream)),
+        )?;
+        drop(pending);
+        trace!("sent argument", escaped_length = length);
 
         self.skip_arg()
     }
 
+    /// Sends the argument written by `write`, escaping its bytes as they are written. Returns what `write` returned,
+    /// or fails when the stream does.
+    ///
+    /// The protocol has no way to take back an argument, so when `write` fails, the `abort` bytes are sent unescaped
+    /// before the terminator. They are meant to make the argument invalid for the server, which then rejects it.
+    pub(crate) fn send_arg_with<F>(&mut self, write: F, abort: &[u8]) -> Result<io::Result<()>>
+    where
+        F: FnOnce(&mut dyn Write) -> io::Result<()>,
+    {
+        let mut pending = lock(&self.pending);
+        let mut stream = pending.writer(&mut self.stream);
+        let mut writer = EscapeWriter::new(&mut stream);
+        let outcome = write(&mut writer);
+        let (length, failed) = (writer.written(), writer.failed());
+        drop(pending);
+
+        match outcome {
+            Err(error) if failed => return watch(&self.broken, Err(error.into())),
+            Err(_) => self.write_all(abort)?,
+            Ok(()) => {}
+        }
+        trace!("sent argument", escaped_length = length);
+        self.skip_arg()?;
+
+        Ok(outcome)
+    }
+
     pub(crate) fn skip_arg(&mut self) -> Result<&mut Self> {
-        self.stream.write_all(&[0])?;
+        self.write_all(&[0])?;
 
         Ok(self)
     }
 
+    /// Holds the `bytes` back until a response is read.
+    fn write_all(&mut self, bytes: &[u8]) -> Result<()> {
+        Ok(watch(&self.broken, lock(&self.pending).write(&mut self.stream, bytes))?)
+    }
+
     /// Gets response string, and returns string if command was successful. Returns `CommandFailed`
     /// error with a message otherwise.
     pub(crate) fn get_response(&mut self) -> Result<String> {
         let info = self.read_string()?;
+        let is_ok = self.is_ok()?;
+        trace!("received response", length = info.len(), success = is_ok);
+        #[cfg(feature = "tracing-payloads")]
+        trace!("received response info", info = info.as_str());
 
-        if self.is_ok()? {
+        if is_ok {
             Ok(info)
         } else {
             Err(ClientError::CommandFailed { message: info })
@@ -108,10 +242,8 @@ where
 
     /// Reads return code and decodes it to TRUE on success or FALSE on error.
     pub(crate) fn is_ok(&mut self) -> Result<bool> {
-        let mut buf: [u8; 1] = [0];
-        self.stream.read_exact(&mut buf)?;
-
-        Ok(buf[0] == 0)
+        let byte = self.read_byte()?;
+        watch(&self.broken, status(byte))
     }
 }
 
@@ -122,7 +254,11 @@ where
     fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
         match buf.is_empty() {
             true => Ok(0),
-            false => self.stream.read(buf),
+            false => {
+                self.flush_pending()?;
+                let mut buffer = lock(&self.buffer);
+                watch(&self.broken, buffer.read(&mut self.stream, buf))
+            }
         }
     }
 }
@@ -136,19 +272,111 @@ where
         Ok(Self {
             state: Default::default(),
             stream: self.stream.try_clone()?,
+            buffer: Arc::clone(&self.buffer),
+            pending: Arc::clone(&self.pending),
+            broken: Arc::clone(&self.broken),
         })
     }
 
+    /// Writes the bytes held back by this connection or any of its clones to the stream. Called before every read, so
+    /// that the server is never left waiting for the rest of a command.
+    pub(crate) fn flush_pending(&mut self) -> io::Result<()> {
+        watch(&self.broken, lock(&self.pending).flush(&mut self.stream))
+    }
+
+    /// Whether reading or writing has failed part way on this connection or any of its clones, or the server has
+    /// answered with something the protocol does not allow. The session is then of no further use.
+    pub(crate) fn is_broken(&self) -> bool {
+        self.broken.load(Ordering::Relaxed)
+    }
+
+    /// Returns a reference to the underlying stream.
+    pub(crate) fn stream(&self) -> &T {
+        &self.stream
+    }
+
+    /// Reads a single byte.
+    pub(crate) fn read_byte(&mut self) -> Result<u8> {
+        self.flush_pending()?;
+        let mut buffer = lock(&self.buffer);
+        let byte = watch(&self.broken, buffer.fill(&mut self.stream))?[0];
+        buffer.consume(1);
+
+        Ok(byte)
+    }
+
+    /// Reads bytes up to the terminating zero byte, removing the escape bytes.
+    pub(crate) fn read_escaped(&mut self) -> Result<Vec<u8>> {
+        let mut bytes: Vec<u8> = vec![];
+        self.copy_escaped(&mut bytes)?;
+
+        Ok(bytes)
+    }
+
+    /// Copies bytes up to the terminating zero byte into `out`, removing the escape bytes. Returns the number of bytes
+    /// written.
+    pub(crate) fn copy_escaped<W: Write>(&mut self, out: &mut W) -> Result<u64> {
+        let mut written = 0u64;
+        let mut unescaper = Unescaper::new();
+        let mut chunk = [0u8; 4096];
+        self.flush_pending()?;
+        let mut buffer = lock(&self.buffer);
+        loop {
+            let available = watch(&self.broken, buffer.fill(&mut self.stream))?;
+            let decoded = unescaper.decode(available, &mut chunk);
+            buffer.consume(decoded.consumed);
+            watch(&self.broken, out.write_all(&chunk[..decoded.written]))?;
+            written += decoded.written as u64;
+
+            if decoded.terminated {
+                return Ok(written);
+            }
+        }
+    }
+
+    /// Reads bytes into `buf` up to the terminating zero byte, removing the escape bytes. The terminator is consumed
+    /// but not copied. Returns the number of bytes read, which is zero only for an empty `buf` or once the terminator
+    /// is reached, and whether it was reached.
+    ///
+    /// The `unescaper` is kept by the caller between the calls, so that `buf` may be as small as a single byte.
+    pub(crate) fn read_escaped_into(&mut self, buf: &mut [u8], unescaper: &mut Unescaper) -> Result<(usize, bool)> {
+        if buf.is_empty() {
+            return Ok((0, false));
+        }
+
+        self.flush_pending()?;
+        let mut buffer = lock(&self.buffer);
+        loop {
+            let available = watch(&self.broken, buffer.fill(&mut self.stream))?;
+            let decoded = unescaper.decode(available, buf);
+            buffer.consume(decoded.consumed);
+
+            if decoded.written > 0 || decoded.terminated {
+                return Ok((decoded.written, decoded.terminated));
+            }
+        }
+    }
+
+    /// Reads a UTF-8 string up to the terminating zero byte.
     pub(crate) fn read_string(&mut self) -> Result<String> {
         let mut raw_string: Vec<u8> = vec![];
+        self.flush_pending()?;
+        let mut buffer = lock(&self.buffer);
         loop {
-            let mut buf: [u8; 1] = [0];
-            self.stream.read_exact(&mut buf)?;
-
-            if buf[0] == 0 {
-                break;
+            let available = watch(&self.broken, buffer.fill(&mut self.stream))?;
+
+            match available.iter().position(|&b| b == 0) {
+                Some(length) => {
+                    raw_string.extend_from_slice(&available[..length]);
+                    buffer.consume(length + 1);
+                    break;
+                }
+                None => {
+                    raw_string.extend_from_slice(available);
+                    let length = available.len();
+                    buffer.consume(length);
+                }
             }
-            raw_string.push(buf[0]);
         }
 
         Ok(String::from_utf8(raw_string)?)
@@ -168,13 +396,16 @@ where
 mod tests {
     use super::*;
     use crate::tests::{FailingStream, MockStream};
+    use crate::{AuthFailure, TeeStream};
     use std::io::Read;
+    use test_case::test_case;
 
     impl<T, State> Connection<T, State>
     where
         T: DatabaseStream,
     {
-        pub(crate) fn into_inner(self) -> T {
+        pub(crate) fn into_inner(mut self) -> T {
+            self.flush_pending().unwrap();
             self.stream
         }
     }
@@ -184,6 +415,9 @@ mod tests {
             Self {
                 state: Default::default(),
                 stream: FailingStream,
+                buffer: Default::default(),
+                pending: Default::default(),
+                broken: Default::default(),
             }
         }
     }
@@ -193,6 +427,9 @@ mod tests {
             Self {
                 state: Default::default(),
                 stream: MockStream::new(s.as_ref().to_owned()),
+                buffer: Default::default(),
+                pending: Default::default(),
+                broken: Default::default(),
             }
         }
 
@@ -200,23 +437,41 @@ mod tests {
             Self {
                 state: Default::default(),
                 stream: MockStream::from_bytes(bytes),
+                buffer: Default::default(),
+                pending: Default::default(),
+                broken: Default::default(),
             }
         }
     }
 
     #[test]
     fn test_authenticated_formats_as_debug() {
-        format!("{:?}", Authenticated);
+        let _ = format!("{:?}", Authenticated);
     }
 
     #[test]
     fn test_unauthenticated_formats_as_debug() {
-        format!("{:?}", Unauthenticated);
+        let _ = format!("{:?}", Unauthenticated);
     }
 
     #[test]
     fn test_formats_as_debug() {
-        format!("{:?}", Connection::failing());
+        let _ = format!("{:?}", Connection::failing());
+    }
+
+    #[test]
+    fn test_formats_state_without_stream_as_debug() {
+        let unauthenticated = Connection::new(MockStream::new("secret".to_owned()));
+        let authenticated = Connection::from_str("secret");
+
+        assert_eq!(
+            "Connection { state: Unauthenticated, .. }",
+            format!("{:?}", unauthenticated)
+        );
+        assert_eq!(
+            "Connection { state: Authenticated, .. }",
+            format!("{:?}", authenticated)
+        );
     }
 
     #[test]
@@ -244,12 +499,84 @@ mod tests {
         assert_eq!(expected_buffer, actual_buffer);
     }
 
+    #[test]
+    fn test_connection_writes_command_with_arguments_at_once_before_reading_response() {
+        let mut log = vec![];
+        {
+            let stream = TeeStream::new(MockStream::new("Resource added.\0".to_owned()), &mut log);
+            let mut connection = Connection::authenticated(stream);
+            connection
+                .send_cmd(9)
+                .unwrap()
+                .send_arg(&mut "lunar.xml".as_bytes())
+                .unwrap()
+                .send_arg(&mut "<lunar/>".as_bytes())
+                .unwrap();
+            connection.get_response().unwrap();
+        }
+
+        let hex = |bytes: &[u8]| -> String { bytes.iter().map(|byte| format!("{:02x}", byte)).collect() };
+        assert_eq!(
+            format!(
+                "> {}\n< {}\n",
+                hex(b"\x09lunar.xml\0<lunar/>\0"),
+                hex(b"Resource added.\0\0")
+            ),
+            String::from_utf8(log).unwrap()
+        );
+    }
+
+    #[cfg(feature = "tracing")]
+    #[test]
+    fn test_connection_traces_commands_arguments_and_responses() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+        use tracing::span::{Attributes, Id, Record};
+        use tracing::{Event, Metadata};
+
+        #[derive(Default)]
+        struct CountingSubscriber(Arc<AtomicUsize>);
+
+        impl tracing::Subscriber for CountingSubscriber {
+            fn enabled(&self, _: &Metadata<'_>) -> bool {
+                true
+            }
+
+            fn new_span(&self, _: &Attributes<'_>) -> Id {
+                Id::from_u64(1)
+            }
+
+            fn record(&self, _: &Id, _: &Record<'_>) {}
+
+            fn record_follows_from(&self, _: &Id, _: &Id) {}
+
+            fn event(&self, _: &Event<'_>) {
+                self.0.fetch_add(1, Ordering::SeqCst);
+            }
+
+            fn enter(&self, _: &Id) {}
+
+            fn exit(&self, _: &Id) {}
+        }
+
+        let events = Arc::new(AtomicUsize::new(0));
+        let subscriber = CountingSubscriber(Arc::clone(&events));
+        let mut connection = Connection::from_str("test_response\0\0");
+
+        tracing::subscriber::with_default(subscriber, || {
+            connection.send_cmd(1).unwrap().send_arg(&mut "foo".as_bytes()).unwrap();
+            connection.get_response().unwrap();
+        });
+
+        let expected_events = if cfg!(feature = "tracing-payloads") { 5 } else { 3 };
+        assert_eq!(expected_events, events.load(Ordering::SeqCst));
+    }
+
     #[test]
     fn test_connection_fails_to_send_command_with_failing_stream() {
         let mut connection = Connection::failing();
-        let result = connection.send_cmd(1);
+        connection.send_cmd(1).unwrap().skip_arg().unwrap();
 
-        let actual_error = result.err().expect("Operation must fail");
+        let actual_error = connection.get_response().expect_err("Operation must fail");
 
         assert!(matches!(actual_error, ClientError::Io(_)));
     }
@@ -288,6 +615,41 @@ mod tests {
         assert!(matches!(actual_error, ClientError::CommandFailed{ message } if message == "test_error"));
     }
 
+    #[test]
+    fn test_connection_fails_to_get_response_with_invalid_status_byte() {
+        let mut connection = Connection::from_str("test_response\0\u{2}");
+        let actual_error = connection.get_response().expect_err("Operation must fail");
+
+        assert!(matches!(actual_error, ClientError::Protocol(_)));
+    }
+
+    #[test]
+    fn test_connection_is_not_broken_by_failed_command() {
+        let mut connection = Connection::from_str("test_error\0\u{1}");
+        let _ = connection.get_response();
+
+        assert!(!connection.is_broken());
+    }
+
+    #[test]
+    fn test_connection_is_broken_by_invalid_status_byte() {
+        let mut connection = Connection::from_str("test_response\0\u{2}");
+        let _ = connection.get_response();
+
+        assert!(connection.is_broken());
+    }
+
+    #[test]
+    fn test_connection_and_its_clones_are_broken_by_unexpected_end_of_stream() {
+        let mut connection = Connection::from_str("test_resp");
+        let cloned_connection = connection.try_clone().unwrap();
+        connection.read_string().unwrap();
+        let _ = connection.get_response();
+
+        assert!(connection.is_broken());
+        assert!(cloned_connection.is_broken());
+    }
+
     #[test]
     fn test_connection_fails_to_get_response_with_failing_stream() {
         let mut connection = Connection::failing();
@@ -298,7 +660,7 @@ mod tests {
 
     #[test]
     fn test_connection_fails_to_get_response_with_malformed_utf_8_string() {
-        let non_utf8_sequence = &[0xa0 as u8, 0xa1];
+        let non_utf8_sequence = &[0xa0_u8, 0xa1];
         let mut connection = Connection::from_bytes(non_utf8_sequence);
         let actual_error = connection.get_response().expect_err("Operation must fail");
 
@@ -316,6 +678,37 @@ mod tests {
         assert_eq!(expected_auth_string, actual_auth_string);
     }
 
+    #[test]
+    fn test_authentication_succeeds_with_legacy_challenge() {
+        let password_digest = format!("{:x}", md5::compute("admin"));
+        let expected_auth_string = format!("admin\0{:x}\0", md5::compute(password_digest + "19501915960728"));
+        let stream = MockStream::new("19501915960728\0\0".to_owned());
+        let connection = Connection::new(stream).authenticate("admin", "admin").unwrap();
+
+        let actual_auth_string = connection.into_inner().to_string();
+
+        assert_eq!(expected_auth_string, actual_auth_string);
+    }
+
+    #[allow(clippy::unused_unit)]
+    #[test_case(""; "empty")]
+    #[test_case("BaseX:"; "missing nonce")]
+    #[test_case(":19501915960728"; "missing realm")]
+    #[test_case("BaseX:1950:1915960728"; "too many parts")]
+    fn test_authentication_fails_on_malformed_challenge(challenge: &str) {
+        let stream = MockStream::new(format!("{}\0", challenge));
+        let connection = Connection::new(stream);
+
+        let actual_error = connection
+            .authenticate("admin", "admin")
+            .expect_err("Operation must fail");
+
+        assert!(matches!(
+            actual_error,
+            ClientError::Auth(AuthFailure::UnsupportedChallenge)
+        ));
+    }
+
     #[test]
     fn test_authentication_fails_on_error_response() {
         let stream = MockStream::new("BaseX:19501915960728\0\u{1}".to_owned());
@@ -323,10 +716,9 @@ mod tests {
 
         let actual_error = connection
             .authenticate("admin", "admin")
-            .err()
-            .expect("Operation must fail");
+            .expect_err("Operation must fail");
 
-        assert!(matches!(actual_error, ClientError::Auth));
+        assert!(matches!(actual_error, ClientError::Auth(AuthFailure::BadCredentials)));
     }
 
     #[test]
@@ -364,4 +756,69 @@ mod tests {
 
         assert_eq!(expected_bytes, actual_bytes);
     }
+
+    #[test]
+    fn test_read_multi_kilobyte_string_from_connection() {
+        let expected_string = "0123456789".repeat(1_000);
+        let mut connection = Connection::from_str(format!("{}\0", expected_string));
+
+        let actual_string = connection.read_string().unwrap();
+
+        assert_eq!(expected_string, actual_string);
+    }
+
+    #[test]
+    fn test_copy_escaped_bytes_spanning_multiple_chunks() {
+        let mut bytes = [0xFFu8, 0, 7].repeat(3000);
+        bytes.extend([0, 1]);
+        let mut connection = Connection::from_bytes(&bytes);
+        let mut out: Vec<u8> = vec![];
+
+        let size = connection.copy_escaped(&mut out).unwrap();
+
+        assert_eq!([0u8, 7].repeat(3000), out);
+        assert_eq!(6000, size);
+        assert_eq!(1, connection.read_byte().unwrap());
+    }
+
+    #[test]
+    fn test_read_escaped_into_single_byte_buffer() {
+        let mut connection = Connection::from_bytes(&[1, 0xFF, 0, 0xFF, 0xFF, 2, 0, 3]);
+        let mut unescaper = Unescaper::new();
+        let mut actual_bytes = vec![];
+        let mut buf = [0u8; 1];
+
+        loop {
+            match connection.read_escaped_into(&mut buf, &mut unescaper).unwrap() {
+                (_, true) => break,
+                (size, false) => actual_bytes.extend_from_slice(&buf[..size]),
+            }
+        }
+
+        assert_eq!(vec![1u8, 0, 0xFF, 2], actual_bytes);
+        assert_eq!(3, connection.read_byte().unwrap());
+    }
+
+    #[test]
+    fn test_bytes_buffered_by_read_string_stay_readable() {
+        let mut connection = Connection::from_str("first\0second");
+
+        let actual_string = connection.read_string().unwrap();
+        let mut actual_rest = String::new();
+        let _ = connection.read_to_string(&mut actual_rest).unwrap();
+
+        assert_eq!("first", actual_string);
+        assert_eq!("second\u{0}", actual_rest);
+    }
+
+    #[test]
+    fn test_cloned_connection_shares_buffered_bytes() {
+        let mut connection = Connection::from_str("first\0second\0");
+        let mut cloned_connection = connection.try_clone().unwrap();
+
+        let _ = connection.read_string().unwrap();
+        let actual_string = cloned_connection.read_string().unwrap();
+
+        assert_eq!("second", actual_string);
+    }
 }
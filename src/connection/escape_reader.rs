@@ -1,4 +1,6 @@
+use crate::connection::protocol::escape;
 use std::cmp::min;
+use std::collections::VecDeque;
 use std::io::Read;
 
 /// Wraps a reader and escapes all bytes with special meaning as defined by
@@ -16,7 +18,7 @@ where
     R: Read,
 {
     inner: &'a mut R,
-    accumulator: Vec<u8>,
+    staging: VecDeque<u8>,
 }
 
 impl<'a, R> EscapeReader<'a, R>
@@ -26,9 +28,20 @@ where
     pub(crate) fn new(inner: &'a mut R) -> Self {
         Self {
             inner,
-            accumulator: vec![],
+            staging: VecDeque::new(),
         }
     }
+
+    /// Moves as many staged bytes into `buf` as fit and returns how many were moved.
+    fn drain_into(&mut self, buf: &mut [u8]) -> usize {
+        let size = min(buf.len(), self.staging.len());
+
+        for (target, byte) in buf.iter_mut().zip(self.staging.drain(..size)) {
+            *target = byte;
+        }
+
+        size
+    }
 }
 
 impl<R> Read for EscapeReader<'_, R>
@@ -36,52 +49,45 @@ where
     R: Read,
 {
     fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
-        let accumulator_length = min(buf.len(), self.accumulator.len());
-
-        for buf in buf.iter_mut().take(accumulator_length) {
-            *buf = self.accumulator.pop().unwrap();
+        if buf.is_empty() {
+            return Ok(0);
+        }
+        if !self.staging.is_empty() {
+            return Ok(self.drain_into(buf));
         }
 
-        let stream_length = self.inner.read(&mut buf[accumulator_length..])?;
-        let size = accumulator_length + stream_length;
-        let escape_chars_count = buf[accumulator_length..size]
-            .iter()
-            .filter(|b| **b == 0 || **b == 0xFF)
-            .count();
-        let escaped_size = size + escape_chars_count;
-        let mut shift = escape_chars_count;
-        let mut next_skip = false;
-
-        for i in (accumulator_length..escaped_size).rev() {
-            if next_skip {
-                next_skip = false;
-                continue;
-            }
-            if i >= buf.len() {
-                self.accumulator.push(buf[i - shift]);
-            } else {
-                buf[i] = buf[i - shift];
-            }
+        let size = self.inner.read(buf)?;
+        self.staging.reserve(size * 2);
 
-            if buf[i - shift] == 0xFF || buf[i - shift] == 0 {
-                if i <= buf.len() {
-                    buf[i - 1] = 0xFF;
-                } else {
-                    self.accumulator.push(0xFF);
-                }
-                shift -= 1;
-                next_skip = true;
-            }
-        }
+        escape(&buf[..size], &mut self.staging);
 
-        Ok(min(buf.len(), accumulator_length + stream_length + escape_chars_count))
+        Ok(self.drain_into(buf))
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::tests::ChunkedReader;
     use std::io::{empty, Read};
+    use test_case::test_case;
+
+    /// Escapes the `bytes` read in chunks of the `input_sizes` into output buffers of the `output_sizes`.
+    fn escape_in_chunks(bytes: &[u8], input_sizes: &[usize], output_sizes: &[usize]) -> Vec<u8> {
+        let mut reader = ChunkedReader::new(bytes, input_sizes);
+        let mut escaped = EscapeReader::new(&mut reader);
+        let mut actual_bytes = vec![];
+
+        for &size in output_sizes.iter().cycle() {
+            let mut buf = vec![0u8; size];
+            match escaped.read(&mut buf).unwrap() {
+                0 => break,
+                read => actual_bytes.extend_from_slice(&buf[..read]),
+            }
+        }
+
+        actual_bytes
+    }
 
     #[test]
     fn test_escaping_without_escape_bytes_leaves_buffer_intact() {
@@ -154,7 +160,38 @@ mod tests {
 
         let expected_bytes: [u8; 0] = [];
         let mut actual_bytes: [u8; 0] = [];
-        escaped.read(&mut actual_bytes).unwrap();
+        let size = escaped.read(&mut actual_bytes).unwrap();
+
+        assert_eq!(0, size);
+        assert_eq!(expected_bytes, actual_bytes);
+    }
+
+    #[test]
+    fn test_escaping_large_buffer_of_escape_bytes() {
+        let bytes = [0u8, 0xFF].repeat(512 * 1024);
+        let mut slice = &bytes[..];
+        let mut escaped = EscapeReader::new(&mut slice);
+
+        let expected_bytes = [0xFF, 0u8, 0xFF, 0xFF].repeat(512 * 1024);
+        let mut actual_bytes = vec![];
+        escaped.read_to_end(&mut actual_bytes).unwrap();
+
+        assert_eq!(expected_bytes, actual_bytes);
+    }
+
+    #[allow(clippy::unused_unit)]
+    #[test_case(&[1], &[1])]
+    #[test_case(&[1, 2, 3], &[64])]
+    #[test_case(&[64], &[1, 2, 3])]
+    #[test_case(&[2, 3, 1], &[3, 1, 2])]
+    #[test_case(&[3], &[2])]
+    fn test_escaping_in_chunks_matches_whole_buffer(input_sizes: &[usize], output_sizes: &[usize]) {
+        let bytes = [0xFF, 0, 0xFF, 7, 0xFF, 0, 0xFF, 0xFF, 0, 0, 9, 0xFF].repeat(5);
+        let mut slice = &bytes[..];
+        let mut expected_bytes = vec![];
+        EscapeReader::new(&mut slice).read_to_end(&mut expected_bytes).unwrap();
+
+        let actual_bytes = escape_in_chunks(&bytes, input_sizes, output_sizes);
 
         assert_eq!(expected_bytes, actual_bytes);
     }
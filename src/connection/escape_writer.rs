@@ -0,0 +1,103 @@
+use crate::connection::protocol::escape;
+use std::io::{Result, Write};
+
+/// Wraps a writer and escapes all bytes with special meaning as defined by
+/// [conventions](https://docs.basex.org/wiki/Server_Protocol#Conventions), like [`EscapeReader`] does for readers.
+///
+/// Remembers whether writing to the inner writer has failed, so that its errors can be told apart from the errors of
+/// whoever writes into this one.
+///
+/// [`EscapeReader`]: crate::connection::escape_reader::EscapeReader
+pub(crate) struct EscapeWriter<'a, W>
+where
+    W: Write,
+{
+    inner: &'a mut W,
+    staging: Vec<u8>,
+    written: u64,
+    failed: bool,
+}
+
+impl<'a, W> EscapeWriter<'a, W>
+where
+    W: Write,
+{
+    pub(crate) fn new(inner: &'a mut W) -> Self {
+        Self {
+            inner,
+            staging: vec![],
+            written: 0,
+            failed: false,
+        }
+    }
+
+    /// Number of escaped bytes written to the inner writer.
+    pub(crate) fn written(&self) -> u64 {
+        self.written
+    }
+
+    /// Whether writing to the inner writer has failed.
+    pub(crate) fn failed(&self) -> bool {
+        self.failed
+    }
+}
+
+impl<'a, W> Write for EscapeWriter<'a, W>
+where
+    W: Write,
+{
+    /// Writes the whole `buf` escaped, so that an escape byte is never separated from the byte it escapes.
+    fn write(&mut self, buf: &[u8]) -> Result<usize> {
+        self.staging.clear();
+        escape(buf, &mut self.staging);
+
+        match self.inner.write_all(&self.staging) {
+            Ok(()) => {
+                self.written += self.staging.len() as u64;
+                Ok(buf.len())
+            }
+            Err(error) => {
+                self.failed = true;
+                Err(error)
+            }
+        }
+    }
+
+    fn flush(&mut self) -> Result<()> {
+        let flushed = self.inner.flush();
+        self.failed |= flushed.is_err();
+        flushed
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::FailingStream;
+
+    #[test]
+    fn test_escaping_chunks_written() {
+        let mut out = vec![];
+        let mut writer = EscapeWriter::new(&mut out);
+
+        writer.write_all(&[1, 0]).unwrap();
+        writer.write_all(&[0xFF]).unwrap();
+        writer.write_all(&[]).unwrap();
+        writer.write_all(&[2]).unwrap();
+        writer.flush().unwrap();
+
+        assert_eq!(6, writer.written());
+        assert!(!writer.failed());
+        assert_eq!(vec![1u8, 0xFF, 0, 0xFF, 0xFF, 2], out);
+    }
+
+    #[test]
+    fn test_remembers_failing_inner_writer() {
+        let mut stream = FailingStream;
+        let mut writer = EscapeWriter::new(&mut stream);
+
+        writer.write_all(b"data").expect_err("Writing must fail");
+
+        assert!(writer.failed());
+    }
+}
@@ -1,7 +1,10 @@
 #[allow(clippy::module_inception)]
 mod connection;
 mod escape_reader;
+mod escape_writer;
+pub mod protocol;
+mod read_buffer;
+mod write_buffer;
 
 pub use self::connection::Authenticated;
 pub use self::connection::Connection;
-pub use self::connection::Unauthenticated;
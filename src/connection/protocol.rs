@@ -0,0 +1,476 @@
+//! The byte-level [server protocol](https://docs.basex.org/wiki/Server_Protocol) without any I/O.
+//!
+//! The types here are fed the bytes received from the server and tell which bytes to send back, so that any transport
+//! can drive the protocol, e.g. a non-blocking socket or a WebSocket in the browser. [`Connection`] is the driver for
+//! the blocking [`DatabaseStream`].
+//!
+//! [`Connection`]: crate::Connection
+//! [`DatabaseStream`]: crate::DatabaseStream
+
+use crate::{AuthFailure, ClientError, Result};
+use std::fmt::{Debug, Formatter};
+
+/// Marks the next byte as data, so that the terminator and the marker itself can be sent.
+const ESCAPE: u8 = 0xFF;
+
+/// Ends every string and argument.
+const TERMINATOR: u8 = 0;
+
+/// Writes the `input` into the `output`, prefixing the bytes with special meaning by the escape byte as defined by
+/// [conventions](https://docs.basex.org/wiki/Server_Protocol#Conventions).
+///
+/// # Example
+///
+/// ```
+/// # use basex::protocol::escape;
+/// let mut output = vec![];
+/// escape(&[0, 1, 0xFF], &mut output);
+/// assert_eq!(vec![0xFF, 0, 1, 0xFF, 0xFF], output);
+/// ```
+pub fn escape(input: &[u8], output: &mut impl Extend<u8>) {
+    for &byte in input {
+        if byte == TERMINATOR || byte == ESCAPE {
+            output.extend([ESCAPE]);
+        }
+        output.extend([byte]);
+    }
+}
+
+/// Frames the command with the `code` and its escaped `arguments`, each followed by the terminator.
+///
+/// # Example
+///
+/// ```
+/// # use basex::protocol::command;
+/// assert_eq!(b"\x03id\0$x\0".to_vec(), command(3, &[b"id", b"$x"]));
+/// ```
+pub fn command(code: u8, arguments: &[&[u8]]) -> Vec<u8> {
+    let mut framed = vec![code];
+
+    for argument in arguments {
+        escape(argument, &mut framed);
+        framed.push(TERMINATOR);
+    }
+
+    framed
+}
+
+/// Decodes the status byte ending a response, i.e. `true` on success or `false` on error. Fails with
+/// [`ClientError::Protocol`] on any other byte.
+///
+/// [`ClientError::Protocol`]: crate::ClientError::Protocol
+pub fn status(byte: u8) -> Result<bool> {
+    match byte {
+        0 => Ok(true),
+        1 => Ok(false),
+        other => Err(ClientError::invalid_status(other)),
+    }
+}
+
+/// What was done by [`Unescaper::decode`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Decoded {
+    /// Number of input bytes used, including the escape bytes and the terminator.
+    pub consumed: usize,
+    /// Number of bytes written to the output.
+    pub written: usize,
+    /// Whether the terminator was reached.
+    pub terminated: bool,
+}
+
+/// Removes the escape bytes from a result streamed in chunks of any size, up to the terminator.
+///
+/// An escape byte ending a chunk is remembered, so that the byte it escapes is taken as data even when it comes in the
+/// next chunk.
+///
+/// # Example
+///
+/// ```
+/// # use basex::protocol::{Decoded, Unescaper};
+/// let mut unescaper = Unescaper::new();
+/// let mut output = [0u8; 8];
+///
+/// let first = unescaper.decode(&[1, 0xFF], &mut output);
+/// let second = unescaper.decode(&[0, 2, 0, 3], &mut output[first.written..]);
+///
+/// assert_eq!(Decoded { consumed: 2, written: 1, terminated: false }, first);
+/// assert_eq!(Decoded { consumed: 3, written: 2, terminated: true }, second);
+/// assert_eq!([1, 0, 2], output[..3]);
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct Unescaper {
+    escaped: bool,
+}
+
+impl Unescaper {
+    /// Creates an unescaper expecting the start of a result.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Decodes as much of the `input` into the `output` as fits, stopping after the terminator. The bytes following
+    /// the terminator are left for the caller, as they belong to the next part of the response.
+    pub fn decode(&mut self, input: &[u8], output: &mut [u8]) -> Decoded {
+        let mut consumed = 0;
+        let mut written = 0;
+        let mut terminated = false;
+
+        for &byte in input {
+            if written == output.len() {
+                break;
+            }
+            consumed += 1;
+
+            match (self.escaped, byte) {
+                (false, ESCAPE) => self.escaped = true,
+                (false, TERMINATOR) => {
+                    terminated = true;
+                    break;
+                }
+                (_, byte) => {
+                    self.escaped = false;
+                    output[written] = byte;
+                    written += 1;
+                }
+            }
+        }
+
+        Decoded {
+            consumed,
+            written,
+            terminated,
+        }
+    }
+}
+
+/// What the transport does next during the [`Handshake`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum HandshakeStep {
+    /// Receive more bytes from the server.
+    Receive,
+    /// Send the bytes to the server, then receive more.
+    Send(Vec<u8>),
+    /// The server accepted the credentials.
+    Authenticated,
+}
+
+#[derive(Debug)]
+enum HandshakeState {
+    Challenge(Vec<u8>),
+    Status,
+    Authenticated,
+}
+
+/// Answers the server challenge with the credentials, as described by the
+/// [server protocol](https://docs.basex.org/wiki/Server_Protocol#Authentication).
+///
+/// # Example
+///
+/// ```
+/// # use basex::protocol::{Handshake, HandshakeStep};
+/// # fn main() -> basex::Result<()> {
+/// let mut handshake = Handshake::new("admin", "admin");
+///
+/// assert_eq!((6, HandshakeStep::Receive), handshake.receive(b"BaseX:")?);
+/// let (_, step) = handshake.receive(b"19501915960728\0")?;
+/// assert_eq!(HandshakeStep::Send(b"admin\0af13b20af0e0b0e3517a406c42622d3d\0".to_vec()), step);
+/// assert_eq!((1, HandshakeStep::Authenticated), handshake.receive(b"\0")?);
+/// # Ok(())
+/// # }
+/// ```
+pub struct Handshake {
+    user: String,
+    password: String,
+    state: HandshakeState,
+}
+
+impl Debug for Handshake {
+    /// Leaves out the password, so that it does not end up in logs.
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Handshake")
+            .field("user", &self.user)
+            .field("password", &format_args!("<redacted>"))
+            .field("state", &self.state)
+            .finish()
+    }
+}
+
+impl Handshake {
+    /// Starts the handshake authenticating as the `user` with the `password`.
+    pub fn new(user: &str, password: &str) -> Self {
+        Self {
+            user: user.to_owned(),
+            password: password.to_owned(),
+            state: HandshakeState::Challenge(vec![]),
+        }
+    }
+
+    /// Feeds the `input` received from the server. Returns how many of its bytes were used, along with what to do next.
+    /// The unused bytes belong to the responses that follow the handshake.
+    ///
+    /// Fails with [`ClientError::Auth`] telling why, when the credentials are refused, the challenge is not recognized
+    /// or the server answers with an unknown status.
+    ///
+    /// [`ClientError::Auth`]: crate::ClientError::Auth
+    pub fn receive(&mut self, input: &[u8]) -> Result<(usize, HandshakeStep)> {
+        match &mut self.state {
+            HandshakeState::Challenge(challenge) => match input.iter().position(|&b| b == TERMINATOR) {
+                Some(length) => {
+                    challenge.extend_from_slice(&input[..length]);
+                    let challenge = String::from_utf8(std::mem::take(challenge))
+                        .map_err(|_| ClientError::Auth(AuthFailure::UnsupportedChallenge))?;
+                    let digest = digest(&challenge, &self.user, &self.password)
+                        .ok_or(ClientError::Auth(AuthFailure::UnsupportedChallenge))?;
+                    self.state = HandshakeState::Status;
+
+                    let answer = format!("{}\0{:x}\0", self.user, digest);
+                    Ok((length + 1, HandshakeStep::Send(answer.into_bytes())))
+                }
+                None => {
+                    challenge.extend_from_slice(input);
+                    Ok((input.len(), HandshakeStep::Receive))
+                }
+            },
+            HandshakeState::Status => match input.first() {
+                Some(0) => {
+                    self.state = HandshakeState::Authenticated;
+                    Ok((1, HandshakeStep::Authenticated))
+                }
+                Some(1) => Err(ClientError::Auth(AuthFailure::BadCredentials)),
+                Some(_) => Err(ClientError::Auth(AuthFailure::ServerRejected)),
+                None => Ok((0, HandshakeStep::Receive)),
+            },
+            HandshakeState::Authenticated => Ok((0, HandshakeStep::Authenticated)),
+        }
+    }
+}
+
+/// Answers the `challenge` sent by the server. BaseX 8.0 and newer send `realm:nonce` and expect the digest
+/// authentication, older versions send just the `nonce` and expect CRAM-MD5. Returns `None` for a challenge in neither
+/// format.
+fn digest(challenge: &str, user: &str, password: &str) -> Option<md5::Digest> {
+    let (password_digest, nonce) = match challenge.split_once(':') {
+        Some((realm, nonce)) if !realm.is_empty() => (md5::compute(format!("{}:{}:{}", user, realm, password)), nonce),
+        Some(_) => return None,
+        None => (md5::compute(password), challenge),
+    };
+
+    match nonce.is_empty() || nonce.contains(':') {
+        true => None,
+        false => Some(md5::compute(format!("{:x}{}", password_digest, nonce))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_case::test_case;
+
+    /// Feeds the `input` to the `handshake` in chunks of the `size`, collecting the bytes to send.
+    fn handshake_in_chunks(handshake: &mut Handshake, input: &[u8], size: usize) -> Result<(Vec<u8>, bool)> {
+        let mut sent = vec![];
+        let mut authenticated = false;
+
+        for mut chunk in input.chunks(size) {
+            while !chunk.is_empty() {
+                let (consumed, step) = handshake.receive(chunk)?;
+                chunk = &chunk[consumed..];
+
+                match step {
+                    HandshakeStep::Receive => {}
+                    HandshakeStep::Send(bytes) => sent.extend(bytes),
+                    HandshakeStep::Authenticated => {
+                        authenticated = true;
+                        break;
+                    }
+                }
+            }
+        }
+
+        Ok((sent, authenticated))
+    }
+
+    #[test]
+    fn test_escapes_special_bytes() {
+        let mut output = vec![];
+
+        escape(&[1, 0, 9, 0xFF, 6], &mut output);
+
+        assert_eq!(vec![1u8, 0xFF, 0, 9, 0xFF, 0xFF, 6], output);
+    }
+
+    #[test]
+    fn test_frames_command_with_arguments() {
+        assert_eq!(b"\x01foo\0\xFF\0\0".to_vec(), command(1, &[b"foo", &[0]]));
+    }
+
+    #[test]
+    fn test_frames_command_without_arguments() {
+        assert_eq!(vec![5u8], command(5, &[]));
+    }
+
+    #[allow(clippy::unused_unit)]
+    #[test_case(0, true)]
+    #[test_case(1, false)]
+    fn test_decodes_status(byte: u8, expected: bool) {
+        assert_eq!(expected, status(byte).unwrap());
+    }
+
+    #[test]
+    fn test_fails_on_invalid_status() {
+        let actual_error = status(2).expect_err("Decoding must fail");
+
+        assert!(matches!(actual_error, ClientError::Protocol(_)));
+    }
+
+    #[allow(clippy::unused_unit)]
+    #[test_case(1)]
+    #[test_case(2)]
+    #[test_case(3)]
+    #[test_case(64)]
+    fn test_unescapes_input_in_chunks(size: usize) {
+        let input = [0xFF, 0, 1, 0xFF, 0xFF, 0xFF, 0, 2, 0, 7];
+        let mut unescaper = Unescaper::new();
+        let mut output = vec![];
+        let mut buf = [0u8; 2];
+        let mut consumed = 0;
+
+        'chunks: for mut chunk in input.chunks(size) {
+            while !chunk.is_empty() {
+                let decoded = unescaper.decode(chunk, &mut buf);
+                output.extend_from_slice(&buf[..decoded.written]);
+                chunk = &chunk[decoded.consumed..];
+                consumed += decoded.consumed;
+
+                if decoded.terminated {
+                    break 'chunks;
+                }
+            }
+        }
+
+        assert_eq!(vec![0u8, 1, 0xFF, 0, 2], output);
+        assert_eq!(9, consumed);
+    }
+
+    #[test]
+    fn test_unescaping_stops_after_terminator() {
+        let mut output = [0u8; 8];
+
+        let decoded = Unescaper::new().decode(b"ab\0cd", &mut output);
+
+        assert_eq!(
+            Decoded {
+                consumed: 3,
+                written: 2,
+                terminated: true
+            },
+            decoded
+        );
+        assert_eq!(b"ab", &output[..2]);
+    }
+
+    #[test]
+    fn test_unescaping_into_empty_output_does_nothing() {
+        let decoded = Unescaper::new().decode(b"ab\0", &mut []);
+
+        assert_eq!(
+            Decoded {
+                consumed: 0,
+                written: 0,
+                terminated: false
+            },
+            decoded
+        );
+    }
+
+    #[allow(clippy::unused_unit)]
+    #[test_case(1)]
+    #[test_case(5)]
+    #[test_case(64)]
+    fn test_handshake_answers_challenge_in_chunks(size: usize) {
+        let mut handshake = Handshake::new("admin", "admin");
+
+        let (sent, authenticated) = handshake_in_chunks(&mut handshake, b"BaseX:19501915960728\0\0", size).unwrap();
+
+        assert_eq!(b"admin\0af13b20af0e0b0e3517a406c42622d3d\0".to_vec(), sent);
+        assert!(authenticated);
+    }
+
+    #[test]
+    fn test_handshake_answers_legacy_challenge() {
+        let password_digest = format!("{:x}", md5::compute("admin"));
+        let expected = format!("admin\0{:x}\0", md5::compute(password_digest + "19501915960728"));
+        let mut handshake = Handshake::new("admin", "admin");
+
+        let (_, step) = handshake.receive(b"19501915960728\0").unwrap();
+
+        assert_eq!(HandshakeStep::Send(expected.into_bytes()), step);
+    }
+
+    #[test]
+    fn test_handshake_leaves_bytes_after_status() {
+        let mut handshake = Handshake::new("admin", "admin");
+        let _ = handshake.receive(b"BaseX:19501915960728\0").unwrap();
+
+        assert_eq!((0, HandshakeStep::Receive), handshake.receive(b"").unwrap());
+        assert_eq!((1, HandshakeStep::Authenticated), handshake.receive(b"\0next").unwrap());
+        assert_eq!((0, HandshakeStep::Authenticated), handshake.receive(b"next").unwrap());
+    }
+
+    #[allow(clippy::unused_unit)]
+    #[test_case(""; "empty")]
+    #[test_case("BaseX:"; "missing nonce")]
+    #[test_case(":19501915960728"; "missing realm")]
+    #[test_case("BaseX:1950:1915960728"; "too many parts")]
+    fn test_handshake_fails_on_malformed_challenge(challenge: &str) {
+        let mut handshake = Handshake::new("admin", "admin");
+
+        let actual_error = handshake
+            .receive(format!("{}\0", challenge).as_bytes())
+            .expect_err("Handshake must fail");
+
+        assert!(matches!(
+            actual_error,
+            ClientError::Auth(AuthFailure::UnsupportedChallenge)
+        ));
+    }
+
+    #[test]
+    fn test_handshake_fails_on_error_status() {
+        let mut handshake = Handshake::new("admin", "admin");
+
+        let actual_error =
+            handshake_in_chunks(&mut handshake, b"BaseX:19501915960728\0\x01", 64).expect_err("Handshake must fail");
+
+        assert!(matches!(actual_error, ClientError::Auth(AuthFailure::BadCredentials)));
+    }
+
+    #[test]
+    fn test_handshake_fails_on_unknown_status() {
+        let mut handshake = Handshake::new("admin", "admin");
+
+        let actual_error =
+            handshake_in_chunks(&mut handshake, b"BaseX:19501915960728\0H", 64).expect_err("Handshake must fail");
+
+        assert!(matches!(actual_error, ClientError::Auth(AuthFailure::ServerRejected)));
+    }
+
+    #[test]
+    fn test_handshake_fails_on_challenge_not_in_utf_8() {
+        let mut handshake = Handshake::new("admin", "admin");
+
+        let actual_error = handshake.receive(b"BaseX:\xFF\0").expect_err("Handshake must fail");
+
+        assert!(matches!(
+            actual_error,
+            ClientError::Auth(AuthFailure::UnsupportedChallenge)
+        ));
+    }
+
+    #[test]
+    fn test_handshake_formats_as_debug_without_password() {
+        let handshake = Handshake::new("admin", "pink_index_69");
+
+        assert!(!format!("{:?}", handshake).contains("pink_index_69"));
+    }
+}
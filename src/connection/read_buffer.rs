@@ -0,0 +1,102 @@
+use std::cmp::min;
+use std::io::{Error, ErrorKind, Read, Result};
+
+/// Size of a single read from the underlying stream.
+const CAPACITY: usize = 4096;
+
+/// Holds the bytes read from the stream ahead of being consumed, so that the stream is read in chunks instead of byte
+/// by byte.
+#[derive(Debug, Default)]
+pub(crate) struct ReadBuffer {
+    bytes: Vec<u8>,
+    position: usize,
+}
+
+impl ReadBuffer {
+    /// Returns the bytes not consumed yet, reading another chunk from the `stream` if there are none. Fails with
+    /// [`UnexpectedEof`] when the stream has ended.
+    ///
+    /// [`UnexpectedEof`]: std::io::ErrorKind::UnexpectedEof
+    pub(crate) fn fill<R: Read>(&mut self, stream: &mut R) -> Result<&[u8]> {
+        if self.position == self.bytes.len() {
+            self.bytes.resize(CAPACITY, 0);
+            self.position = 0;
+
+            match stream.read(&mut self.bytes) {
+                Ok(0) => {
+                    self.bytes.clear();
+                    return Err(Error::new(ErrorKind::UnexpectedEof, "stream ended unexpectedly"));
+                }
+                Ok(size) => self.bytes.truncate(size),
+                Err(error) => {
+                    self.bytes.clear();
+                    return Err(error);
+                }
+            }
+        }
+
+        Ok(&self.bytes[self.position..])
+    }
+
+    /// Marks the `amount` of bytes as consumed.
+    pub(crate) fn consume(&mut self, amount: usize) {
+        self.position = min(self.position + amount, self.bytes.len());
+    }
+
+    /// Reads into `buf` from the bytes not consumed yet or, once there are none, directly from the `stream`.
+    pub(crate) fn read<R: Read>(&mut self, stream: &mut R, buf: &mut [u8]) -> Result<usize> {
+        if self.position == self.bytes.len() && buf.len() >= CAPACITY {
+            return stream.read(buf);
+        }
+
+        let available = match self.fill(stream) {
+            Ok(available) => available,
+            Err(error) if error.kind() == ErrorKind::UnexpectedEof => return Ok(0),
+            Err(error) => return Err(error),
+        };
+        let size = min(available.len(), buf.len());
+        buf[..size].copy_from_slice(&available[..size]);
+        self.consume(size);
+
+        Ok(size)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_reads_stream_in_chunks() {
+        let mut stream = &b"abcdef"[..];
+        let mut buffer = ReadBuffer::default();
+
+        let mut buf = [0u8; 2];
+        assert_eq!(2, buffer.read(&mut stream, &mut buf).unwrap());
+        assert_eq!(b"ab", &buf);
+        assert!(stream.is_empty());
+        assert_eq!(b"cdef", buffer.fill(&mut stream).unwrap());
+    }
+
+    #[test]
+    fn test_large_reads_bypass_the_buffer() {
+        let data = vec![7u8; CAPACITY * 2];
+        let mut stream = &data[..];
+        let mut buffer = ReadBuffer::default();
+
+        let mut buf = vec![0u8; CAPACITY * 2];
+        assert_eq!(CAPACITY * 2, buffer.read(&mut stream, &mut buf).unwrap());
+        assert_eq!(data, buf);
+    }
+
+    #[test]
+    fn test_fill_fails_at_end_of_stream() {
+        let mut stream = &b""[..];
+        let mut buffer = ReadBuffer::default();
+
+        let actual_error = buffer.fill(&mut stream).expect_err("Operation must fail");
+
+        assert_eq!(ErrorKind::UnexpectedEof, actual_error.kind());
+        assert_eq!(0, buffer.read(&mut stream, &mut [0u8; 1]).unwrap());
+    }
+}
@@ -0,0 +1,106 @@
+use std::io::{Result, Write};
+
+/// Amount of bytes held back before they are written to the underlying stream.
+const CAPACITY: usize = 8192;
+
+/// Holds the bytes written to the stream until a response is about to be read, so that a command and its arguments
+/// are written in one go instead of piece by piece.
+#[derive(Debug, Default)]
+pub(crate) struct WriteBuffer {
+    bytes: Vec<u8>,
+}
+
+impl WriteBuffer {
+    /// Holds the `buf` back, writing the held bytes to the `stream` first when they would not fit. Writes a `buf` that
+    /// does not fit on its own directly.
+    pub(crate) fn write<W: Write>(&mut self, stream: &mut W, buf: &[u8]) -> Result<()> {
+        if self.bytes.len() + buf.len() > CAPACITY {
+            self.flush(stream)?;
+            if buf.len() >= CAPACITY {
+                return stream.write_all(buf);
+            }
+        }
+        self.bytes.extend_from_slice(buf);
+
+        Ok(())
+    }
+
+    /// Writes all the held bytes to the `stream`. The bytes are dropped even when writing fails, as it is not known how
+    /// many of them have reached the stream.
+    pub(crate) fn flush<W: Write>(&mut self, stream: &mut W) -> Result<()> {
+        if self.bytes.is_empty() {
+            return Ok(());
+        }
+        let written = stream.write_all(&self.bytes);
+        self.bytes.clear();
+
+        written
+    }
+
+    /// Borrows the buffer along with the `stream` it holds the bytes back from as a single writer.
+    pub(crate) fn writer<'a, W: Write>(&'a mut self, stream: &'a mut W) -> BufferedWriter<'a, W> {
+        BufferedWriter { buffer: self, stream }
+    }
+}
+
+/// Writer holding the bytes back in the [`WriteBuffer`] on their way to the stream.
+pub(crate) struct BufferedWriter<'a, W: Write> {
+    buffer: &'a mut WriteBuffer,
+    stream: &'a mut W,
+}
+
+impl<W: Write> Write for BufferedWriter<'_, W> {
+    fn write(&mut self, buf: &[u8]) -> Result<usize> {
+        self.buffer.write(self.stream, buf)?;
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> Result<()> {
+        self.buffer.flush(self.stream)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::FailingStream;
+
+    #[test]
+    fn test_holds_bytes_until_flushed() {
+        let mut stream = vec![];
+        let mut buffer = WriteBuffer::default();
+
+        buffer.write(&mut stream, b"OPEN ").unwrap();
+        buffer.write(&mut stream, b"lunar\0").unwrap();
+        assert!(stream.is_empty());
+
+        buffer.flush(&mut stream).unwrap();
+        assert_eq!(b"OPEN lunar\0".to_vec(), stream);
+    }
+
+    #[test]
+    fn test_writes_held_bytes_before_they_overflow() {
+        let mut stream = vec![];
+        let mut buffer = WriteBuffer::default();
+
+        buffer.write(&mut stream, &[1u8; CAPACITY - 1]).unwrap();
+        buffer.write(&mut stream, &[2u8; 2]).unwrap();
+        assert_eq!(vec![1u8; CAPACITY - 1], stream);
+
+        buffer.write(&mut stream, &[3u8; CAPACITY]).unwrap();
+        assert_eq!(CAPACITY * 2 + 1, stream.len());
+        assert_eq!(&[2u8, 2, 3], &stream[CAPACITY - 1..CAPACITY + 2]);
+    }
+
+    #[test]
+    fn test_drops_held_bytes_when_flushing_fails() {
+        let mut buffer = WriteBuffer::default();
+        buffer.write(&mut FailingStream, b"OPEN lunar\0").unwrap();
+
+        buffer.flush(&mut FailingStream).expect_err("Flushing must fail");
+
+        let mut stream = vec![];
+        buffer.flush(&mut stream).unwrap();
+        assert!(stream.is_empty());
+    }
+}
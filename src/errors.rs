@@ -3,6 +3,7 @@ use std::error;
 use std::fmt::{Display, Formatter};
 use std::io;
 use std::string::FromUtf8Error;
+use std::time::Duration;
 
 /// The error type for the DB operations of the [`Client`], [`Query`] and associated structs and traits.
 ///
@@ -16,31 +17,130 @@ pub enum ClientError {
     Io(io::Error),
     /// The byte sequence being parsed is not a valid UTF-8 sequence.
     Utf8Parse(FromUtf8Error),
-    /// The provided credentials for authorizing are invalid.
-    Auth,
+    /// The authentication has failed for the given reason.
+    Auth(AuthFailure),
     /// The command was processed but failed to get the expected result.
     CommandFailed { message: String },
     /// The query was processed but failed to get the expected result.
     QueryFailed(QueryFailed),
+    /// The server responded with data that does not follow the protocol, e.g. when the stream got desynchronized.
+    Protocol(String),
+    /// The query result could not be parsed into the requested type.
+    ResultParse { result: String, message: String },
+    /// The name is not valid for the server, so the command was not sent.
+    InvalidName(String),
+    /// The result was not read within the given time.
+    Timeout(Duration),
+    /// The command would change the database with the given name, which is opened for reading only by
+    /// [`Client::begin_readonly`], so the command was not sent.
+    ///
+    /// [`Client::begin_readonly`]: crate::Client::begin_readonly
+    ReadOnly(String),
+    /// The query result is not valid JSON.
+    #[cfg(feature = "serde_json")]
+    JsonParse(serde_json::Error),
+}
+
+impl ClientError {
+    /// Creates the error for a status byte other than the `0` of success and the `1` of failure.
+    pub(crate) fn invalid_status(status: u8) -> Self {
+        ClientError::Protocol(format!("invalid status byte {}", status))
+    }
+}
+
+/// Why the authentication has failed, as told by [`ClientError::Auth`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AuthFailure {
+    /// The server refused the user name or the password. It does not tell which of them is wrong.
+    BadCredentials,
+    /// The challenge sent by the server is in neither of the known formats, so it could not be answered.
+    UnsupportedChallenge,
+    /// The server answered the handshake with a status other than success or failure, e.g. when the stream does not
+    /// lead to a BaseX server.
+    ServerRejected,
+}
+
+impl Display for AuthFailure {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        match self {
+            AuthFailure::BadCredentials => write!(f, "wrong user name or password"),
+            AuthFailure::UnsupportedChallenge => write!(f, "unsupported server challenge"),
+            AuthFailure::ServerRejected => write!(f, "handshake rejected by the server"),
+        }
+    }
 }
 
 impl Display for ClientError {
     fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
-        match &*self {
+        match self {
             ClientError::Io(ref e) => e.fmt(f),
             ClientError::Utf8Parse(ref e) => e.fmt(f),
-            ClientError::Auth => write!(f, "access denied"),
+            ClientError::Auth(reason) => write!(f, "access denied: {}", reason),
             ClientError::CommandFailed { message } => write!(f, "{}", message),
-            ClientError::QueryFailed(q) => write!(f, "{}", q.raw()),
+            ClientError::QueryFailed(q) if q.code().is_empty() => write!(f, "{}", q.raw()),
+            ClientError::QueryFailed(q) => {
+                write!(f, "{} at {}:{}: {}", q.code(), q.line(), q.position(), q.message())
+            }
+            ClientError::Protocol(message) => write!(f, "protocol violation: {}", message),
+            ClientError::ResultParse { result, message } => {
+                write!(f, "cannot parse result \"{}\": {}", result, message)
+            }
+            ClientError::InvalidName(name) => write!(f, "invalid name \"{}\"", name),
+            ClientError::Timeout(timeout) => write!(f, "timed out after {:?}", timeout),
+            ClientError::ReadOnly(name) => write!(f, "database \"{}\" is opened for reading only", name),
+            #[cfg(feature = "serde_json")]
+            ClientError::JsonParse(e) => write!(f, "cannot parse result as JSON: {}", e),
         }
     }
 }
 
-impl error::Error for ClientError {}
+impl error::Error for ClientError {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match self {
+            ClientError::Io(e) => Some(e),
+            ClientError::Utf8Parse(e) => Some(e),
+            ClientError::QueryFailed(q) => Some(q),
+            #[cfg(feature = "serde_json")]
+            ClientError::JsonParse(e) => Some(e),
+            ClientError::Auth(_)
+            | ClientError::CommandFailed { .. }
+            | ClientError::Protocol(_)
+            | ClientError::ResultParse { .. }
+            | ClientError::InvalidName(_)
+            | ClientError::Timeout(_)
+            | ClientError::ReadOnly(_) => None,
+        }
+    }
+}
 
 impl From<io::Error> for ClientError {
+    /// Wraps the `err` unless it carries a [`ClientError`] raised from within a [`Read`] implementation, in which case
+    /// that error is unwrapped.
+    ///
+    /// [`Read`]: std::io::Read
     fn from(err: io::Error) -> ClientError {
-        ClientError::Io(err)
+        match err.get_ref() {
+            Some(inner) if inner.is::<ClientError>() => *err.into_inner().unwrap().downcast().unwrap(),
+            _ => ClientError::Io(err),
+        }
+    }
+}
+
+impl From<ClientError> for io::Error {
+    /// Carries the `err` through the [`Read`] implementations, so that it converts back into itself.
+    ///
+    /// [`Read`]: std::io::Read
+    fn from(err: ClientError) -> io::Error {
+        match err {
+            ClientError::Io(e) => e,
+            other => io::Error::new(io::ErrorKind::InvalidData, other),
+        }
+    }
+}
+
+impl From<QueryFailed> for ClientError {
+    fn from(err: QueryFailed) -> ClientError {
+        ClientError::QueryFailed(err)
     }
 }
 
@@ -53,42 +153,42 @@ impl From<FromUtf8Error> for ClientError {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use std::io::ErrorKind;
+    use std::error::Error;
 
     #[test]
     fn test_io_error_formats_as_debug() {
-        let error = ClientError::Io(io::Error::new(ErrorKind::Other, "test"));
+        let error = ClientError::Io(io::Error::other("test"));
         let _ = format!("{:?}", error);
     }
 
     #[test]
     fn test_io_error_formats_as_empty() {
-        let error = ClientError::Io(io::Error::new(ErrorKind::Other, "test"));
+        let error = ClientError::Io(io::Error::other("test"));
         let _ = format!("{}", error);
     }
 
     #[test]
     fn test_utf8_parse_formats_as_debug() {
-        let error = ClientError::Utf8Parse(String::from_utf8(vec![0xa0 as u8, 0xa1]).unwrap_err());
+        let error = ClientError::Utf8Parse(String::from_utf8(vec![0xa0_u8, 0xa1]).unwrap_err());
         let _ = format!("{:?}", error);
     }
 
     #[test]
     fn test_utf8_parse_formats_as_empty() {
-        let error = ClientError::Utf8Parse(String::from_utf8(vec![0xa0 as u8, 0xa1]).unwrap_err());
+        let error = ClientError::Utf8Parse(String::from_utf8(vec![0xa0_u8, 0xa1]).unwrap_err());
         let _ = format!("{}", error);
     }
 
     #[test]
     fn test_auth_formats_as_debug() {
-        let error = ClientError::Auth;
+        let error = ClientError::Auth(AuthFailure::BadCredentials);
         let _ = format!("{:?}", error);
     }
 
     #[test]
-    fn test_auth_formats_as_empty() {
-        let error = ClientError::Auth;
-        let _ = format!("{}", error);
+    fn test_auth_formats_with_reason() {
+        let error = ClientError::Auth(AuthFailure::BadCredentials);
+        assert_eq!("access denied: wrong user name or password", error.to_string());
     }
 
     #[test]
@@ -116,10 +216,164 @@ mod tests {
     }
 
     #[test]
-    fn test_query_failed_formats_as_empty() {
-        let error = ClientError::QueryFailed(QueryFailed::new(
-            "Stopped at ., 1/1: [XPST0008] Undeclared variable $x.".to_owned(),
-        ));
-        let _ = format!("{}", error);
+    fn test_query_failed_formats_with_code_and_location() {
+        let error: ClientError =
+            QueryFailed::new("Stopped at ., 1/5: [XPST0008] Undeclared variable $x.".to_owned()).into();
+        assert_eq!("XPST0008 at 1:5: Undeclared variable $x.", format!("{}", error));
+    }
+
+    #[test]
+    fn test_unparsed_query_failed_formats_as_raw() {
+        let error: ClientError = QueryFailed::new("Connection reset by peer".to_owned()).into();
+        assert_eq!("Connection reset by peer", format!("{}", error));
+    }
+
+    #[test]
+    fn test_query_failed_converts_from_query_failed() {
+        let raw = "Stopped at ., 1/5: [XPST0008] Undeclared variable $x.";
+        let error: ClientError = QueryFailed::new(raw.to_owned()).into();
+
+        assert!(matches!(error, ClientError::QueryFailed(q) if q.raw() == raw));
+    }
+
+    #[test]
+    fn test_protocol_formats_as_debug() {
+        let error = ClientError::Protocol("invalid status byte 2".to_owned());
+        let _ = format!("{:?}", error);
+    }
+
+    #[test]
+    fn test_protocol_formats_as_empty() {
+        let error = ClientError::Protocol("invalid status byte 2".to_owned());
+        assert_eq!("protocol violation: invalid status byte 2", format!("{}", error));
+    }
+
+    #[test]
+    fn test_protocol_converts_through_io_error() {
+        let error: io::Error = ClientError::Protocol("invalid status byte 2".to_owned()).into();
+        let error: ClientError = error.into();
+
+        assert!(matches!(error, ClientError::Protocol(message) if message == "invalid status byte 2"));
+    }
+
+    #[test]
+    fn test_protocol_has_no_source() {
+        assert!(ClientError::Protocol("invalid status byte 2".to_owned())
+            .source()
+            .is_none());
+    }
+
+    #[test]
+    fn test_result_parse_formats_as_debug() {
+        let error = ClientError::ResultParse {
+            result: "lunar".to_owned(),
+            message: "invalid digit found in string".to_owned(),
+        };
+        let _ = format!("{:?}", error);
+    }
+
+    #[test]
+    fn test_result_parse_formats_as_empty() {
+        let error = ClientError::ResultParse {
+            result: "lunar".to_owned(),
+            message: "invalid digit found in string".to_owned(),
+        };
+        assert_eq!(
+            "cannot parse result \"lunar\": invalid digit found in string",
+            format!("{}", error)
+        );
+    }
+
+    #[test]
+    fn test_result_parse_has_no_source() {
+        let error = ClientError::ResultParse {
+            result: "lunar".to_owned(),
+            message: "invalid digit found in string".to_owned(),
+        };
+        assert!(error.source().is_none());
+    }
+
+    #[test]
+    fn test_invalid_name_formats_as_debug() {
+        let _ = format!("{:?}", ClientError::InvalidName("lunar base".to_owned()));
+    }
+
+    #[test]
+    fn test_invalid_name_formats_as_empty() {
+        let error = ClientError::InvalidName("lunar base".to_owned());
+        assert_eq!("invalid name \"lunar base\"", format!("{}", error));
+    }
+
+    #[test]
+    fn test_invalid_name_has_no_source() {
+        assert!(ClientError::InvalidName("lunar base".to_owned()).source().is_none());
+    }
+
+    #[test]
+    fn test_timeout_formats_as_debug() {
+        let _ = format!("{:?}", ClientError::Timeout(Duration::from_millis(100)));
+    }
+
+    #[test]
+    fn test_timeout_formats_as_empty() {
+        let error = ClientError::Timeout(Duration::from_millis(100));
+        assert_eq!("timed out after 100ms", format!("{}", error));
+    }
+
+    #[test]
+    fn test_timeout_has_no_source() {
+        assert!(ClientError::Timeout(Duration::from_millis(100)).source().is_none());
+    }
+
+    #[test]
+    fn test_read_only_formats_with_database_name() {
+        let error = ClientError::ReadOnly("lunar".to_owned());
+        assert_eq!("database \"lunar\" is opened for reading only", format!("{}", error));
+    }
+
+    #[test]
+    fn test_read_only_has_no_source() {
+        assert!(ClientError::ReadOnly("lunar".to_owned()).source().is_none());
+    }
+
+    #[cfg(feature = "serde_json")]
+    #[test]
+    fn test_json_parse_formats_with_source() {
+        let error = ClientError::JsonParse(serde_json::from_str::<serde_json::Value>("{").unwrap_err());
+
+        assert!(format!("{}", error).starts_with("cannot parse result as JSON: "));
+        assert!(error.source().is_some());
+    }
+
+    #[test]
+    fn test_io_error_has_source() {
+        let error = ClientError::Io(io::Error::other("test"));
+        assert_eq!("test", error.source().unwrap().to_string());
+    }
+
+    #[test]
+    fn test_utf8_parse_has_source() {
+        let error = ClientError::Utf8Parse(String::from_utf8(vec![0xa0_u8, 0xa1]).unwrap_err());
+        assert!(error.source().is_some());
+    }
+
+    #[test]
+    fn test_query_failed_has_source() {
+        let raw = "Stopped at ., 1/1: [XPST0008] Undeclared variable $x.";
+        let error = ClientError::QueryFailed(QueryFailed::new(raw.to_owned()));
+        assert_eq!(raw, error.source().unwrap().to_string());
+    }
+
+    #[test]
+    fn test_auth_has_no_source() {
+        assert!(ClientError::Auth(AuthFailure::ServerRejected).source().is_none());
+    }
+
+    #[test]
+    fn test_command_failed_has_no_source() {
+        let error = ClientError::CommandFailed {
+            message: "error".to_owned(),
+        };
+        assert!(error.source().is_none());
     }
 }
@@ -1,17 +1,29 @@
 mod client;
 mod connection;
 mod errors;
+mod pool;
 mod query;
 mod resource;
 mod stream;
 #[cfg(test)]
 mod tests;
+#[cfg(feature = "tls")]
+mod tls;
 
-pub use client::Client;
-pub use connection::Connection;
-pub use errors::ClientError;
-pub use query::{compiler, serializer, ArgumentWriter, Query, ToQueryArgument, WithInfo, WithoutInfo};
-pub use stream::DatabaseStream;
+pub use client::{
+    Client, ClientBuilder, Command, CommandOutcome, CreateOptions, CreateOutcome, DatabaseInfo, DatabaseStats,
+    DropTarget, OpenDbGuard, QueryObserver, ResourceEntry, ResourceKind, ServerInfo, ServerOption, Version,
+};
+pub use connection::{protocol, Connection};
+pub use errors::{AuthFailure, ClientError};
+pub use pool::{ClientPool, PoolOptions, PooledClient};
+pub use query::{
+    compiler, escape_xquery_name, escape_xquery_string, serializer, AnyUri, ArgumentWriter, Base64Binary, Binding,
+    EncodedValue, HexBinary, QName, Query, QueryFailed, ResultIter, ToQueryArgument, WithInfo, WithoutInfo, XdmType,
+};
+pub use stream::{DatabaseStream, TeeStream};
+#[cfg(feature = "tls")]
+pub use tls::TlsStream;
 
 /// A [`Result`] with its [`Err`] variant set to [`ClientError`].
 ///
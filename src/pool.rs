@@ -0,0 +1,462 @@
+use crate::{Client, DatabaseStream, Result};
+use std::fmt::{Debug, Formatter};
+use std::net::TcpStream;
+use std::ops::{Deref, DerefMut};
+use std::sync::{Condvar, Mutex, MutexGuard, PoisonError};
+
+/// Configures the size of a [`ClientPool`] and when its connections are created.
+///
+/// # Example
+///
+/// ```
+/// # use basex::PoolOptions;
+/// let options = PoolOptions::new(4).eager();
+/// assert_eq!(4, options.max_size());
+/// assert!(options.is_eager());
+/// ```
+///
+/// [`ClientPool`]: crate::ClientPool
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PoolOptions {
+    max_size: usize,
+    eager: bool,
+}
+
+impl PoolOptions {
+    /// Creates options for a pool holding at most `max_size` connections, created lazily as they are needed.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `max_size` is zero.
+    pub fn new(max_size: usize) -> Self {
+        assert!(max_size > 0, "Pool must hold at least one connection");
+
+        Self { max_size, eager: false }
+    }
+
+    /// Creates all the connections up front when the pool is created.
+    pub fn eager(self) -> Self {
+        Self { eager: true, ..self }
+    }
+
+    /// Creates the connections one by one, only when no idle connection is available. This is the default.
+    pub fn lazy(self) -> Self {
+        Self { eager: false, ..self }
+    }
+
+    /// Maximum number of connections the pool holds.
+    pub fn max_size(&self) -> usize {
+        self.max_size
+    }
+
+    /// Whether all the connections are created up front.
+    pub fn is_eager(&self) -> bool {
+        self.eager
+    }
+}
+
+impl Default for PoolOptions {
+    fn default() -> Self {
+        Self::new(10)
+    }
+}
+
+/// Connections of the pool along with the number of those created so far.
+struct Slots<T: DatabaseStream> {
+    idle: Vec<Client<T>>,
+    created: usize,
+}
+
+/// Bounded set of authenticated clients shared across threads.
+///
+/// Each call to [`get`] hands out a [`PooledClient`] guard dereferencing to a [`Client`]. Dropping the guard
+/// returns the client to the pool. When all the clients are taken, [`get`] blocks until one is returned.
+///
+/// A client whose connection broke, i.e. reading or writing failed part way or the server answered with something the
+/// protocol does not allow, is discarded instead of returned, and a new one is created in its place when needed.
+/// Otherwise, the session is handed out again as it was left: the opened database, session options and open queries
+/// carry over to whoever takes the client next, so they should be closed or reset before the guard is dropped.
+///
+/// # Example
+///
+/// ```
+/// # use basex::{ClientError, ClientPool, PoolOptions};
+/// # use std::sync::Arc;
+/// # use std::thread;
+/// # fn main() -> Result<(), ClientError> {
+/// let pool = Arc::new(ClientPool::connect("localhost", 1984, "admin", "admin", PoolOptions::new(4))?);
+///
+/// let workers: Vec<_> = (0..8)
+///     .map(|_| {
+///         let pool = Arc::clone(&pool);
+///         thread::spawn(move || pool.get()?.list())
+///     })
+///     .collect();
+///
+/// for worker in workers {
+///     worker.join().unwrap()?;
+/// }
+/// # Ok(())
+/// # }
+/// ```
+///
+/// [`get`]: crate::ClientPool::get
+/// [`PooledClient`]: crate::PooledClient
+/// [`Client`]: crate::Client
+pub struct ClientPool<T: DatabaseStream> {
+    factory: Box<dyn Fn() -> Result<Client<T>> + Send + Sync>,
+    options: PoolOptions,
+    slots: Mutex<Slots<T>>,
+    returned: Condvar,
+}
+
+impl ClientPool<TcpStream> {
+    /// Creates a pool of clients connected and authenticated to BaseX server using TCP stream.
+    ///
+    /// With [eager] options, fails if any of the connections cannot be established.
+    ///
+    /// [eager]: crate::PoolOptions::eager
+    pub fn connect(host: &str, port: u16, user: &str, password: &str, options: PoolOptions) -> Result<Self> {
+        let (host, user, password) = (host.to_owned(), user.to_owned(), password.to_owned());
+
+        Self::new(move || Client::connect(&host, port, &user, &password), options)
+    }
+}
+
+impl<T: DatabaseStream> ClientPool<T> {
+    /// Creates a pool of clients produced by the `factory`.
+    ///
+    /// Typically, you only need to use this method when using a custom stream. For regular usage, refer to the
+    /// [`ClientPool::connect`] method.
+    ///
+    /// [`ClientPool::connect`]: crate::ClientPool<TcpStream>::connect
+    pub fn new<F>(factory: F, options: PoolOptions) -> Result<Self>
+    where
+        F: Fn() -> Result<Client<T>> + Send + Sync + 'static,
+    {
+        let idle = if options.eager {
+            (0..options.max_size).map(|_| factory()).collect::<Result<Vec<_>>>()?
+        } else {
+            vec![]
+        };
+
+        Ok(Self {
+            factory: Box::new(factory),
+            options,
+            slots: Mutex::new(Slots {
+                created: idle.len(),
+                idle,
+            }),
+            returned: Condvar::new(),
+        })
+    }
+
+    /// Takes an idle client out of the pool, creating a new one if the pool is not full yet. Blocks until another
+    /// client is returned when the pool is exhausted.
+    pub fn get(&self) -> Result<PooledClient<'_, T>> {
+        let mut slots = self.slots();
+
+        loop {
+            if let Some(client) = self.take(&mut slots) {
+                drop(slots);
+                return self.create_or_reuse(client);
+            }
+            slots = self.returned.wait(slots).unwrap_or_else(PoisonError::into_inner);
+        }
+    }
+
+    /// Takes an idle client out of the pool, creating a new one if the pool is not full yet. Returns `None` when
+    /// the pool is exhausted.
+    pub fn try_get(&self) -> Result<Option<PooledClient<'_, T>>> {
+        let mut slots = self.slots();
+
+        match self.take(&mut slots) {
+            Some(client) => {
+                drop(slots);
+                self.create_or_reuse(client).map(Some)
+            }
+            None => Ok(None),
+        }
+    }
+
+    /// Options the pool was created with.
+    pub fn options(&self) -> PoolOptions {
+        self.options
+    }
+
+    /// Number of clients created so far, both idle and taken.
+    pub fn size(&self) -> usize {
+        self.slots().created
+    }
+
+    /// Number of clients waiting in the pool to be taken.
+    pub fn idle(&self) -> usize {
+        self.slots().idle.len()
+    }
+
+    /// Takes an idle client or reserves a slot for a new one, signalled by `Some(None)`.
+    fn take(&self, slots: &mut Slots<T>) -> Option<Option<Client<T>>> {
+        if let Some(client) = slots.idle.pop() {
+            Some(Some(client))
+        } else if slots.created < self.options.max_size {
+            slots.created += 1;
+            Some(None)
+        } else {
+            None
+        }
+    }
+
+    fn create_or_reuse(&self, client: Option<Client<T>>) -> Result<PooledClient<'_, T>> {
+        let client = match client {
+            Some(client) => client,
+            None => (self.factory)().inspect_err(|_| {
+                self.slots().created -= 1;
+                self.returned.notify_one();
+            })?,
+        };
+
+        Ok(PooledClient {
+            pool: self,
+            client: Some(client),
+        })
+    }
+
+    fn slots(&self) -> MutexGuard<'_, Slots<T>> {
+        self.slots.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+impl<T: DatabaseStream> Debug for ClientPool<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let slots = self.slots();
+
+        f.debug_struct("ClientPool")
+            .field("options", &self.options)
+            .field("created", &slots.created)
+            .field("idle", &slots.idle.len())
+            .finish()
+    }
+}
+
+/// Client taken out of a [`ClientPool`]. Dereferences to [`Client`] and returns it to the pool on drop, unless its
+/// connection broke, in which case it is discarded.
+///
+/// [`ClientPool`]: crate::ClientPool
+/// [`Client`]: crate::Client
+#[derive(Debug)]
+pub struct PooledClient<'a, T: DatabaseStream> {
+    pool: &'a ClientPool<T>,
+    client: Option<Client<T>>,
+}
+
+impl<T: DatabaseStream> Deref for PooledClient<'_, T> {
+    type Target = Client<T>;
+
+    fn deref(&self) -> &Self::Target {
+        self.client.as_ref().unwrap()
+    }
+}
+
+impl<T: DatabaseStream> DerefMut for PooledClient<'_, T> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        self.client.as_mut().unwrap()
+    }
+}
+
+impl<T: DatabaseStream> Drop for PooledClient<'_, T> {
+    fn drop(&mut self) {
+        if let Some(client) = self.client.take() {
+            match client.is_broken() {
+                true => {
+                    self.pool.slots().created -= 1;
+                    drop(client);
+                }
+                false => self.pool.slots().idle.push(client),
+            }
+            self.pool.returned.notify_one();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::{spawn_server, MockStream};
+    use crate::{ClientError, Connection};
+    use std::io;
+    use std::io::ErrorKind;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+    use std::thread;
+    use std::time::Duration;
+
+    fn counting_factory(created: Arc<AtomicUsize>) -> impl Fn() -> Result<Client<MockStream>> {
+        move || {
+            created.fetch_add(1, Ordering::SeqCst);
+            Ok(Client::new(Connection::from_str("\0Database 'lunar' was opened.\0")))
+        }
+    }
+
+    #[test]
+    fn test_lazy_pool_creates_clients_on_demand() {
+        let created = Arc::new(AtomicUsize::new(0));
+        let pool = ClientPool::new(counting_factory(Arc::clone(&created)), PoolOptions::new(2)).unwrap();
+
+        assert_eq!(0, pool.size());
+        let _client = pool.get().unwrap();
+
+        assert_eq!(1, created.load(Ordering::SeqCst));
+        assert_eq!(1, pool.size());
+        assert_eq!(0, pool.idle());
+    }
+
+    #[test]
+    fn test_eager_pool_creates_all_clients_up_front() {
+        let created = Arc::new(AtomicUsize::new(0));
+        let pool = ClientPool::new(counting_factory(Arc::clone(&created)), PoolOptions::new(3).eager()).unwrap();
+
+        assert_eq!(3, created.load(Ordering::SeqCst));
+        assert_eq!(3, pool.size());
+        assert_eq!(3, pool.idle());
+    }
+
+    #[test]
+    fn test_dropped_client_returns_to_pool() {
+        let created = Arc::new(AtomicUsize::new(0));
+        let pool = ClientPool::new(counting_factory(Arc::clone(&created)), PoolOptions::new(1)).unwrap();
+
+        drop(pool.get().unwrap());
+        assert_eq!(1, pool.idle());
+        let _client = pool.get().unwrap();
+
+        assert_eq!(1, created.load(Ordering::SeqCst));
+        assert_eq!(0, pool.idle());
+    }
+
+    #[test]
+    fn test_client_with_broken_connection_is_discarded() {
+        let created = Arc::new(AtomicUsize::new(0));
+        let factory = {
+            let created = Arc::clone(&created);
+            move || {
+                created.fetch_add(1, Ordering::SeqCst);
+                Ok(Client::new(Connection::from_str("\0Database 'lunar' was")))
+            }
+        };
+        let pool = ClientPool::new(factory, PoolOptions::new(1)).unwrap();
+
+        let mut client = pool.get().unwrap();
+        let actual_error = client.open("lunar").expect_err("Operation must fail");
+        drop(client);
+
+        assert!(matches!(actual_error, ClientError::Io(e) if e.kind() == ErrorKind::UnexpectedEof));
+        assert_eq!(0, pool.size());
+        assert_eq!(0, pool.idle());
+        let _client = pool.try_get().unwrap().unwrap();
+        assert_eq!(2, created.load(Ordering::SeqCst));
+    }
+
+    #[test]
+    fn test_client_with_failed_command_returns_to_pool() {
+        let pool = ClientPool::new(
+            || {
+                Ok(Client::new(Connection::from_str(
+                    "\0Database 'lunar' was not found.\0\u{1}",
+                )))
+            },
+            PoolOptions::new(1),
+        )
+        .unwrap();
+
+        let mut client = pool.get().unwrap();
+        let actual_error = client.open("lunar").expect_err("Operation must fail");
+        drop(client);
+
+        assert!(matches!(actual_error, ClientError::CommandFailed { .. }));
+        assert_eq!(1, pool.size());
+        assert_eq!(1, pool.idle());
+    }
+
+    #[test]
+    fn test_exhausted_pool_blocks_until_client_is_returned() {
+        let (address, server) = spawn_server();
+        let pool =
+            Arc::new(ClientPool::connect("127.0.0.1", address.port(), "admin", "admin", PoolOptions::new(1)).unwrap());
+        let client = pool.get().unwrap();
+
+        let waiting = {
+            let pool = Arc::clone(&pool);
+            thread::spawn(move || pool.get().map(|_| ()))
+        };
+        thread::sleep(Duration::from_millis(50));
+        assert!(!waiting.is_finished());
+        drop(client);
+        waiting.join().unwrap().unwrap();
+        let _ = server.join().unwrap();
+
+        assert_eq!(1, pool.size());
+        assert_eq!(1, pool.idle());
+    }
+
+    #[test]
+    fn test_exhausted_pool_gives_no_client() {
+        let created = Arc::new(AtomicUsize::new(0));
+        let pool = ClientPool::new(counting_factory(Arc::clone(&created)), PoolOptions::new(1)).unwrap();
+
+        let _client = pool.try_get().unwrap().unwrap();
+
+        assert!(pool.try_get().unwrap().is_none());
+    }
+
+    #[test]
+    fn test_pooled_client_dereferences_to_client() {
+        let created = Arc::new(AtomicUsize::new(0));
+        let pool = ClientPool::new(counting_factory(Arc::clone(&created)), PoolOptions::default()).unwrap();
+
+        let info = pool.get().unwrap().open("lunar").unwrap();
+
+        assert_eq!("Database 'lunar' was opened.", info);
+    }
+
+    #[test]
+    fn test_failed_creation_frees_the_slot() {
+        let pool: ClientPool<MockStream> = ClientPool::new(
+            || Err(io::Error::from(ErrorKind::ConnectionRefused).into()),
+            PoolOptions::new(1),
+        )
+        .unwrap();
+
+        let actual_error = pool.get().expect_err("Operation must fail");
+
+        assert!(matches!(actual_error, ClientError::Io(e) if e.kind() == ErrorKind::ConnectionRefused));
+        assert_eq!(0, pool.size());
+    }
+
+    #[test]
+    fn test_eager_pool_fails_when_client_fails_to_connect() {
+        let actual_error = ClientPool::<MockStream>::new(
+            || Err(io::Error::from(ErrorKind::ConnectionRefused).into()),
+            PoolOptions::new(1).eager(),
+        )
+        .expect_err("Operation must fail");
+
+        assert!(matches!(actual_error, ClientError::Io(_)));
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_options_reject_zero_size() {
+        let _ = PoolOptions::new(0);
+    }
+
+    #[test]
+    fn test_formats_as_debug() {
+        let created = Arc::new(AtomicUsize::new(0));
+        let pool = ClientPool::new(counting_factory(created), PoolOptions::new(1)).unwrap();
+        let client = pool.get().unwrap();
+
+        let _ = format!("{:?}", client);
+        drop(client);
+        let _ = format!("{:?}", pool);
+    }
+}
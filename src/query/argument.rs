@@ -1,7 +1,14 @@
 use crate::connection::Authenticated;
+use crate::query::escape::is_ncname;
 use crate::resource::AsResource;
-use crate::{Connection, DatabaseStream, Result};
+use crate::{ClientError, Connection, DatabaseStream, Result};
+use std::fmt::Display;
+use std::io::{Read, Write};
 use std::net::IpAddr;
+use std::time::Duration;
+
+/// Type the server binds an empty sequence with.
+const EMPTY_SEQUENCE_TYPE: &str = "empty-sequence()";
 
 /// Writes argument values using a [`Connection`].
 ///
@@ -47,7 +54,7 @@ pub trait ToQueryArgument<'a> {
 
 impl<'a> ToQueryArgument<'a> for bool {
     fn write_xquery<T: DatabaseStream>(&self, writer: &mut ArgumentWriter<T>) -> Result<()> {
-        writer.write(&mut self.to_string().as_str().as_bytes())
+        writer.write(&mut self.to_string().as_bytes())
     }
 
     fn xquery_type() -> String {
@@ -57,7 +64,7 @@ impl<'a> ToQueryArgument<'a> for bool {
 
 impl<'a> ToQueryArgument<'a> for u8 {
     fn write_xquery<T: DatabaseStream>(&self, writer: &mut ArgumentWriter<T>) -> Result<()> {
-        writer.write(&mut self.to_string().as_str().as_bytes())
+        writer.write(&mut self.to_string().as_bytes())
     }
 
     fn xquery_type() -> String {
@@ -67,7 +74,7 @@ impl<'a> ToQueryArgument<'a> for u8 {
 
 impl<'a> ToQueryArgument<'a> for i8 {
     fn write_xquery<T: DatabaseStream>(&self, writer: &mut ArgumentWriter<T>) -> Result<()> {
-        writer.write(&mut self.to_string().as_str().as_bytes())
+        writer.write(&mut self.to_string().as_bytes())
     }
 
     fn xquery_type() -> String {
@@ -77,7 +84,7 @@ impl<'a> ToQueryArgument<'a> for i8 {
 
 impl<'a> ToQueryArgument<'a> for u16 {
     fn write_xquery<T: DatabaseStream>(&self, writer: &mut ArgumentWriter<T>) -> Result<()> {
-        writer.write(&mut self.to_string().as_str().as_bytes())
+        writer.write(&mut self.to_string().as_bytes())
     }
 
     fn xquery_type() -> String {
@@ -87,7 +94,7 @@ impl<'a> ToQueryArgument<'a> for u16 {
 
 impl<'a> ToQueryArgument<'a> for i16 {
     fn write_xquery<T: DatabaseStream>(&self, writer: &mut ArgumentWriter<T>) -> Result<()> {
-        writer.write(&mut self.to_string().as_str().as_bytes())
+        writer.write(&mut self.to_string().as_bytes())
     }
 
     fn xquery_type() -> String {
@@ -97,7 +104,7 @@ impl<'a> ToQueryArgument<'a> for i16 {
 
 impl<'a> ToQueryArgument<'a> for u32 {
     fn write_xquery<T: DatabaseStream>(&self, writer: &mut ArgumentWriter<T>) -> Result<()> {
-        writer.write(&mut self.to_string().as_str().as_bytes())
+        writer.write(&mut self.to_string().as_bytes())
     }
 
     fn xquery_type() -> String {
@@ -107,7 +114,7 @@ impl<'a> ToQueryArgument<'a> for u32 {
 
 impl<'a> ToQueryArgument<'a> for i32 {
     fn write_xquery<T: DatabaseStream>(&self, writer: &mut ArgumentWriter<T>) -> Result<()> {
-        writer.write(&mut self.to_string().as_str().as_bytes())
+        writer.write(&mut self.to_string().as_bytes())
     }
 
     fn xquery_type() -> String {
@@ -117,7 +124,7 @@ impl<'a> ToQueryArgument<'a> for i32 {
 
 impl<'a> ToQueryArgument<'a> for u64 {
     fn write_xquery<T: DatabaseStream>(&self, writer: &mut ArgumentWriter<T>) -> Result<()> {
-        writer.write(&mut self.to_string().as_str().as_bytes())
+        writer.write(&mut self.to_string().as_bytes())
     }
 
     fn xquery_type() -> String {
@@ -127,7 +134,7 @@ impl<'a> ToQueryArgument<'a> for u64 {
 
 impl<'a> ToQueryArgument<'a> for i64 {
     fn write_xquery<T: DatabaseStream>(&self, writer: &mut ArgumentWriter<T>) -> Result<()> {
-        writer.write(&mut self.to_string().as_str().as_bytes())
+        writer.write(&mut self.to_string().as_bytes())
     }
 
     fn xquery_type() -> String {
@@ -135,9 +142,11 @@ impl<'a> ToQueryArgument<'a> for i64 {
     }
 }
 
+/// Writes the number in its shortest form, spelling infinities as `INF` and `-INF` and not-a-number as `NaN`, as XSD
+/// does.
 impl<'a> ToQueryArgument<'a> for f32 {
     fn write_xquery<T: DatabaseStream>(&self, writer: &mut ArgumentWriter<T>) -> Result<()> {
-        writer.write(&mut self.to_string().as_str().as_bytes())
+        writer.write(&mut float_lexical(*self).as_bytes())
     }
 
     fn xquery_type() -> String {
@@ -145,9 +154,11 @@ impl<'a> ToQueryArgument<'a> for f32 {
     }
 }
 
+/// Writes the number in its shortest form, spelling infinities as `INF` and `-INF` and not-a-number as `NaN`, as XSD
+/// does.
 impl<'a> ToQueryArgument<'a> for f64 {
     fn write_xquery<T: DatabaseStream>(&self, writer: &mut ArgumentWriter<T>) -> Result<()> {
-        writer.write(&mut self.to_string().as_str().as_bytes())
+        writer.write(&mut float_lexical(*self).as_bytes())
     }
 
     fn xquery_type() -> String {
@@ -175,7 +186,7 @@ impl<'a> ToQueryArgument<'a> for String {
     }
 }
 
-impl<'a, 'b, D: ToQueryArgument<'a>> ToQueryArgument<'a> for &'b D {
+impl<'a, D: ToQueryArgument<'a>> ToQueryArgument<'a> for &D {
     fn write_xquery<T: DatabaseStream>(&self, writer: &mut ArgumentWriter<T>) -> Result<()> {
         (*self).write_xquery(writer)
     }
@@ -205,11 +216,531 @@ impl<'a> ToQueryArgument<'a> for IpAddr {
     }
 }
 
+/// Writes the duration as `xs:dayTimeDuration` in its canonical form, like `PT1.5S`.
+impl<'a> ToQueryArgument<'a> for Duration {
+    fn write_xquery<T: DatabaseStream>(&self, writer: &mut ArgumentWriter<T>) -> Result<()> {
+        writer.write(&mut day_time_duration(false, self.as_secs(), self.subsec_nanos()).as_bytes())
+    }
+
+    fn xquery_type() -> String {
+        "xs:dayTimeDuration".to_owned()
+    }
+}
+
+/// Writes the decimal with its full precision.
+#[cfg(feature = "decimal")]
+impl<'a> ToQueryArgument<'a> for rust_decimal::Decimal {
+    fn write_xquery<T: DatabaseStream>(&self, writer: &mut ArgumentWriter<T>) -> Result<()> {
+        writer.write(&mut self.to_string().as_bytes())
+    }
+
+    fn xquery_type() -> String {
+        "xs:decimal".to_owned()
+    }
+}
+
+/// Binary data bound as `xs:base64Binary`.
+///
+/// # Example
+/// ```
+/// use basex::{Base64Binary, ToQueryArgument};
+/// let blob = Base64Binary(&[0, 1, 2, 3]);
+/// assert_eq!("xs:base64Binary", Base64Binary::xquery_type());
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Base64Binary<'a>(pub &'a [u8]);
+
+impl<'a> ToQueryArgument<'a> for Base64Binary<'a> {
+    fn write_xquery<T: DatabaseStream>(&self, writer: &mut ArgumentWriter<T>) -> Result<()> {
+        writer.write(&mut BinaryEncoder::new(self.0, Encoding::Base64))
+    }
+
+    fn xquery_type() -> String {
+        "xs:base64Binary".to_owned()
+    }
+}
+
+/// Binary data bound as `xs:hexBinary`.
+///
+/// # Example
+/// ```
+/// use basex::{HexBinary, ToQueryArgument};
+/// let blob = HexBinary(&[0xCA, 0xFE]);
+/// assert_eq!("xs:hexBinary", HexBinary::xquery_type());
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct HexBinary<'a>(pub &'a [u8]);
+
+impl<'a> ToQueryArgument<'a> for HexBinary<'a> {
+    fn write_xquery<T: DatabaseStream>(&self, writer: &mut ArgumentWriter<T>) -> Result<()> {
+        writer.write(&mut BinaryEncoder::new(self.0, Encoding::Hex))
+    }
+
+    fn xquery_type() -> String {
+        "xs:hexBinary".to_owned()
+    }
+}
+
+/// Qualified name bound as `xs:QName`, e.g. `xs:string` or `wojak`.
+///
+/// The name is checked to be either a name without a prefix or a prefix and a local name separated by a colon. The
+/// prefix is resolved by the server using the namespaces declared in the query.
+///
+/// # Example
+/// ```
+/// # use basex::{ClientError, QName, ToQueryArgument};
+/// # fn main() -> Result<(), ClientError> {
+/// let name = QName::new("xs:string")?;
+/// assert_eq!("xs:string", name.as_str());
+/// assert_eq!("xs:QName", QName::xquery_type());
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct QName(String);
+
+impl QName {
+    /// Creates the qualified name, failing with [`ClientError::InvalidName`] when the `name` is not one, so that it is
+    /// never sent.
+    ///
+    /// [`ClientError::InvalidName`]: crate::ClientError::InvalidName
+    pub fn new(name: &str) -> Result<Self> {
+        let valid = match name.split_once(':') {
+            Some((prefix, local)) => is_ncname(prefix) && is_ncname(local),
+            None => is_ncname(name),
+        };
+
+        match valid {
+            true => Ok(Self(name.to_owned())),
+            false => Err(ClientError::InvalidName(name.to_owned())),
+        }
+    }
+
+    /// The name as it is bound.
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl<'a> ToQueryArgument<'a> for QName {
+    fn write_xquery<T: DatabaseStream>(&self, writer: &mut ArgumentWriter<T>) -> Result<()> {
+        writer.write(self.0.as_str())
+    }
+
+    fn xquery_type() -> String {
+        "xs:QName".to_owned()
+    }
+}
+
+/// URI bound as `xs:anyURI`, e.g. `http://www.w3.org/2005/xpath-functions`.
+///
+/// The URI is only checked not to be blank, as the server accepts nearly any string as `xs:anyURI`.
+///
+/// # Example
+/// ```
+/// # use basex::{AnyUri, ClientError, ToQueryArgument};
+/// # fn main() -> Result<(), ClientError> {
+/// let uri = AnyUri::new("http://expath.org/ns/crypto")?;
+/// assert_eq!("http://expath.org/ns/crypto", uri.as_str());
+/// assert_eq!("xs:anyURI", AnyUri::xquery_type());
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct AnyUri(String);
+
+impl AnyUri {
+    /// Creates the URI, failing with [`ClientError::InvalidName`] when the `uri` is blank, so that it is never sent.
+    ///
+    /// [`ClientError::InvalidName`]: crate::ClientError::InvalidName
+    pub fn new(uri: &str) -> Result<Self> {
+        match uri.trim().is_empty() {
+            true => Err(ClientError::InvalidName(uri.to_owned())),
+            false => Ok(Self(uri.to_owned())),
+        }
+    }
+
+    /// The URI as it is bound.
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl<'a> ToQueryArgument<'a> for AnyUri {
+    fn write_xquery<T: DatabaseStream>(&self, writer: &mut ArgumentWriter<T>) -> Result<()> {
+        writer.write(self.0.as_str())
+    }
+
+    fn xquery_type() -> String {
+        "xs:anyURI".to_owned()
+    }
+}
+
+/// Text encoding of binary data.
+#[derive(Debug, Clone, Copy)]
+enum Encoding {
+    Base64,
+    Hex,
+}
+
+impl Encoding {
+    /// Number of input bytes encoded together.
+    fn group_size(self) -> usize {
+        match self {
+            Encoding::Base64 => 3,
+            Encoding::Hex => 1,
+        }
+    }
+
+    /// Encodes a group of at most `group_size` bytes, returning the number of characters written to `output`.
+    fn encode(self, group: &[u8], output: &mut [u8; 4]) -> usize {
+        const BASE64: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+        const HEX: &[u8; 16] = b"0123456789ABCDEF";
+
+        match self {
+            Encoding::Base64 => {
+                let bits = group
+                    .iter()
+                    .enumerate()
+                    .fold(0u32, |bits, (i, byte)| bits | (*byte as u32) << (16 - 8 * i));
+                for (i, character) in output.iter_mut().enumerate() {
+                    *character = if i <= group.len() {
+                        BASE64[(bits >> (18 - 6 * i) & 0x3F) as usize]
+                    } else {
+                        b'='
+                    };
+                }
+                4
+            }
+            Encoding::Hex => {
+                output[0] = HEX[(group[0] >> 4) as usize];
+                output[1] = HEX[(group[0] & 0x0F) as usize];
+                2
+            }
+        }
+    }
+}
+
+/// Reads the text encoding of the binary `input`, encoding it group by group as it is read.
+struct BinaryEncoder<'a> {
+    input: &'a [u8],
+    encoding: Encoding,
+    output: [u8; 4],
+    output_length: usize,
+    output_position: usize,
+}
+
+impl<'a> BinaryEncoder<'a> {
+    fn new(input: &'a [u8], encoding: Encoding) -> Self {
+        Self {
+            input,
+            encoding,
+            output: [0; 4],
+            output_length: 0,
+            output_position: 0,
+        }
+    }
+}
+
+impl Read for BinaryEncoder<'_> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        let mut size = 0;
+
+        while size < buf.len() {
+            if self.output_position == self.output_length {
+                if self.input.is_empty() {
+                    break;
+                }
+                let (group, rest) = self.input.split_at(self.encoding.group_size().min(self.input.len()));
+                self.output_length = self.encoding.encode(group, &mut self.output);
+                self.output_position = 0;
+                self.input = rest;
+            }
+            buf[size] = self.output[self.output_position];
+            self.output_position += 1;
+            size += 1;
+        }
+
+        Ok(size)
+    }
+}
+
+#[cfg(feature = "chrono")]
+impl<'a> ToQueryArgument<'a> for chrono::NaiveDate {
+    fn write_xquery<T: DatabaseStream>(&self, writer: &mut ArgumentWriter<T>) -> Result<()> {
+        writer.write(&mut self.format("%Y-%m-%d").to_string().as_bytes())
+    }
+
+    fn xquery_type() -> String {
+        "xs:date".to_owned()
+    }
+}
+
+#[cfg(feature = "chrono")]
+impl<'a> ToQueryArgument<'a> for chrono::NaiveTime {
+    fn write_xquery<T: DatabaseStream>(&self, writer: &mut ArgumentWriter<T>) -> Result<()> {
+        use chrono::Timelike;
+        let time = format!("{}{}", self.format("%H:%M:%S"), seconds_fraction(self.nanosecond()));
+        writer.write(&mut time.as_bytes())
+    }
+
+    fn xquery_type() -> String {
+        "xs:time".to_owned()
+    }
+}
+
+#[cfg(feature = "chrono")]
+impl<'a> ToQueryArgument<'a> for chrono::NaiveDateTime {
+    fn write_xquery<T: DatabaseStream>(&self, writer: &mut ArgumentWriter<T>) -> Result<()> {
+        writer.write(&mut naive_date_time(self).as_bytes())
+    }
+
+    fn xquery_type() -> String {
+        "xs:dateTime".to_owned()
+    }
+}
+
+#[cfg(feature = "chrono")]
+impl<'a, Tz: chrono::TimeZone> ToQueryArgument<'a> for chrono::DateTime<Tz> {
+    fn write_xquery<T: DatabaseStream>(&self, writer: &mut ArgumentWriter<T>) -> Result<()> {
+        writer.write(&mut format!("{}Z", naive_date_time(&self.naive_utc())).as_bytes())
+    }
+
+    fn xquery_type() -> String {
+        "xs:dateTime".to_owned()
+    }
+}
+
+#[cfg(feature = "chrono")]
+impl<'a> ToQueryArgument<'a> for chrono::Duration {
+    fn write_xquery<T: DatabaseStream>(&self, writer: &mut ArgumentWriter<T>) -> Result<()> {
+        let negative = *self < chrono::Duration::zero();
+        let seconds = self.num_seconds().unsigned_abs();
+        let nanoseconds = self.subsec_nanos().unsigned_abs();
+        writer.write(&mut day_time_duration(negative, seconds, nanoseconds).as_bytes())
+    }
+
+    fn xquery_type() -> String {
+        "xs:dayTimeDuration".to_owned()
+    }
+}
+
+/// Formats the date and time in the `xs:dateTime` lexical form without timezone, like `2021-03-07T08:05:09.5`.
+#[cfg(feature = "chrono")]
+fn naive_date_time(date_time: &chrono::NaiveDateTime) -> String {
+    use chrono::Timelike;
+    format!(
+        "{}{}",
+        date_time.format("%Y-%m-%dT%H:%M:%S"),
+        seconds_fraction(date_time.nanosecond())
+    )
+}
+
+/// Formats the fraction of a second with the trailing zeros removed, like `.5`, or nothing for a whole second.
+fn seconds_fraction(nanoseconds: u32) -> String {
+    match nanoseconds % 1_000_000_000 {
+        0 => String::new(),
+        nanoseconds => format!(".{}", format!("{:09}", nanoseconds).trim_end_matches('0')),
+    }
+}
+
+/// Formats the floating point number in the XSD lexical form, which differs from Rust in spelling the special values.
+fn float_lexical<F: Into<f64> + Display + Copy>(value: F) -> String {
+    let wide: f64 = value.into();
+
+    if wide.is_nan() {
+        "NaN".to_owned()
+    } else if wide == f64::INFINITY {
+        "INF".to_owned()
+    } else if wide == f64::NEG_INFINITY {
+        "-INF".to_owned()
+    } else {
+        value.to_string()
+    }
+}
+
+/// Formats the duration in the canonical `xs:dayTimeDuration` lexical form, like `-P1DT2H3M4.5S`.
+fn day_time_duration(negative: bool, seconds: u64, nanoseconds: u32) -> String {
+    let (days, hours, minutes, seconds) = (
+        seconds / 86_400,
+        seconds % 86_400 / 3_600,
+        seconds % 3_600 / 60,
+        seconds % 60,
+    );
+    let mut duration = if negative { "-P".to_owned() } else { "P".to_owned() };
+
+    if days > 0 {
+        duration.push_str(&format!("{}D", days));
+    }
+    if hours == 0 && minutes == 0 && seconds == 0 && nanoseconds == 0 {
+        if days == 0 {
+            duration.push_str("T0S");
+        }
+        return duration;
+    }
+    duration.push('T');
+    if hours > 0 {
+        duration.push_str(&format!("{}H", hours));
+    }
+    if minutes > 0 {
+        duration.push_str(&format!("{}M", minutes));
+    }
+    if seconds > 0 || nanoseconds > 0 {
+        duration.push_str(&format!("{}{}S", seconds, seconds_fraction(nanoseconds)));
+    }
+
+    duration
+}
+
+/// In-memory stream collecting the value of a single sequence item.
+#[derive(Debug, Clone, Default)]
+struct ItemBuffer(Vec<u8>);
+
+impl Read for ItemBuffer {
+    fn read(&mut self, _: &mut [u8]) -> std::io::Result<usize> {
+        Ok(0)
+    }
+}
+
+impl Write for ItemBuffer {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.0.extend_from_slice(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+impl DatabaseStream for ItemBuffer {
+    fn try_clone(&self) -> Result<Self> {
+        Ok(self.clone())
+    }
+}
+
+/// Writes the `items` as a single sequence argument followed by its type.
+///
+/// The server expects each item as its value and type separated by `\1`, with the items separated by `\2`. An empty
+/// sequence is sent as an empty value of the `empty-sequence()` type.
+pub(crate) fn write_sequence<'a, T, A, I>(connection: &mut Connection<T, Authenticated>, items: I) -> Result<()>
+where
+    T: DatabaseStream,
+    A: ToQueryArgument<'a>,
+    I: IntoIterator<Item = A>,
+{
+    let mut sequence = vec![];
+
+    for (index, item) in items.into_iter().enumerate() {
+        if index > 0 {
+            sequence.push(2);
+        }
+        sequence.extend(encode(&item)?);
+        sequence.push(1);
+        sequence.extend_from_slice(A::xquery_type().as_bytes());
+    }
+
+    let xquery_type = if sequence.is_empty() { EMPTY_SEQUENCE_TYPE } else { "" };
+    connection.send_arg(&mut sequence.as_slice())?;
+    connection.send_arg(&mut xquery_type.as_bytes())?;
+    Ok(())
+}
+
+/// Value of an argument bound by [`Query::bind_all`], either encoded from any [`ToQueryArgument`] or missing.
+///
+/// # Example
+///
+/// ```
+/// # use basex::{Binding, Client, ClientError};
+/// # fn main() -> Result<(), ClientError> {
+/// let client = Client::connect("localhost", 1984, "admin", "admin")?;
+/// let xquery = "declare variable $a external; declare variable $b external; ($a, $b)";
+/// let mut query = client.query(xquery)?.without_info()?;
+/// query.bind_all(vec![("a", Binding::value(123)), ("b", Binding::Empty)])?;
+/// # Ok(())
+/// # }
+/// ```
+///
+/// [`Query::bind_all`]: crate::Query::bind_all
+#[derive(Debug)]
+pub enum Binding {
+    /// The encoded value with its XQuery type, see [`Binding::value`].
+    Value(EncodedValue),
+    /// No value, the same as binding the argument `without_value`.
+    Empty,
+}
+
+impl Binding {
+    /// Encodes the `value` along with its XQuery type. Failure to encode it surfaces once the binding is about to be
+    /// sent, before any of it is.
+    pub fn value<'a, A: ToQueryArgument<'a>>(value: A) -> Self {
+        Self::Value(EncodedValue(encode(&value).map(|bytes| (bytes, A::xquery_type()))))
+    }
+
+    /// Fails with the error the value failed to encode with, if any, so that it is known before anything is sent.
+    pub(crate) fn checked(self) -> Result<Self> {
+        match self {
+            Binding::Value(EncodedValue(encoded)) => Ok(Binding::Value(EncodedValue(Ok(encoded?)))),
+            Binding::Empty => Ok(Binding::Empty),
+        }
+    }
+
+    /// Sends the binding as the value and type arguments.
+    pub(crate) fn write<T: DatabaseStream>(self, connection: &mut Connection<T, Authenticated>) -> Result<()> {
+        match self {
+            Binding::Value(EncodedValue(encoded)) => {
+                let (bytes, xquery_type) = encoded?;
+                connection.send_arg(&mut bytes.as_slice())?;
+                connection.send_arg(&mut xquery_type.as_bytes())?;
+            }
+            Binding::Empty => {
+                connection.skip_arg()?;
+                connection.skip_arg()?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl<'a, A: ToQueryArgument<'a>> From<A> for Binding {
+    fn from(value: A) -> Self {
+        Self::value(value)
+    }
+}
+
+/// Value of a [`Binding`] encoded ahead of being sent.
+#[derive(Debug)]
+pub struct EncodedValue(Result<(Vec<u8>, String)>);
+
+/// Writes the `value` into memory the same way it would be sent as an argument.
+fn encode<'a, A: ToQueryArgument<'a>>(value: &A) -> Result<Vec<u8>> {
+    let mut buffer = Connection::authenticated(ItemBuffer::default());
+    value.write_xquery(&mut ArgumentWriter(&mut buffer))?;
+    buffer.flush_pending()?;
+    let mut bytes = vec![];
+    unescape_into(&buffer.stream().0, &mut bytes);
+    Ok(bytes)
+}
+
+/// Reverts the escaping done when sending an argument, dropping the argument terminators.
+fn unescape_into(escaped: &[u8], output: &mut Vec<u8>) {
+    let mut bytes = escaped.iter();
+
+    while let Some(&byte) = bytes.next() {
+        match byte {
+            0xFF => output.extend(bytes.next()),
+            0 => {}
+            _ => output.push(byte),
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use test_case::test_case;
 
+    #[allow(clippy::unused_unit)]
     #[test_case(IpAddr::V4("125.0.0.1".parse().unwrap()), "125.0.0.1\0", "xs:string")]
     #[test_case("test", "test\0", "xs:string")]
     #[test_case("test".to_owned(), "test\0", "xs:string")]
@@ -225,6 +756,17 @@ mod tests {
     #[test_case(5.5f32, "5.5\0", "xs:float")]
     #[test_case(5.5f64, "5.5\0", "xs:double")]
     #[test_case(&5.2f64, "5.2\0", "xs:double")]
+    #[test_case(f32::INFINITY, "INF\0", "xs:float")]
+    #[test_case(f32::NEG_INFINITY, "-INF\0", "xs:float")]
+    #[test_case(f32::NAN, "NaN\0", "xs:float")]
+    #[test_case(f64::INFINITY, "INF\0", "xs:double")]
+    #[test_case(f64::NEG_INFINITY, "-INF\0", "xs:double")]
+    #[test_case(f64::NAN, "NaN\0", "xs:double")]
+    #[test_case(-0.0f64, "-0\0", "xs:double")]
+    #[test_case(Duration::from_secs(0), "PT0S\0", "xs:dayTimeDuration")]
+    #[test_case(Duration::from_millis(1_500), "PT1.5S\0", "xs:dayTimeDuration")]
+    #[test_case(Duration::from_secs(90_061), "P1DT1H1M1S\0", "xs:dayTimeDuration")]
+    #[test_case(Duration::from_nanos(1), "PT0.000000001S\0", "xs:dayTimeDuration")]
     #[test_case(Some(true), "true\0", "xs:boolean")]
     fn test_writing_values_as_query_argument<'a, T: ToQueryArgument<'a>>(
         value: T,
@@ -239,4 +781,140 @@ mod tests {
         assert_eq!(expected_stream, actual_stream);
         assert_eq!(expected_type, T::xquery_type());
     }
+
+    #[allow(clippy::unused_unit)]
+    #[test_case(Base64Binary(&[]), "\0", "xs:base64Binary")]
+    #[test_case(Base64Binary(b"M"), "TQ==\0", "xs:base64Binary")]
+    #[test_case(Base64Binary(b"Ma"), "TWE=\0", "xs:base64Binary")]
+    #[test_case(Base64Binary(b"Man"), "TWFu\0", "xs:base64Binary")]
+    #[test_case(Base64Binary(&[0, 0xFF, 0xFE, 0x10]), "AP/+EA==\0", "xs:base64Binary")]
+    #[test_case(HexBinary(&[]), "\0", "xs:hexBinary")]
+    #[test_case(HexBinary(&[0, 0xCA, 0xFE, 0x0F]), "00CAFE0F\0", "xs:hexBinary")]
+    fn test_writing_binary_values_as_query_argument<'a, T: ToQueryArgument<'a>>(
+        value: T,
+        expected_stream: &str,
+        expected_type: &str,
+    ) {
+        let mut connection = Connection::from_str("");
+        let mut writer = ArgumentWriter(&mut connection);
+        value.write_xquery(&mut writer).unwrap();
+        let actual_stream = connection.into_inner().to_string();
+
+        assert_eq!(expected_stream, actual_stream);
+        assert_eq!(expected_type, T::xquery_type());
+    }
+
+    #[allow(clippy::unused_unit)]
+    #[test_case(QName::new("wojak").unwrap(), "wojak\0", "xs:QName"; "local name")]
+    #[test_case(QName::new("xs:string").unwrap(), "xs:string\0", "xs:QName"; "prefixed name")]
+    #[test_case(AnyUri::new("http://expath.org/ns/crypto").unwrap(), "http://expath.org/ns/crypto\0", "xs:anyURI"; "uri")]
+    #[test_case(AnyUri::new("../lunar.xml").unwrap(), "../lunar.xml\0", "xs:anyURI"; "relative uri")]
+    fn test_writing_name_values_as_query_argument<'a, T: ToQueryArgument<'a>>(
+        value: T,
+        expected_stream: &str,
+        expected_type: &str,
+    ) {
+        let mut connection = Connection::from_str("");
+        let mut writer = ArgumentWriter(&mut connection);
+        value.write_xquery(&mut writer).unwrap();
+        let actual_stream = connection.into_inner().to_string();
+
+        assert_eq!(expected_stream, actual_stream);
+        assert_eq!(expected_type, T::xquery_type());
+    }
+
+    #[allow(clippy::unused_unit)]
+    #[test_case(""; "empty")]
+    #[test_case(":string"; "missing prefix")]
+    #[test_case("xs:"; "missing local name")]
+    #[test_case("a:b:c"; "two colons")]
+    #[test_case("1st"; "leading digit")]
+    #[test_case("wo jak"; "space")]
+    #[test_case("Q{http://www.w3.org/2001/XMLSchema}string"; "uri qualified name")]
+    fn test_rejects_qname(name: &str) {
+        let actual_error = QName::new(name).expect_err("Creating must fail");
+
+        assert!(matches!(actual_error, ClientError::InvalidName(n) if n == name));
+    }
+
+    #[allow(clippy::unused_unit)]
+    #[test_case(""; "empty")]
+    #[test_case(" \t"; "whitespace")]
+    fn test_rejects_any_uri(uri: &str) {
+        let actual_error = AnyUri::new(uri).expect_err("Creating must fail");
+
+        assert!(matches!(actual_error, ClientError::InvalidName(_)));
+    }
+
+    #[test]
+    fn test_binary_encoder_reads_into_small_buffer() {
+        let mut encoder = BinaryEncoder::new(b"Many hands", Encoding::Base64);
+        let mut encoded = vec![];
+
+        let mut buf = [0; 3];
+        loop {
+            match encoder.read(&mut buf).unwrap() {
+                0 => break,
+                size => encoded.extend_from_slice(&buf[..size]),
+            }
+        }
+
+        assert_eq!(b"TWFueSBoYW5kcw==".to_vec(), encoded);
+    }
+
+    #[cfg(feature = "decimal")]
+    #[allow(clippy::unused_unit)]
+    #[test_case("0", "0\0")]
+    #[test_case("-12.50", "-12.50\0")]
+    #[test_case("79228162514264337593543950335", "79228162514264337593543950335\0")]
+    #[test_case("0.0000000000000000000000000001", "0.0000000000000000000000000001\0")]
+    fn test_writing_decimal_as_query_argument(value: &str, expected_stream: &str) {
+        let value: rust_decimal::Decimal = value.parse().unwrap();
+        let mut connection = Connection::from_str("");
+        let mut writer = ArgumentWriter(&mut connection);
+        value.write_xquery(&mut writer).unwrap();
+        let actual_stream = connection.into_inner().to_string();
+
+        assert_eq!(expected_stream, actual_stream);
+        assert_eq!("xs:decimal", rust_decimal::Decimal::xquery_type());
+    }
+
+    #[cfg(feature = "chrono")]
+    #[allow(clippy::unused_unit)]
+    #[test_case(chrono::NaiveDate::from_ymd_opt(2021, 3, 7).unwrap(), "2021-03-07\0", "xs:date")]
+    #[test_case(chrono::NaiveTime::from_hms_opt(8, 5, 9).unwrap(), "08:05:09\0", "xs:time")]
+    #[test_case(chrono::NaiveTime::from_hms_milli_opt(8, 5, 9, 250).unwrap(), "08:05:09.25\0", "xs:time")]
+    #[test_case(
+        chrono::NaiveDate::from_ymd_opt(2021, 3, 7).unwrap().and_hms_opt(8, 5, 9).unwrap(),
+        "2021-03-07T08:05:09\0",
+        "xs:dateTime"
+    )]
+    #[test_case(
+        chrono::DateTime::<chrono::Utc>::from_timestamp(1615104309, 0).unwrap(),
+        "2021-03-07T08:05:09Z\0",
+        "xs:dateTime"
+    )]
+    #[test_case(
+        chrono::DateTime::parse_from_rfc3339("2021-03-07T08:05:09.5+02:00").unwrap(),
+        "2021-03-07T06:05:09.5Z\0",
+        "xs:dateTime"
+    )]
+    #[test_case(chrono::Duration::zero(), "PT0S\0", "xs:dayTimeDuration")]
+    #[test_case(chrono::Duration::days(2), "P2D\0", "xs:dayTimeDuration")]
+    #[test_case(chrono::Duration::seconds(93_784), "P1DT2H3M4S\0", "xs:dayTimeDuration")]
+    #[test_case(chrono::Duration::milliseconds(-4_500), "-PT4.5S\0", "xs:dayTimeDuration")]
+    #[test_case(chrono::Duration::minutes(90), "PT1H30M\0", "xs:dayTimeDuration")]
+    fn test_writing_chrono_values_as_query_argument<'a, T: ToQueryArgument<'a>>(
+        value: T,
+        expected_stream: &str,
+        expected_type: &str,
+    ) {
+        let mut connection = Connection::from_str("");
+        let mut writer = ArgumentWriter(&mut connection);
+        value.write_xquery(&mut writer).unwrap();
+        let actual_stream = connection.into_inner().to_string();
+
+        assert_eq!(expected_stream, actual_stream);
+        assert_eq!(expected_type, T::xquery_type());
+    }
 }
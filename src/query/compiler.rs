@@ -16,11 +16,16 @@ use std::time::Duration;
 /// println!("Hit(s): {:?}", info.hits());
 /// println!("Updated: {:?}", info.updated());
 /// println!("Printed: {:?}", info.printed());
+/// println!("Printed Bytes: {:?}", info.printed_bytes());
 /// println!("Read Locking: {:?}", info.read_locking());
 /// println!("Write Locking: {:?}", info.write_locking());
 /// println!("Optimized Query: {:?}", info.optimized_query());
 /// println!("Query: {:?}", info.query());
 /// println!("Compiling: {:?}", info.compiling());
+/// println!("Compiling Steps: {:?}", info.compiling_steps());
+/// println!("Number of Results: {:?}", info.result_count());
+/// println!("Runtime: {:?}", info.field("Runtime"));
+/// println!("Raw: {}", info.raw());
 /// # }
 /// ```
 ///
@@ -47,9 +52,22 @@ pub trait Info: Debug + Display + Clone + PartialEq {
     /// Nodes updated.
     fn updated(&self) -> usize;
 
-    /// Bytes printed for the query analysis.
+    /// Unit the [`hits`] are counted in.
+    ///
+    /// [`hits`]: Info::hits
+    fn hits_unit(&self) -> Option<CountUnit>;
+
+    /// Unit the [`updated`] are counted in.
+    ///
+    /// [`updated`]: Info::updated
+    fn updated_unit(&self) -> Option<CountUnit>;
+
+    /// Bytes printed for the query analysis, as the number the server reported regardless of its unit.
     fn printed(&self) -> usize;
 
+    /// Bytes printed for the query analysis, converted from the reported unit, e.g. `2 kb` is `2048`.
+    fn printed_bytes(&self) -> u64;
+
     /// Specifies the database that's going to be locked for reading by running this query, if there is any.
     fn read_locking(&self) -> Option<String>;
 
@@ -64,6 +82,86 @@ pub trait Info: Debug + Display + Clone + PartialEq {
 
     /// Compilation steps to parse XQuery and produce an optimized version.
     fn compiling(&self) -> Vec<String>;
+
+    /// The [`compiling`] steps, with the rewrites split into the rule and the expressions before and after.
+    ///
+    /// [`compiling`]: Info::compiling
+    fn compiling_steps(&self) -> Vec<CompileStep>;
+
+    /// Number of results, if the server reports it in the `Number of Results` line.
+    fn result_count(&self) -> Option<usize>;
+
+    /// Value of any line starting with the `name` followed by a colon, e.g. `Runtime`, for the fields not modeled by
+    /// the other methods. Sections with the value on the next line, like `Query`, yield their first line.
+    fn field(&self, name: &str) -> Option<String>;
+
+    /// The unparsed info as sent by the server.
+    fn raw(&self) -> &str;
+}
+
+/// What the [`Info`] counts are made of.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CountUnit {
+    /// Items of the result sequence.
+    Items,
+    /// Database nodes.
+    Nodes,
+}
+
+/// Single step of the [`Info::compiling`], as returned by [`Info::compiling_steps`].
+///
+/// # Example
+/// ```
+/// # use basex::compiler::CompileStep;
+/// let step = CompileStep::parse("rewrite fn:count(items) to xs:integer item: count((1, 2)) -> 2");
+/// assert_eq!(
+///     CompileStep::Rewrite {
+///         rule: "rewrite fn:count(items) to xs:integer item".to_owned(),
+///         before: "count((1, 2))".to_owned(),
+///         after: "2".to_owned(),
+///     },
+///     step
+/// );
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CompileStep {
+    /// The expression `before` was rewritten to `after` by the `rule`.
+    Rewrite {
+        rule: String,
+        before: String,
+        after: String,
+    },
+    /// Any other step, kept as the server described it.
+    Other(String),
+}
+
+impl CompileStep {
+    /// Parses the `step` formatted like `rule: before -> after` into [`CompileStep::Rewrite`], falling back to
+    /// [`CompileStep::Other`] with the whole `step` when it is formatted otherwise.
+    pub fn parse(step: &str) -> Self {
+        let rewrite = step
+            .split_once(": ")
+            .and_then(|(rule, change)| Some((rule, change.split_once(" -> ")?)));
+
+        match rewrite {
+            Some((rule, (before, after))) => CompileStep::Rewrite {
+                rule: rule.to_owned(),
+                before: before.to_owned(),
+                after: after.to_owned(),
+            },
+            None => CompileStep::Other(step.to_owned()),
+        }
+    }
+}
+
+impl Display for CompileStep {
+    /// Formats the step the way the server described it.
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CompileStep::Rewrite { rule, before, after } => write!(f, "{}: {} -> {}", rule, before, after),
+            CompileStep::Other(step) => f.write_str(step),
+        }
+    }
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -82,45 +180,93 @@ impl RawInfo {
         Self { raw }
     }
 
+    /// Parses the duration like [`parse_duration`], falling back to zero on unknown format.
+    ///
+    /// [`parse_duration`]: RawInfo::parse_duration
     fn duration_from_str(duration: &str) -> Duration {
-        let v: Vec<&str> = duration.splitn(2, ' ').collect();
-        let (time, unit) = (v[0], v[1]);
+        RawInfo::parse_duration(duration).unwrap_or_default()
+    }
+
+    /// Parses durations formatted by the server in seconds down to nanoseconds, such as `12.3 ms` or `900 ns`. Returns
+    /// `None` on unknown format.
+    pub(crate) fn parse_duration(duration: &str) -> Option<Duration> {
+        let (time, unit) = duration.split_once(' ')?;
         let unit: String = unit.chars().take_while(|c| c.is_alphabetic()).collect();
-        let time = f64::from_str(time).unwrap();
+        let time = f64::from_str(time).ok()?;
 
         match unit.as_str() {
-            "s" => Duration::from_secs_f64(time),
-            "ms" => Duration::from_nanos((time * 1000000.0) as u64),
-            other => panic!("Unexpected unit: {}", other),
+            "s" => Some(Duration::from_secs_f64(time)),
+            "ms" => Some(Duration::from_nanos((time * 1000000.0) as u64)),
+            "µs" | "us" => Some(Duration::from_nanos((time * 1000.0) as u64)),
+            "ns" => Some(Duration::from_nanos(time as u64)),
+            _ => None,
         }
     }
 
+    /// Parses sizes formatted by the server, such as `2 kb`, into bytes. Returns `None` on unknown format.
+    fn parse_size(size: &str) -> Option<u64> {
+        let (amount, unit) = size.split_once(' ')?;
+        let amount = f64::from_str(amount).ok()?;
+        let multiplier: u64 = match unit.trim().to_lowercase().as_str() {
+            "b" => 1,
+            "kb" => 1 << 10,
+            "mb" => 1 << 20,
+            "gb" => 1 << 30,
+            _ => return None,
+        };
+
+        Some((amount * multiplier as f64) as u64)
+    }
+
+    /// Finds the `header` at the start of a line and returns the rest of the info after it, or `None` when absent.
+    fn section(&self, header: &str) -> Option<&str> {
+        self.raw
+            .match_indices(header)
+            .find(|(start, _)| *start == 0 || self.raw.as_bytes()[start - 1] == b'\n')
+            .map(|(start, _)| &self.raw[start + header.len()..])
+    }
+
+    fn line_from(&self, header: &str) -> Option<&str> {
+        self.section(header).and_then(|rest| rest.lines().next())
+    }
+
     fn string_from(&self, header: &str) -> String {
-        let start = self.raw.find(header).unwrap() + header.len();
-        let stop = self.raw[start..].find('\n').unwrap();
-        self.raw[start..start + stop].to_owned()
+        self.line_from(header).unwrap_or_default().to_owned()
     }
 
     fn option_string_from(&self, header: &str) -> Option<String> {
-        let str = self.string_from(header);
-        match str.as_str() {
+        match self.line_from(header)? {
             "(none)" => None,
-            _ => Some(str),
+            str => Some(str.to_owned()),
         }
     }
 
     fn duration_from(&self, header: &str) -> Duration {
-        RawInfo::duration_from_str(&self.string_from(header))
+        match self.line_from(header) {
+            Some(duration) => RawInfo::duration_from_str(duration),
+            None => Duration::ZERO,
+        }
+    }
+
+    fn unit_from(&self, header: &str) -> Option<CountUnit> {
+        let (_, unit) = self.line_from(header)?.split_once(' ')?;
+
+        match unit.trim() {
+            "Item" | "Items" => Some(CountUnit::Items),
+            "Node" | "Nodes" => Some(CountUnit::Nodes),
+            _ => None,
+        }
     }
 
     fn usize_from(&self, header: &str) -> usize {
         let s: String = self
-            .string_from(header)
+            .line_from(header)
+            .unwrap_or_default()
             .chars()
             .take_while(|c| c.is_ascii_digit())
             .collect();
 
-        usize::from_str(&s).unwrap()
+        usize::from_str(&s).unwrap_or_default()
     }
 }
 
@@ -153,10 +299,24 @@ impl Info for RawInfo {
         self.usize_from("Updated: ")
     }
 
+    fn hits_unit(&self) -> Option<CountUnit> {
+        self.unit_from("Hit(s): ")
+    }
+
+    fn updated_unit(&self) -> Option<CountUnit> {
+        self.unit_from("Updated: ")
+    }
+
     fn printed(&self) -> usize {
         self.usize_from("Printed: ")
     }
 
+    fn printed_bytes(&self) -> u64 {
+        self.line_from("Printed: ")
+            .and_then(RawInfo::parse_size)
+            .unwrap_or_default()
+    }
+
     fn read_locking(&self) -> Option<String> {
         self.option_string_from("Read Locking: ")
     }
@@ -174,14 +334,36 @@ impl Info for RawInfo {
     }
 
     fn compiling(&self) -> Vec<String> {
-        let header = "Compiling:\n- ";
-        let start = self.raw.find(header).unwrap() + header.len();
-        let stop = self.raw[start..].find("\n\n").unwrap();
-        self.raw[start..start + stop]
-            .to_owned()
-            .split("\n- ")
-            .map(|v| v.to_owned())
-            .collect()
+        let steps = match self.section("Compiling:\n- ") {
+            Some(steps) => steps,
+            None => return vec![],
+        };
+        let stop = steps.find("\n\n").unwrap_or(steps.trim_end().len());
+        steps[..stop].split("\n- ").map(|v| v.to_owned()).collect()
+    }
+
+    fn result_count(&self) -> Option<usize> {
+        let count: String = self
+            .line_from("Number of Results: ")?
+            .chars()
+            .take_while(|c| c.is_ascii_digit())
+            .collect();
+
+        usize::from_str(&count).ok()
+    }
+
+    fn field(&self, name: &str) -> Option<String> {
+        self.line_from(&format!("{}: ", name))
+            .or_else(|| self.line_from(&format!("{}:\n", name)))
+            .map(|value| value.to_owned())
+    }
+
+    fn compiling_steps(&self) -> Vec<CompileStep> {
+        self.compiling().iter().map(|step| CompileStep::parse(step)).collect()
+    }
+
+    fn raw(&self) -> &str {
+        &self.raw
     }
 }
 
@@ -224,7 +406,7 @@ Query executed in 398.5 ms.
             assert_eq!("3", info.optimized_query());
             assert_eq!(Duration::from_micros(381410), info.parsing_time());
             assert_eq!(Duration::from_micros(12220), info.compiling_time());
-            assert_eq!(Duration::from_micros(0090), info.evaluating_time());
+            assert_eq!(Duration::from_micros(90), info.evaluating_time());
             assert_eq!(Duration::from_micros(4790), info.printing_time());
             assert_eq!(Duration::from_micros(398500), info.total_time());
             assert_eq!(
@@ -240,14 +422,15 @@ Query executed in 398.5 ms.
             );
             assert_eq!(1, info.hits());
             assert_eq!(0, info.updated());
+            assert_eq!(Some($crate::compiler::CountUnit::Items), info.hits_unit());
+            assert_eq!(Some($crate::compiler::CountUnit::Items), info.updated_unit());
             assert_eq!(1, info.printed());
+            assert_eq!(1, info.printed_bytes());
             assert_eq!(Some("d601a46"), info.read_locking().as_ref().map(|v| v.as_str()));
             assert_eq!(None, info.write_locking());
         };
     }
 
-    pub use assert_query_info;
-
     #[test]
     fn test_parses_with_correct_values() {
         let raw = QUERY_INFO;
@@ -255,14 +438,152 @@ Query executed in 398.5 ms.
         assert_query_info!(info);
     }
 
+    #[test]
+    fn test_parses_defaults_for_missing_sections() {
+        let info = RawInfo::new(
+            "
+Query:
+1
+
+Optimized Query:
+1
+
+Parsing: 0.05 ms
+Total Time: 0.12 ms
+
+Query executed in 0.12 ms.
+"
+            .to_owned(),
+        );
+
+        assert_eq!("1", info.query());
+        assert_eq!("1", info.optimized_query());
+        assert_eq!(Vec::<String>::new(), info.compiling());
+        assert_eq!(Duration::from_micros(50), info.parsing_time());
+        assert_eq!(Duration::ZERO, info.compiling_time());
+        assert_eq!(0, info.hits());
+        assert_eq!(0, info.printed());
+        assert_eq!(None, info.read_locking());
+        assert_eq!(None, info.write_locking());
+    }
+
+    #[test]
+    fn test_parses_defaults_for_empty_info() {
+        let info = RawInfo::new(String::new());
+
+        assert_eq!("", info.query());
+        assert_eq!("", info.optimized_query());
+        assert_eq!(Vec::<String>::new(), info.compiling());
+        assert_eq!(Duration::ZERO, info.total_time());
+        assert_eq!(0, info.updated());
+        assert_eq!(None, info.read_locking());
+    }
+
+    #[test]
+    fn test_parses_compiling_steps_at_end_of_info() {
+        let info = RawInfo::new("Compiling:\n- pre-evaluate 1 + 1 to xs:integer\n".to_owned());
+
+        assert_eq!(vec!["pre-evaluate 1 + 1 to xs:integer"], info.compiling());
+    }
+
+    #[test]
+    fn test_parses_compiling_steps_into_rewrites() {
+        let info = RawInfo::new(QUERY_INFO.to_owned());
+
+        let steps = info.compiling_steps();
+
+        assert_eq!(
+            vec![
+                CompileStep::Rewrite {
+                    rule: "rewrite context value to document-node() item".to_owned(),
+                    before: ".".to_owned(),
+                    after: "db:open-pre(\"d601a46\", 0)".to_owned(),
+                },
+                CompileStep::Rewrite {
+                    rule: "rewrite util:root(nodes) to document-node() item".to_owned(),
+                    before: "util:root(db:open-pre(\"d601a46\", 0))".to_owned(),
+                    after: "db:open-pre(\"d601a46\", 0)".to_owned(),
+                },
+                CompileStep::Rewrite {
+                    rule: "rewrite fn:count(items) to xs:integer item".to_owned(),
+                    before: "count(db:open-pre(\"d601a46\", 0)/None/*)".to_owned(),
+                    after: "3".to_owned(),
+                },
+            ],
+            steps
+        );
+        let formatted: Vec<String> = steps.iter().map(|step| step.to_string()).collect();
+        assert_eq!(info.compiling(), formatted);
+    }
+
+    #[test]
+    fn test_keeps_unparseable_compiling_steps_as_text() {
+        let info = RawInfo::new("Compiling:\n- pre-evaluate 1 + 1 to xs:integer\n- simplify: 2\n".to_owned());
+
+        assert_eq!(
+            vec![
+                CompileStep::Other("pre-evaluate 1 + 1 to xs:integer".to_owned()),
+                CompileStep::Other("simplify: 2".to_owned()),
+            ],
+            info.compiling_steps()
+        );
+    }
+
+    #[test]
+    fn test_parses_printed_kilobytes() {
+        let info = RawInfo::new("Hit(s): 3 Nodes\nPrinted: 2 kb\n".to_owned());
+
+        assert_eq!(2, info.printed());
+        assert_eq!(2048, info.printed_bytes());
+        assert_eq!(Some(CountUnit::Nodes), info.hits_unit());
+        assert_eq!(None, info.updated_unit());
+    }
+
+    #[test]
+    fn test_reads_unmodeled_fields() {
+        let info = RawInfo::new(format!("{}Number of Results: 3\nRuntime: 1.2 ms\n", QUERY_INFO));
+
+        assert_eq!(Some("d601a46".to_owned()), info.field("Read Locking"));
+        assert_eq!(Some("(none)".to_owned()), info.field("Write Locking"));
+        assert_eq!(Some("1.2 ms".to_owned()), info.field("Runtime"));
+        assert_eq!(Some("count(/None/*)".to_owned()), info.field("Query"));
+        assert_eq!(Some(3), info.result_count());
+    }
+
+    #[test]
+    fn test_reads_missing_fields_as_none() {
+        let info = RawInfo::new(QUERY_INFO.to_owned());
+
+        assert_eq!(None, info.field("Runtime"));
+        assert_eq!(None, info.field("Locking"));
+        assert_eq!(None, info.result_count());
+    }
+
+    #[test]
+    fn test_parses_size_in_megabytes() {
+        assert_eq!(Some(1572864), RawInfo::parse_size("1.5 MB"));
+    }
+
+    #[test]
+    fn test_parsing_size_fails_on_invalid_unit() {
+        assert_eq!(None, RawInfo::parse_size("2 items"));
+    }
+
+    #[test]
+    fn test_keeps_raw_info() {
+        let info = RawInfo::new(QUERY_INFO.to_owned());
+
+        assert_eq!(QUERY_INFO, info.raw());
+    }
+
     #[test]
     fn test_formats_as_debug() {
-        format!("{:?}", RawInfo::new(QUERY_INFO.to_owned()));
+        let _ = format!("{:?}", RawInfo::new(QUERY_INFO.to_owned()));
     }
 
     #[test]
     fn test_formats_as_display() {
-        format!("{}", RawInfo::new(QUERY_INFO.to_owned()));
+        let _ = format!("{}", RawInfo::new(QUERY_INFO.to_owned()));
     }
 
     #[test]
@@ -276,8 +597,33 @@ Query executed in 398.5 ms.
     }
 
     #[test]
-    #[should_panic]
-    fn test_duration_from_str_panics_on_invalid_unit() {
-        RawInfo::duration_from_str("69 mss.");
+    fn test_parses_duration_in_milliseconds() {
+        assert_eq!(Some(Duration::from_micros(12300)), RawInfo::parse_duration("12.3 ms."));
+    }
+
+    #[test]
+    fn test_parsing_duration_fails_on_invalid_number() {
+        assert_eq!(None, RawInfo::parse_duration("abc ms"));
+    }
+
+    #[test]
+    fn test_parses_duration_in_microseconds() {
+        assert_eq!(Some(Duration::from_nanos(12500)), RawInfo::parse_duration("12.5 µs"));
+        assert_eq!(Some(Duration::from_nanos(12500)), RawInfo::parse_duration("12.5 us"));
+    }
+
+    #[test]
+    fn test_parses_duration_in_nanoseconds() {
+        assert_eq!(Some(Duration::from_nanos(900)), RawInfo::parse_duration("900 ns"));
+    }
+
+    #[test]
+    fn test_parsing_duration_fails_on_invalid_unit() {
+        assert_eq!(None, RawInfo::parse_duration("69 mss."));
+    }
+
+    #[test]
+    fn test_duration_from_str_is_zero_on_invalid_unit() {
+        assert_eq!(Duration::ZERO, RawInfo::duration_from_str("69 mss."));
     }
 }
@@ -1,3 +1,5 @@
+use std::error::Error;
+use std::fmt::{Display, Formatter};
 use std::str::FromStr;
 
 /// The error type for failed query execution.
@@ -8,35 +10,51 @@ pub struct QueryFailed {
     line: usize,
     position: usize,
     message: String,
-    file: String,
+    file: Option<String>,
 }
 
 impl QueryFailed {
+    /// Parses the error in the `Stopped at <file>, <line>/<position>: [<code>] <message>` format. Falls back to the
+    /// whole `raw` string as the message, with empty code and zero line and position, when it is in any other format.
     pub(crate) fn new(raw: String) -> Self {
-        let code_index = raw.find('[').unwrap();
-        let code_stop = code_index + raw[code_index..].find(']').unwrap();
-        let code = raw[code_index + 1..code_stop].to_owned();
-
-        let line_separator = raw[..code_index].rfind('/').unwrap();
-        let line_start = raw[..line_separator].rfind(',').unwrap();
-        let line_stop = line_separator + raw[line_separator..].find(':').unwrap();
-        let line = &raw[line_start + 2..line_separator];
-        let line = usize::from_str(line).unwrap();
-
-        let position = &raw[line_separator + 1..line_stop];
-        let position = usize::from_str(position).unwrap();
+        Self::parse(&raw).unwrap_or_else(|| Self {
+            message: raw.clone(),
+            raw,
+            code: String::new(),
+            line: 0,
+            position: 0,
+            file: None,
+        })
+    }
 
-        let message = raw[code_stop + 2..].to_owned();
-        let file = raw[11..line_start].to_owned();
+    fn parse(raw: &str) -> Option<Self> {
+        let code_index = raw.find('[')?;
+        let code_stop = code_index + raw[code_index..].find(']')?;
+        let code = raw[code_index + 1..code_stop].to_owned();
 
-        Self {
-            raw,
+        let location = raw[..code_index]
+            .trim_end()
+            .strip_prefix("Stopped at ")?
+            .strip_suffix(':')?;
+        let (file, line_position) = location.rsplit_once(", ")?;
+        let (line, position) = line_position.split_once('/')?;
+        let line = usize::from_str(line).ok()?;
+        let position = usize::from_str(position).ok()?;
+
+        let message = raw[code_stop + 1..].trim_start().to_owned();
+        let file = match file {
+            "." => None,
+            file => Some(file.to_owned()),
+        };
+
+        Some(Self {
+            raw: raw.to_owned(),
             code,
             line,
             position,
             message,
             file,
-        }
+        })
     }
 
     /// The unparsed error string.
@@ -64,12 +82,20 @@ impl QueryFailed {
         &self.message
     }
 
-    /// The XQuery source file. Is presented as `.` (dot character) when not from file.
-    pub fn file(&self) -> &str {
-        &self.file
+    /// The XQuery source file, or `None` when the query was not read from a file.
+    pub fn file(&self) -> Option<&str> {
+        self.file.as_deref()
+    }
+}
+
+impl Display for QueryFailed {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.raw)
     }
 }
 
+impl Error for QueryFailed {}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -82,7 +108,7 @@ mod tests {
                  zero-length variable name",
                 "XPST0003",
                 "Expecting ']', found '&'. Error in parse(text = x) : attempt to use zero-length variable name",
-                ".",
+                None,
                 1,
                 2264,
             ),
@@ -90,7 +116,7 @@ mod tests {
                 "Stopped at C:/Program Files (x86)/BaseX/etc/file2, 9/6: [XPST0003] Expecting '}', found '{'.",
                 "XPST0003",
                 "Expecting '}', found '{'.",
-                "C:/Program Files (x86)/BaseX/etc/file2",
+                Some("C:/Program Files (x86)/BaseX/etc/file2"),
                 9,
                 6,
             ),
@@ -100,7 +126,7 @@ mod tests {
                 "bxerr:BASX0000",
                 "java.lang.StringIndexOutOfBoundsException: String index out of range: -1 Error in parse(text = x) : \
                 attempt to use zero-length variable name",
-                ".",
+                None,
                 1,
                 87,
             ),
@@ -118,4 +144,36 @@ mod tests {
             assert_eq!(expected_position, error.position());
         }
     }
+
+    #[test]
+    fn test_undeclared_variable_error_exposes_its_fields() {
+        let error = QueryFailed::new("Stopped at ., 1/8:\n[XPST0008] Undeclared variable: $x.".to_owned());
+
+        assert_eq!("XPST0008", error.code());
+        assert_eq!(1, error.line());
+        assert_eq!(8, error.position());
+        assert_eq!("Undeclared variable: $x.", error.message());
+        assert_eq!(None, error.file());
+    }
+
+    #[test]
+    fn test_parsing_falls_back_to_raw_message_without_markers() {
+        let dataset = [
+            "Connection reset by peer",
+            "Stopped at the gate: [XPST0008] Undeclared variable: $x.",
+            "Stopped at ., x/y: [XPST0008] Undeclared variable: $x.",
+            "[XPST0008 Undeclared variable",
+        ];
+
+        for expected_raw in dataset {
+            let error = QueryFailed::new(expected_raw.to_owned());
+
+            assert_eq!(expected_raw, error.raw());
+            assert_eq!(expected_raw, error.message());
+            assert_eq!("", error.code());
+            assert_eq!(0, error.line());
+            assert_eq!(0, error.position());
+            assert_eq!(None, error.file());
+        }
+    }
 }
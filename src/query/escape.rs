@@ -0,0 +1,120 @@
+/// Quotes the `value` as an XQuery string literal, so that it can be put into query code built at runtime.
+///
+/// Quotes are doubled and ampersands are written as entity references, since both would otherwise end the literal or
+/// be read as the start of a reference. Control characters are written as character references, so that line breaks
+/// are not normalized by the server. The ones XML does not allow, e.g. `\u{1}`, make the server reject the query.
+///
+/// Binding the value as an external variable using [`Query::bind`] is preferred, as the value is then never parsed
+/// as code. This helps where the query code itself has to be composed dynamically.
+///
+/// # Example
+///
+/// ```
+/// # use basex::escape_xquery_string;
+/// assert_eq!(r#""Tom &amp; ""Jerry""""#, escape_xquery_string(r#"Tom & "Jerry""#));
+/// ```
+///
+/// [`Query::bind`]: crate::Query::bind
+pub fn escape_xquery_string(value: &str) -> String {
+    let mut literal = String::with_capacity(value.len() + 2);
+    literal.push('"');
+
+    for c in value.chars() {
+        match c {
+            '"' => literal.push_str("\"\""),
+            '&' => literal.push_str("&amp;"),
+            c if c.is_control() => literal.push_str(&format!("&#x{:X};", c as u32)),
+            c => literal.push(c),
+        }
+    }
+
+    literal.push('"');
+    literal
+}
+
+/// Turns the `name` into a valid element or attribute name without a prefix, so that it can be put into query code
+/// built at runtime, e.g. in a direct element constructor.
+///
+/// Characters not allowed in names are replaced by underscores, and an underscore is prepended when the name does not
+/// start with a letter or an underscore. Different names may thus end up the same.
+///
+/// # Example
+///
+/// ```
+/// # use basex::escape_xquery_name;
+/// assert_eq!("_1st_place", escape_xquery_name("1st place"));
+/// assert_eq!("a_b", escape_xquery_name("a>b"));
+/// ```
+pub fn escape_xquery_name(name: &str) -> String {
+    let mut escaped: String = name
+        .chars()
+        .map(|c| match is_name_char(c) {
+            true => c,
+            false => '_',
+        })
+        .collect();
+
+    if !escaped.starts_with(is_name_start_char) {
+        escaped.insert(0, '_');
+    }
+
+    escaped
+}
+
+/// Tells whether the `name` is a valid [name](https://www.w3.org/TR/REC-xml-names/#NT-NCName) without a prefix.
+pub(crate) fn is_ncname(name: &str) -> bool {
+    name.starts_with(is_name_start_char) && name.chars().all(is_name_char)
+}
+
+/// Tells whether `c` may start a [name](https://www.w3.org/TR/REC-xml-names/#NT-NCName) without a prefix.
+fn is_name_start_char(c: char) -> bool {
+    matches!(c,
+        'A'..='Z' | '_' | 'a'..='z' | '\u{C0}'..='\u{D6}' | '\u{D8}'..='\u{F6}' | '\u{F8}'..='\u{2FF}'
+        | '\u{370}'..='\u{37D}' | '\u{37F}'..='\u{1FFF}' | '\u{200C}'..='\u{200D}' | '\u{2070}'..='\u{218F}'
+        | '\u{2C00}'..='\u{2FEF}' | '\u{3001}'..='\u{D7FF}' | '\u{F900}'..='\u{FDCF}' | '\u{FDF0}'..='\u{FFFD}'
+        | '\u{10000}'..='\u{EFFFF}'
+    )
+}
+
+/// Tells whether `c` may appear in a [name](https://www.w3.org/TR/REC-xml-names/#NT-NCName) without a prefix.
+fn is_name_char(c: char) -> bool {
+    is_name_start_char(c)
+        || matches!(c, '-' | '.' | '0'..='9' | '\u{B7}' | '\u{300}'..='\u{36F}' | '\u{203F}'..='\u{2040}')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_case::test_case;
+
+    #[allow(clippy::unused_unit)]
+    #[test_case("lunar", "\"lunar\""; "plain")]
+    #[test_case("", "\"\""; "empty")]
+    #[test_case("say \"hi\"", "\"say \"\"hi\"\"\""; "quotes")]
+    #[test_case("it's", "\"it's\""; "apostrophe")]
+    #[test_case("Tom & Jerry", "\"Tom &amp; Jerry\""; "ampersand")]
+    #[test_case("&amp;", "\"&amp;amp;\""; "entity reference")]
+    #[test_case("{$x}", "\"{$x}\""; "braces")]
+    #[test_case("a\tb\r\nc", "\"a&#x9;b&#xD;&#xA;c\""; "line breaks")]
+    #[test_case("\u{1}\u{7F}", "\"&#x1;&#x7F;\""; "control characters")]
+    #[test_case("\") || db:drop(\"x", "\"\"\") || db:drop(\"\"x\""; "injection")]
+    #[test_case("žluťoučký", "\"žluťoučký\""; "unicode string")]
+    fn test_escapes_string(value: &str, expected: &str) {
+        assert_eq!(expected, escape_xquery_string(value));
+    }
+
+    #[allow(clippy::unused_unit)]
+    #[test_case("lunar", "lunar"; "valid name")]
+    #[test_case("boy_sminem-69.v2", "boy_sminem-69.v2"; "name with punctuation")]
+    #[test_case("", "_"; "empty name")]
+    #[test_case("1st", "_1st"; "leading digit")]
+    #[test_case("-dash", "_-dash"; "leading dash")]
+    #[test_case("ns:name", "ns_name"; "prefix")]
+    #[test_case("a b", "a_b"; "space")]
+    #[test_case("<script>", "_script_"; "angle brackets")]
+    #[test_case("a\"&'b", "a___b"; "quotes and ampersand")]
+    #[test_case("žluťoučký", "žluťoučký"; "unicode name")]
+    fn test_escapes_name(name: &str, expected: &str) {
+        assert_eq!(expected, escape_xquery_name(name));
+    }
+}
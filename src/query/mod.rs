@@ -3,14 +3,25 @@ pub mod serializer;
 
 mod argument;
 mod errors;
+mod escape;
 #[allow(clippy::module_inception)]
 mod query;
 mod response;
+mod results;
 
+pub use self::argument::AnyUri;
 pub use self::argument::ArgumentWriter;
+pub use self::argument::Base64Binary;
+pub use self::argument::Binding;
+pub use self::argument::EncodedValue;
+pub use self::argument::HexBinary;
+pub use self::argument::QName;
 pub use self::argument::ToQueryArgument;
 pub use self::errors::QueryFailed;
+pub use self::escape::{escape_xquery_name, escape_xquery_string};
+pub(crate) use self::query::close_query;
 pub use self::query::Query;
 pub use self::query::WithInfo;
 pub use self::query::WithoutInfo;
-pub use self::response::Response;
+pub use self::results::ResultIter;
+pub use self::results::XdmType;
@@ -1,13 +1,24 @@
 use crate::connection::Authenticated;
-use crate::query::argument::{ArgumentWriter, ToQueryArgument};
+use crate::query::argument::{write_sequence, ArgumentWriter, Binding, ToQueryArgument};
 use crate::query::compiler::{Info, RawInfo};
 use crate::query::response::Response;
+use crate::query::results::ResultIter;
 use crate::query::serializer::Options;
+#[cfg(feature = "serde_json")]
+use crate::query::serializer::SerializationMethod;
 use crate::resource::AsResource;
-use crate::{Client, Connection, DatabaseStream, Result};
+use crate::{Client, ClientError, Connection, DatabaseStream, Result, ServerOption};
 use std::borrow::{Borrow, BorrowMut};
+use std::collections::BTreeMap;
+use std::fmt::Display;
+use std::io::{copy, ErrorKind, Read, Write};
 use std::marker::PhantomData;
+use std::net::{Shutdown, TcpStream};
 use std::str::FromStr;
+use std::time::{Duration, Instant};
+
+/// The client is only taken out of the query when it is being closed or dropped.
+const CLOSED: &str = "query is not closed";
 
 /// Query that has its compiler [`info`] collected.
 ///
@@ -25,6 +36,7 @@ pub struct WithoutInfo;
 enum Command {
     Close = 2,
     Bind = 3,
+    Results = 4,
     Execute = 5,
     Info = 6,
     Options = 7,
@@ -32,6 +44,14 @@ enum Command {
     Updating = 0x1e,
 }
 
+/// Deletes the query with the given `id` on the server.
+pub(crate) fn close_query<T: DatabaseStream>(connection: &mut Connection<T, Authenticated>, id: &str) -> Result<()> {
+    connection.send_cmd(Command::Close as u8)?;
+    connection.send_arg(&mut id.as_bytes())?;
+    connection.get_response()?;
+    Ok(())
+}
+
 /// Encapsulates a query argument with optional value. To bind the argument, either call [`with_input`] or
 /// [`without_input`].
 ///
@@ -62,6 +82,49 @@ where
         Ok(self.query)
     }
 
+    /// Sends the `items` as a sequence to the argument, returning back the mutable reference to [`Query`].
+    ///
+    /// Every item is bound with the XQuery type of `A`. An empty iterator binds the empty sequence, as opposed to
+    /// [`without_value`] which leaves the argument unbound.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use basex::{Client, ClientError};
+    /// # use std::io::Read;
+    /// # fn main() -> Result<(), ClientError> {
+    /// let client = Client::connect("localhost", 1984, "admin", "admin")?;
+    /// let mut query = client.query("declare variable $numbers external; sum($numbers)")?.without_info()?;
+    /// query.bind("numbers")?.with_sequence(vec![1, 2, 3])?;
+    /// let mut result = String::new();
+    /// query.execute()?.read_to_string(&mut result)?;
+    /// assert_eq!("6", result);
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// [`Query`]: self::Query
+    /// [`without_value`]: self::ArgumentWithOptionalValue::without_value
+    pub fn with_sequence<'b, A, I>(self, items: I) -> Result<&'a mut Query<T, HasInfo>>
+    where
+        A: ToQueryArgument<'b>,
+        I: IntoIterator<Item = A>,
+    {
+        write_sequence(self.query.connection(), items)?;
+        self.query.connection().get_response()?;
+        Ok(self.query)
+    }
+
+    /// Streams the `input` as the string value of the argument, returning back the mutable reference to [`Query`].
+    ///
+    /// [`Query`]: self::Query
+    pub(crate) fn with_resource<'b, R: AsResource<'b>>(self, input: R) -> Result<&'a mut Query<T, HasInfo>> {
+        self.query.connection().send_arg(&mut input.into_read())?;
+        self.query.connection().send_arg(&mut "xs:string".as_bytes())?;
+        self.query.connection().get_response()?;
+        Ok(self.query)
+    }
+
     /// Omits the value from the argument, returning back the mutable reference to [`Query`].
     ///
     /// [`Query`]: self::Query
@@ -80,7 +143,11 @@ where
 ///
 /// Furthermore, the client can [`execute`] the query, check for [`updating`] statements or read compiler [`info`].
 ///
+/// The query is deleted on the server when dropped, ignoring any errors. Use [`close`] to get the client back and to
+/// find out whether deleting the query failed.
+///
 /// [`bind`]: self::Query::bind
+/// [`close`]: self::Query::close
 /// [`context`]: self::Query::context
 /// [`execute`]: self::Query::execute
 /// [`info`]: self::Query::info
@@ -93,7 +160,7 @@ where
 {
     has_info: PhantomData<HasInfo>,
     id: String,
-    client: Client<T>,
+    client: Option<Client<T>>,
 }
 
 impl<T, HasInfo> Query<T, HasInfo>
@@ -114,11 +181,10 @@ where
     /// # }
     /// ```
     pub fn close(mut self) -> Result<Client<T>> {
-        let connection: &mut Connection<T, Authenticated> = self.client.borrow_mut();
-        connection.send_cmd(Command::Close as u8)?;
-        connection.send_arg(&mut self.id.as_bytes())?;
-        connection.get_response()?;
-        Ok(self.client)
+        let mut client = self.client.take().expect(CLOSED);
+        client.forget_query(&self.id);
+        close_query(client.borrow_mut(), &self.id)?;
+        Ok(client)
     }
 
     /// Binds a variable under the given valid XML `name`.
@@ -147,14 +213,75 @@ where
     ///
     /// [`with_value`]: self::ArgumentWithOptionalValue::with_value
     /// [`without_value`]: self::ArgumentWithOptionalValue::without_value
-    pub fn bind(&mut self, name: &str) -> Result<ArgumentWithOptionalValue<T, HasInfo>> {
-        let connection: &mut Connection<T, Authenticated> = self.client.borrow_mut();
+    pub fn bind(&mut self, name: &str) -> Result<ArgumentWithOptionalValue<'_, T, HasInfo>> {
+        let connection: &mut Connection<T, Authenticated> = self.client.as_mut().expect(CLOSED).borrow_mut();
         connection.send_cmd(Command::Bind as u8)?;
         connection.send_arg(&mut self.id.as_bytes())?;
         connection.send_arg(&mut name.as_bytes())?;
         Ok(ArgumentWithOptionalValue::new(self))
     }
 
+    /// Binds each of the `bindings` under its name, in order, as if [`bind`] was chained for every one of them.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use basex::{Binding, Client, ClientError};
+    /// # fn main() -> Result<(), ClientError> {
+    /// let client = Client::connect("localhost", 1984, "admin", "admin")?;
+    /// let mut query = client.query("/")?.without_info()?;
+    /// query.bind_all(vec![
+    ///     ("boy_sminem", Binding::value(123)),
+    ///     ("bogdanoff", "pump".into()),
+    ///     ("lunar", Binding::Empty),
+    /// ])?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// [`bind`]: self::Query::bind
+    pub fn bind_all<'a, I>(&mut self, bindings: I) -> Result<&mut Self>
+    where
+        I: IntoIterator<Item = (&'a str, Binding)>,
+    {
+        for (name, binding) in bindings {
+            let binding = binding.checked()?;
+            let argument = self.bind(name)?;
+            binding.write(argument.query.connection())?;
+            self.connection().get_response()?;
+        }
+        Ok(self)
+    }
+
+    /// Binds each of the `vars` under its name, in the order of the names, as if [`bind`] was chained for every one
+    /// of them.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use basex::{Client, ClientError};
+    /// # use std::collections::BTreeMap;
+    /// # fn main() -> Result<(), ClientError> {
+    /// let client = Client::connect("localhost", 1984, "admin", "admin")?;
+    /// let xquery = "declare variable $title external; declare variable $author external; $title";
+    /// let mut query = client.query(xquery)?.without_info()?;
+    /// let vars = BTreeMap::from([
+    ///     ("title".to_owned(), "Lunar Report"),
+    ///     ("author".to_owned(), "Bogdanoff"),
+    /// ]);
+    /// query.bind_map(&vars)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// [`bind`]: self::Query::bind
+    pub fn bind_map<'a, V: ToQueryArgument<'a>>(&mut self, vars: &BTreeMap<String, V>) -> Result<&mut Self> {
+        for (name, value) in vars {
+            self.bind(name)?.with_value(value)?;
+        }
+        Ok(self)
+    }
+
     /// Executes the query and returns its response.
     ///
     /// The response is readable using the [`Read`] trait.
@@ -186,10 +313,252 @@ where
     ///
     /// [`Read`]: std::io::Read
     pub fn execute(mut self) -> Result<Response<T, HasInfo>> {
-        let connection: &mut Connection<T, Authenticated> = self.client.borrow_mut();
-        connection.send_cmd(Command::Execute as u8)?;
+        let started = self.start_execution()?;
+        Ok(Response::new(self, started))
+    }
+
+    /// Executes the query and returns its response, which borrows the query instead of taking it. Once the response
+    /// is closed, the query can be bound new arguments and executed again.
+    ///
+    /// The server keeps the bound values between executions, so only the ones that change need to be bound again.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use basex::{Client, ClientError};
+    /// # use std::io::Read;
+    /// # fn main() -> Result<(), ClientError> {
+    /// let client = Client::connect("localhost", 1984, "admin", "admin")?;
+    /// let mut query = client.query("declare variable $x external; $x * 2")?.without_info()?;
+    ///
+    /// for x in 1..=3 {
+    ///     query.bind("x")?.with_value(x)?;
+    ///     let mut result = String::new();
+    ///     let mut response = query.execute_keep()?;
+    ///     response.read_to_string(&mut result)?;
+    ///     response.close()?;
+    ///     println!("{}", result);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn execute_keep(&mut self) -> Result<Response<T, HasInfo, &mut Self>> {
+        let started = self.start_execution()?;
+        Ok(Response::new(self, started))
+    }
+
+    /// Executes the query and returns an iterator over its result items, each along with its [`XdmType`].
+    ///
+    /// As opposed to [`execute`], the boundaries between the items are kept.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use basex::{Client, ClientError, XdmType};
+    /// # fn main() -> Result<(), ClientError> {
+    /// let client = Client::connect("localhost", 1984, "admin", "admin")?;
+    /// let query = client.query("(1 to 3) ! string()")?.without_info()?;
+    /// let mut results = query.results()?;
+    /// for item in results.by_ref() {
+    ///     let (xdm_type, value) = item?;
+    ///     assert_eq!(XdmType::String, xdm_type);
+    ///     println!("{}", String::from_utf8_lossy(&value));
+    /// }
+    /// results.close()?.close()?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// [`XdmType`]: crate::XdmType
+    /// [`execute`]: self::Query::execute
+    pub fn results(mut self) -> Result<ResultIter<T, HasInfo>> {
+        let connection: &mut Connection<T, Authenticated> = self.client.as_mut().expect(CLOSED).borrow_mut();
+        connection.send_cmd(Command::Results as u8)?;
         connection.send_arg(&mut self.id.as_bytes())?;
-        Ok(Response::new(self))
+        connection.flush_pending()?;
+        Ok(ResultIter::new(self))
+    }
+
+    /// Executes the query and reads its whole result as a string, closing both the response and the query.
+    ///
+    /// Returns the result along with the client the query was created by. If the query fails, returns its
+    /// [`QueryFailed`] error instead of the partially read result.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use basex::{Client, ClientError};
+    /// # fn main() -> Result<(), ClientError> {
+    /// let client = Client::connect("localhost", 1984, "admin", "admin")?;
+    /// let query = client.query("count((1, 2, 3))")?.without_info()?;
+    /// let (result, client) = query.execute_to_string()?;
+    /// assert_eq!("3", result);
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// [`QueryFailed`]: crate::ClientError::QueryFailed
+    pub fn execute_to_string(self) -> Result<(String, Client<T>)> {
+        let mut response = self.execute()?;
+        let mut result = vec![];
+        response.read_to_end(&mut result)?;
+        let query = response.close()?;
+
+        Ok((String::from_utf8(result)?, query.close()?))
+    }
+
+    /// Executes the query and copies its whole result into `out`, closing both the response and the query.
+    ///
+    /// Returns the number of bytes written along with the client the query was created by. The result is streamed as
+    /// it arrives, so it is never held in memory as a whole. If the query fails, returns its [`QueryFailed`] error,
+    /// although part of the result may have been written already.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use basex::{Client, ClientError};
+    /// # fn main() -> Result<(), ClientError> {
+    /// let client = Client::connect("localhost", 1984, "admin", "admin")?;
+    /// let query = client.query("<wojak/>")?.without_info()?;
+    /// let mut out = vec![];
+    /// let (size, client) = query.execute_into(&mut out)?;
+    /// assert_eq!(8, size);
+    /// assert_eq!(b"<wojak/>".to_vec(), out);
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// [`QueryFailed`]: crate::ClientError::QueryFailed
+    pub fn execute_into<W: Write>(self, out: &mut W) -> Result<(u64, Client<T>)> {
+        let mut response = self.execute()?;
+        let size = copy(&mut response, out)?;
+        let query = response.close()?;
+
+        Ok((size, query.close()?))
+    }
+
+    /// Executes the query, parses its single atomic result into `V` and closes the query, returning back the client.
+    ///
+    /// The whitespace around the result is trimmed before parsing.
+    ///
+    /// # Errors
+    /// Fails with [`ResultParse`] when the result cannot be parsed into `V`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use basex::{Client, ClientError};
+    /// # fn main() -> Result<(), ClientError> {
+    /// let client = Client::connect("localhost", 1984, "admin", "admin")?;
+    /// let query = client.query("count((1, 2, 3))")?.without_info()?;
+    /// let (count, client): (usize, _) = query.execute_one()?;
+    ///
+    /// assert_eq!(3, count);
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// [`ResultParse`]: crate::ClientError::ResultParse
+    pub fn execute_one<V>(self) -> Result<(V, Client<T>)>
+    where
+        V: FromStr,
+        V::Err: Display,
+    {
+        let (result, client) = self.execute_to_string()?;
+        let result = result.trim();
+
+        match result.parse() {
+            Ok(value) => Ok((value, client)),
+            Err(error) => Err(ClientError::ResultParse {
+                result: result.to_owned(),
+                message: error.to_string(),
+            }),
+        }
+    }
+
+    /// Executes the query with the serializer switched to the JSON method, parses its result into a JSON value and
+    /// closes the query, returning back the client.
+    ///
+    /// The previous serializer options of the session are restored afterwards, even if the query fails.
+    ///
+    /// # Errors
+    /// Fails with [`JsonParse`] when the result is not valid JSON.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use basex::{Client, ClientError};
+    /// # fn main() -> Result<(), ClientError> {
+    /// let client = Client::connect("localhost", 1984, "admin", "admin")?;
+    /// let query = client.query("map { 'lunar': [1, 2] }")?.without_info()?;
+    /// let (value, client) = query.execute_json()?;
+    ///
+    /// assert_eq!(2, value["lunar"][1]);
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// [`JsonParse`]: crate::ClientError::JsonParse
+    #[cfg(feature = "serde_json")]
+    pub fn execute_json(mut self) -> Result<(serde_json::Value, Client<T>)> {
+        let previous = self.client().get_option(ServerOption::Serializer)?;
+        let mut options = Options::from_str(&previous).map_err(|e| ClientError::Protocol(e.to_string()))?;
+        options.set_method(SerializationMethod::Json);
+        self.client()
+            .set_option(ServerOption::Serializer, &options.to_string())?;
+
+        let result = self.read_kept();
+        let restored = self.client().set_option(ServerOption::Serializer, &previous);
+        let result = result?;
+        restored?;
+        let client = self.close()?;
+
+        match serde_json::from_slice(&result) {
+            Ok(value) => Ok((value, client)),
+            Err(error) => Err(ClientError::JsonParse(error)),
+        }
+    }
+
+    /// Executes the updating query with the `QUERYINFO` option turned on and closes it, returning how many items it
+    /// updated along with the client. Any result of the query is discarded.
+    ///
+    /// The option is restored to its previous value afterwards, even if the query fails.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use basex::{Client, ClientError};
+    /// # fn main() -> Result<(), ClientError> {
+    /// let mut client = Client::connect("localhost", 1984, "admin", "admin")?;
+    /// client.create("lunar")?.with_input("<lunar><crater/><crater/></lunar>")?;
+    ///
+    /// let query = client.query("delete node //crater")?.without_info()?;
+    /// let (updated, client) = query.execute_update()?;
+    ///
+    /// assert_eq!(2, updated);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn execute_update(mut self) -> Result<(usize, Client<T>)> {
+        let previous = self.client().get_option(ServerOption::QueryInfo)?;
+        self.client().set_option(ServerOption::QueryInfo, "true")?;
+
+        let info = self.read_kept().and_then(|_| self.raw_info());
+        let restored = self.client().set_option(ServerOption::QueryInfo, &previous);
+        let info = info?;
+        restored?;
+        let client = self.close()?;
+
+        Ok((info.updated(), client))
+    }
+
+    /// Executes the query, keeping it for further use, and reads its whole result.
+    fn read_kept(&mut self) -> Result<Vec<u8>> {
+        let mut response = self.execute_keep()?;
+        let mut result = vec![];
+        response.read_to_end(&mut result)?;
+        response.close()?;
+        Ok(result)
     }
 
     /// Returns all query serialization options.
@@ -209,13 +578,43 @@ where
     /// # Ok(())
     /// # }
     pub fn options(&mut self) -> Result<Options> {
-        let connection: &mut Connection<T, Authenticated> = self.client.borrow_mut();
+        let connection: &mut Connection<T, Authenticated> = self.client.as_mut().expect(CLOSED).borrow_mut();
         connection.send_cmd(Command::Options as u8)?;
         connection.send_arg(&mut self.id.as_bytes())?;
         let response = self.connection().get_response()?;
         Ok(Options::from_str(&response).unwrap())
     }
 
+    /// Applies the serialization `options` to the following executions of this query, without closing it.
+    ///
+    /// The `OPTIONS` command of the query mode can only return the options, not change them. So the options are set
+    /// as the `SERIALIZER` of the session instead, which the server reads on every execution. They thus stay in effect
+    /// for the other queries of the session as well, until changed again.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use basex::{Client, ClientError};
+    /// # use std::io::Read;
+    /// # fn main() -> Result<(), ClientError> {
+    /// let client = Client::connect("localhost", 1984, "admin", "admin")?;
+    /// let mut query = client.query("<wojak><pink_index/></wojak>")?.without_info()?;
+    /// let mut options = query.options()?;
+    /// options.set_indent(false);
+    ///
+    /// let mut result = String::new();
+    /// query.apply_options(&options)?;
+    /// query.execute()?.read_to_string(&mut result)?;
+    /// assert_eq!("<wojak><pink_index/></wojak>", result);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn apply_options(&mut self, options: &Options) -> Result<&mut Self> {
+        self.client()
+            .set_option(ServerOption::Serializer, &options.to_string())?;
+        Ok(self)
+    }
+
     /// Replaces whatever context is set (if any) to the given `value`.
     ///
     /// By default the context is set to currently opened database (if any). Setting context allows you to run query
@@ -243,11 +642,33 @@ where
     /// # }
     /// ```
     pub fn context<'a>(&mut self, value: impl AsResource<'a>) -> Result<&mut Self> {
-        let connection: &mut Connection<T, Authenticated> = self.client.borrow_mut();
+        self.context_typed(value, "document-node()")
+    }
+
+    /// Replaces whatever context is set (if any) to the given `value` interpreted as the XQuery type `type_name`, e.g.
+    /// `xs:string` or `element()`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use basex::{Client, ClientError};
+    /// # fn main() -> Result<(), ClientError> {
+    /// let client = Client::connect("localhost", 1984, "admin", "admin")?;
+    /// let mut query = client.query("string-length(.)")?.without_info()?;
+    /// query.context_typed("lunar", "xs:string")?;
+    /// let (result, _) = query.execute_to_string()?;
+    ///
+    /// assert_eq!("5", result);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn context_typed<'a>(&mut self, value: impl AsResource<'a>, type_name: &str) -> Result<&mut Self> {
+        let mut value = value.try_into_read()?;
+        let connection: &mut Connection<T, Authenticated> = self.client.as_mut().expect(CLOSED).borrow_mut();
         connection.send_cmd(Command::Context as u8)?;
         connection.send_arg(&mut self.id.as_bytes())?;
-        connection.send_arg(&mut value.into_read())?;
-        connection.send_arg(&mut "document-node()".as_bytes())?;
+        connection.send_arg(&mut value)?;
+        connection.send_arg(&mut type_name.as_bytes())?;
         connection.get_response()?;
         Ok(self)
     }
@@ -275,19 +696,147 @@ where
     /// # }
     /// ```
     pub fn updating(&mut self) -> Result<bool> {
-        let connection: &mut Connection<T, Authenticated> = self.client.borrow_mut();
+        let connection: &mut Connection<T, Authenticated> = self.client.as_mut().expect(CLOSED).borrow_mut();
         connection.send_cmd(Command::Updating as u8)?;
         connection.send_arg(&mut self.id.as_bytes())?;
 
         match self.connection().get_response()?.as_str() {
             "true" => Ok(true),
             "false" => Ok(false),
-            other => panic!("Expected boolean string, got \"{}\"", other),
+            other => Err(ClientError::Protocol(format!(
+                "expected boolean string, got \"{}\"",
+                other
+            ))),
         }
     }
 
+    fn raw_info(&mut self) -> Result<RawInfo> {
+        let connection: &mut Connection<T, Authenticated> = self.client.as_mut().expect(CLOSED).borrow_mut();
+        connection.send_cmd(Command::Info as u8)?;
+        connection.send_arg(&mut self.id.as_bytes())?;
+        Ok(RawInfo::new(self.connection().get_response()?))
+    }
+
+    /// Sends the execute command, notifying the observer, and returns when the execution started.
+    fn start_execution(&mut self) -> Result<Instant> {
+        let started = Instant::now();
+        let client = self.client.as_ref().expect(CLOSED);
+        client.observer().on_query_start(&self.id);
+
+        if let Err(error) = self.send_execute() {
+            let client = self.client.as_ref().expect(CLOSED);
+            client.observer().on_query_end(&self.id, started.elapsed(), Err(&error));
+            return Err(error);
+        }
+
+        Ok(started)
+    }
+
+    fn send_execute(&mut self) -> Result<()> {
+        let connection: &mut Connection<T, Authenticated> = self.client.as_mut().expect(CLOSED).borrow_mut();
+        connection.send_cmd(Command::Execute as u8)?;
+        connection.send_arg(&mut self.id.as_bytes())?;
+        connection.flush_pending()?;
+        Ok(())
+    }
+
+    /// The identifier the server assigned to the query, e.g. to correlate it with the server logs.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use basex::{Client, ClientError};
+    /// # fn main() -> Result<(), ClientError> {
+    /// let client = Client::connect("localhost", 1984, "admin", "admin")?;
+    /// let query = client.query("count(/)")?.without_info()?;
+    /// println!("Query {} was created", query.id());
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+
     fn connection(&mut self) -> &mut Connection<T, Authenticated> {
-        self.client.borrow_mut()
+        self.client().borrow_mut()
+    }
+
+    fn client(&mut self) -> &mut Client<T> {
+        self.borrow_mut()
+    }
+}
+
+impl<HasInfo> Query<TcpStream, HasInfo> {
+    /// Executes the query, reads its whole result within the `timeout` and closes the query, returning back the client.
+    ///
+    /// The time left is set as the read timeout of the underlying stream before every read, and the previous read
+    /// timeout is restored once the result is read or fails to be read for any reason other than the timeout.
+    ///
+    /// # Errors
+    /// Fails with [`Timeout`] when the result is not read in time. The connection is left in the middle of the response
+    /// then, so it is shut down and the client is dropped instead of being handed back.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use basex::{Client, ClientError};
+    /// # use std::time::Duration;
+    /// # fn main() -> Result<(), ClientError> {
+    /// let client = Client::connect("localhost", 1984, "admin", "admin")?;
+    /// let query = client.query("count(1 to 10)")?.without_info()?;
+    /// let (result, client) = query.execute_with_timeout(Duration::from_secs(5))?;
+    ///
+    /// assert_eq!("10", result);
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// [`Timeout`]: crate::ClientError::Timeout
+    pub fn execute_with_timeout(self, timeout: Duration) -> Result<(String, Client<TcpStream>)> {
+        let deadline = Instant::now() + timeout;
+        let client: &Client<TcpStream> = self.borrow();
+        let connection: &Connection<TcpStream, Authenticated> = client.borrow();
+        let stream = connection.stream().try_clone()?;
+        let previous_timeout = stream.read_timeout()?;
+        let give_up = || {
+            let _ = stream.shutdown(Shutdown::Both);
+            ClientError::Timeout(timeout)
+        };
+        let timed_out = |error: ClientError| match error {
+            ClientError::Io(e) if matches!(e.kind(), ErrorKind::WouldBlock | ErrorKind::TimedOut) => give_up(),
+            other => other,
+        };
+        let limit_read = || -> Result<()> {
+            match deadline.checked_duration_since(Instant::now()) {
+                Some(left) if !left.is_zero() => Ok(stream.set_read_timeout(Some(left))?),
+                _ => Err(give_up()),
+            }
+        };
+
+        let read_all = || -> Result<(Vec<u8>, Query<TcpStream, HasInfo>)> {
+            let mut response = self.execute()?;
+            let mut result = vec![];
+            let mut chunk = [0u8; 4096];
+            loop {
+                limit_read()?;
+                match response.read(&mut chunk) {
+                    Ok(0) => break,
+                    Ok(size) => result.extend_from_slice(&chunk[..size]),
+                    Err(e) if e.kind() == ErrorKind::Interrupted => continue,
+                    Err(e) => return Err(timed_out(e.into())),
+                }
+            }
+            limit_read()?;
+            Ok((result, response.close().map_err(timed_out)?))
+        };
+
+        let read = read_all();
+        if !matches!(read, Err(ClientError::Timeout(_))) {
+            stream.set_read_timeout(previous_timeout)?;
+        }
+        let (result, query) = read?;
+
+        Ok((String::from_utf8(result)?, query.close()?))
     }
 }
 
@@ -299,12 +848,39 @@ where
     ///
     /// [`Query`]: self::Query
     pub(crate) fn without_info(id: String, client: Client<T>) -> Query<T, WithoutInfo> {
+        client.track_query(&id);
         Self {
             has_info: Default::default(),
             id,
-            client,
+            client: Some(client),
         }
     }
+
+    /// Returns the query compilation and profiling [`Info`] by turning the `QUERYINFO` option on just for this call.
+    ///
+    /// The option is restored to its previous value afterwards, even if getting the info fails.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use basex::{Query, DatabaseStream, WithoutInfo, compiler::Info, Result};
+    /// # fn example<T: DatabaseStream>(mut query: Query<T, WithoutInfo>) -> Result<()> {
+    /// let info = query.fetch_info()?;
+    /// println!("Optimized query: {}", info.optimized_query());
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// [`Info`]: super::compiler::Info
+    pub fn fetch_info(&mut self) -> Result<impl Info> {
+        let previous = self.client().get_option(ServerOption::QueryInfo)?;
+
+        self.client().set_option(ServerOption::QueryInfo, "true")?;
+        let info = self.raw_info();
+        self.client().set_option(ServerOption::QueryInfo, &previous)?;
+
+        info
+    }
 }
 
 impl<T> Query<T, WithInfo>
@@ -315,10 +891,11 @@ where
     ///
     /// [`Query`]: self::Query
     pub(crate) fn with_info(id: String, client: Client<T>) -> Query<T, WithInfo> {
+        client.track_query(&id);
         Self {
             has_info: Default::default(),
             id,
-            client,
+            client: Some(client),
         }
     }
 
@@ -341,10 +918,7 @@ where
     ///
     /// [`Info`]: super::analysis::Info
     pub fn info(&mut self) -> Result<impl Info> {
-        let connection: &mut Connection<T, Authenticated> = self.client.borrow_mut();
-        connection.send_cmd(Command::Info as u8)?;
-        connection.send_arg(&mut self.id.as_bytes())?;
-        Ok(RawInfo::new(self.connection().get_response()?))
+        self.raw_info()
     }
 }
 
@@ -353,7 +927,7 @@ where
     T: DatabaseStream,
 {
     fn borrow(&self) -> &Client<T> {
-        &self.client
+        self.client.as_ref().expect(CLOSED)
     }
 }
 
@@ -362,7 +936,24 @@ where
     T: DatabaseStream,
 {
     fn borrow_mut(&mut self) -> &mut Client<T> {
-        &mut self.client
+        self.client.as_mut().expect(CLOSED)
+    }
+}
+
+impl<T, HasInfo> Drop for Query<T, HasInfo>
+where
+    T: DatabaseStream,
+{
+    /// Deletes the query on the server unless it was closed, ignoring any errors. Call [`close`] to get the client back
+    /// or to find out whether deleting the query failed.
+    ///
+    /// [`close`]: self::Query::close
+    fn drop(&mut self) {
+        if let Some(mut client) = self.client.take() {
+            if client.forget_query(&self.id) {
+                let _ = close_query(client.borrow_mut(), &self.id);
+            }
+        }
     }
 }
 
@@ -370,37 +961,49 @@ where
 mod tests {
     use super::*;
     use crate::query::compiler::tests::QUERY_INFO;
-    use crate::tests::FailingStream;
-    use crate::{assert_query_info, ClientError};
-    use std::io::{empty, Read};
+    use crate::tests::{spawn_server_replying, FailingStream};
+    use crate::{assert_query_info, ClientError, QueryObserver};
+    use std::io::empty;
+    use std::sync::{Arc, Mutex};
 
     impl<T, HasInfo> Query<T, HasInfo>
     where
         T: DatabaseStream,
     {
-        pub(crate) fn into_inner(self) -> Connection<T, Authenticated> {
-            self.client.into_inner()
+        pub(crate) fn into_inner(mut self) -> Connection<T, Authenticated> {
+            self.client.take().unwrap().into_inner()
         }
     }
 
     #[test]
     fn test_with_info_formats_as_debug() {
-        format!("{:?}", WithInfo);
+        let _ = format!("{:?}", WithInfo);
     }
 
     #[test]
     fn test_without_info_formats_as_debug() {
-        format!("{:?}", WithoutInfo);
+        let _ = format!("{:?}", WithoutInfo);
     }
 
     #[test]
     fn test_formats_as_debug() {
-        format!(
+        let _ = format!(
             "{:?}",
             Query::with_info("".to_owned(), Client::new(Connection::failing()))
         );
     }
 
+    #[test]
+    fn test_exposes_id_received_from_server() {
+        let (address, server) = spawn_server_replying(vec![(1, b"\0\0\0"), (2, b"query_7\0\0")]);
+        let client = Client::connect("127.0.0.1", address.port(), "admin", "admin").unwrap();
+
+        let query = client.query("count(/)").unwrap().without_info().unwrap();
+        let _ = server.join().unwrap();
+
+        assert_eq!("query_7", query.id());
+    }
+
     #[test]
     fn test_borrows_as_client() {
         let _: &Client<FailingStream> = Query::with_info("".to_owned(), Client::new(Connection::failing())).borrow();
@@ -431,12 +1034,138 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_query_binds_map_ordered_by_name() -> Result<()> {
+        let connection = Connection::from_str("\0\0\0\0");
+        let mut query = Query::with_info("test".to_owned(), Client::new(connection));
+        let vars = BTreeMap::from([("title".to_owned(), "Lunar"), ("author".to_owned(), "Bogdanoff")]);
+
+        query.bind_map(&vars)?;
+
+        let actual_buffer = query.into_inner().into_inner().to_string();
+        let expected_buffer = "\u{3}test\u{0}author\u{0}Bogdanoff\u{0}xs:string\u{0}\
+            \u{3}test\u{0}title\u{0}Lunar\u{0}xs:string\u{0}"
+            .to_owned();
+
+        assert_eq!(expected_buffer, actual_buffer);
+        Ok(())
+    }
+
+    #[test]
+    fn test_query_binds_all_arguments_like_chained_binds() -> Result<()> {
+        let mut chained = Query::with_info("test".to_owned(), Client::new(Connection::from_str("\0\0\0\0\0\0\0")));
+        chained
+            .bind("foo")?
+            .with_value("aaa")?
+            .bind("bar")?
+            .with_value(123)?
+            .bind("void")?
+            .without_value()?;
+
+        let mut query = Query::with_info("test".to_owned(), Client::new(Connection::from_str("\0\0\0\0\0\0\0")));
+        query.bind_all(vec![
+            ("foo", Binding::value("aaa")),
+            ("bar", 123.into()),
+            ("void", Binding::Empty),
+        ])?;
+
+        assert_eq!(
+            chained.into_inner().into_inner().to_string(),
+            query.into_inner().into_inner().to_string()
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_query_fails_to_bind_all_arguments_failing_to_encode_without_sending_anything() {
+        struct Unencodable;
+
+        impl<'a> ToQueryArgument<'a> for Unencodable {
+            fn write_xquery<T: DatabaseStream>(&self, _: &mut ArgumentWriter<T>) -> Result<()> {
+                Err(ClientError::InvalidName("unencodable".to_owned()))
+            }
+
+            fn xquery_type() -> String {
+                "xs:string".to_owned()
+            }
+        }
+
+        let mut query = Query::with_info("test".to_owned(), Client::new(Connection::from_str("")));
+
+        let actual_error = query
+            .bind_all(vec![("foo", Binding::value(Unencodable))])
+            .expect_err("Operation must fail");
+
+        assert!(matches!(actual_error, ClientError::InvalidName(_)));
+        assert_eq!("", query.into_inner().into_inner().to_string());
+    }
+
+    #[test]
+    fn test_query_fails_to_bind_all_arguments_rejected_by_server() {
+        let connection = Connection::from_str("Item expected.\0\u{1}");
+        let mut query = Query::with_info("test".to_owned(), Client::new(connection));
+
+        let actual_error = query
+            .bind_all(vec![("foo", Binding::value("aaa")), ("bar", Binding::Empty)])
+            .expect_err("Operation must fail");
+
+        assert!(matches!(
+            actual_error,
+            ClientError::CommandFailed { message } if message == "Item expected."
+        ));
+    }
+
+    #[test]
+    fn test_query_binds_sequences() -> Result<()> {
+        let connection = Connection::from_str("\0\0\0\0\0");
+
+        let mut query = Query::with_info("test".to_owned(), Client::new(connection));
+
+        query
+            .bind("numbers")?
+            .with_sequence(vec![1, 2, 3])?
+            .bind("words")?
+            .with_sequence(["aaa"].iter())?
+            .bind("empty")?
+            .with_sequence(Vec::<String>::new())?;
+
+        let stream = query.into_inner().into_inner();
+        let actual_buffer = stream.to_string();
+        let expected_buffer = "\u{3}test\u{0}numbers\u{0}1\u{1}xs:int\u{2}2\u{1}xs:int\u{2}3\u{1}xs:int\u{0}\u{0}\
+            \u{3}test\u{0}words\u{0}aaa\u{1}xs:string\u{0}\u{0}\
+            \u{3}test\u{0}empty\u{0}\u{0}empty-sequence()\u{0}"
+            .to_owned();
+
+        assert_eq!(expected_buffer, actual_buffer);
+        Ok(())
+    }
+
+    #[test]
+    fn test_query_fails_to_bind_sequence_rejected_by_server() {
+        let connection = Connection::from_str("Item expected.\0\u{1}");
+        let mut query = Query::with_info("test".to_owned(), Client::new(connection));
+
+        let actual_error = query
+            .bind("numbers")
+            .unwrap()
+            .with_sequence(vec![1, 2, 3])
+            .expect_err("Operation must fail");
+
+        assert!(matches!(
+            actual_error,
+            ClientError::CommandFailed { message } if message == "Item expected."
+        ));
+    }
+
     #[test]
     fn test_query_fails_to_bind_argument_with_failing_stream() {
         let connection = Connection::failing();
 
         let mut query = Query::with_info("test".to_owned(), Client::new(connection));
-        let actual_error = query.bind("foo").err().expect("Operation must fail");
+        let actual_error = query
+            .bind("foo")
+            .and_then(|argument| argument.with_value("bar"))
+            .expect_err("Operation must fail");
 
         assert!(matches!(actual_error, ClientError::Io(_)));
     }
@@ -456,15 +1185,15 @@ mod tests {
     }
 
     #[test]
-    fn test_query_binds_value_to_context_with_empty_type() {
+    fn test_query_binds_value_to_context_with_given_type() {
         let connection = Connection::from_str("\0\0");
 
         let mut query = Query::with_info("test".to_owned(), Client::new(connection));
-        let _ = query.context("aaa").unwrap();
+        let _ = query.context_typed("aaa", "xs:string").unwrap();
 
         let stream = query.into_inner().into_inner();
         let actual_buffer = stream.to_string();
-        let expected_buffer = "\u{e}test\u{0}aaa\u{0}document-node()\u{0}".to_owned();
+        let expected_buffer = "\u{e}test\u{0}aaa\u{0}xs:string\u{0}".to_owned();
 
         assert_eq!(expected_buffer, actual_buffer);
     }
@@ -483,12 +1212,26 @@ mod tests {
         assert_eq!(expected_buffer, actual_buffer);
     }
 
+    #[test]
+    fn test_query_binds_value_to_context_with_empty_type() {
+        let connection = Connection::from_str("\0\0");
+
+        let mut query = Query::with_info("test".to_owned(), Client::new(connection));
+        let _ = query.context_typed("aaa", "").unwrap();
+
+        let stream = query.into_inner().into_inner();
+        let actual_buffer = stream.to_string();
+        let expected_buffer = "\u{e}test\u{0}aaa\u{0}\u{0}".to_owned();
+
+        assert_eq!(expected_buffer, actual_buffer);
+    }
+
     #[test]
     fn test_query_fails_to_bind_context_with_failing_stream() {
         let connection = Connection::failing();
 
         let mut query = Query::with_info("test".to_owned(), Client::new(connection));
-        let actual_error = query.context(&mut empty()).err().expect("Operation must fail");
+        let actual_error = query.context(&mut empty()).expect_err("Operation must fail");
 
         assert!(matches!(actual_error, ClientError::Io(_)));
     }
@@ -513,6 +1256,311 @@ mod tests {
         assert_eq!(expected_buffer, actual_buffer);
     }
 
+    #[derive(Default)]
+    struct RecordingObserver(Arc<Mutex<Vec<String>>>);
+
+    impl QueryObserver for RecordingObserver {
+        fn on_query_start(&self, id: &str) {
+            self.0.lock().unwrap().push(format!("start {}", id));
+        }
+
+        fn on_query_end(&self, id: &str, _elapsed: Duration, result: std::result::Result<(), &ClientError>) {
+            self.0.lock().unwrap().push(format!("end {} {}", id, result.is_ok()));
+        }
+    }
+
+    #[test]
+    fn test_query_notifies_observer_around_execution() {
+        let events = Arc::new(Mutex::new(vec![]));
+        let mut client = Client::new(Connection::from_str("test_response\0"));
+        client.set_observer(Box::new(RecordingObserver(Arc::clone(&events))));
+
+        let query = Query::with_info("test".to_owned(), client);
+        let mut response = query.execute().unwrap();
+        assert_eq!(vec!["start test"], *events.lock().unwrap());
+
+        response.read_to_string(&mut String::new()).unwrap();
+        let _ = response.close().unwrap();
+
+        assert_eq!(vec!["start test", "end test true"], *events.lock().unwrap());
+    }
+
+    #[test]
+    fn test_query_notifies_observer_about_failure() {
+        let events = Arc::new(Mutex::new(vec![]));
+        let mut client = Client::new(Connection::from_str(
+            "\0\u{1}Stopped at ., 1/1:\n[XPST0008] Undeclared variable: $x.\0",
+        ));
+        client.set_observer(Box::new(RecordingObserver(Arc::clone(&events))));
+
+        let query = Query::with_info("test".to_owned(), client);
+        let _ = query.execute().unwrap().close().expect_err("Operation must fail");
+
+        assert_eq!(vec!["start test", "end test false"], *events.lock().unwrap());
+    }
+
+    #[test]
+    fn test_query_notifies_observer_when_response_is_dropped() {
+        let events = Arc::new(Mutex::new(vec![]));
+        let mut client = Client::new(Connection::from_str("test_response\0"));
+        client.set_observer(Box::new(RecordingObserver(Arc::clone(&events))));
+
+        let query = Query::with_info("test".to_owned(), client);
+        drop(query.execute().unwrap());
+
+        assert_eq!(vec!["start test", "end test true"], *events.lock().unwrap());
+    }
+
+    #[test]
+    fn test_query_executes_repeatedly_with_different_bindings() {
+        let (address, server) = spawn_server_replying(vec![(4, b"\0\0"), (1, b"2\0\0"), (4, b"\0\0"), (1, b"4\0\0")]);
+        let client = Client::connect("127.0.0.1", address.port(), "admin", "admin").unwrap();
+        let mut query = Query::without_info("test".to_owned(), client);
+        let mut results = vec![];
+
+        for x in 1..=2 {
+            query.bind("x").unwrap().with_value(x).unwrap();
+            let mut result = String::new();
+            let mut response = query.execute_keep().unwrap();
+            response.read_to_string(&mut result).unwrap();
+            response.close().unwrap();
+            results.push(result);
+        }
+        let _ = server.join().unwrap();
+
+        assert_eq!(vec!["2", "4"], results);
+    }
+
+    #[test]
+    fn test_query_executes_kept_with_buffer() {
+        let connection = Connection::from_str("test_response\0");
+
+        let mut query = Query::without_info("test".to_owned(), Client::new(connection));
+        let mut actual_response = String::new();
+        let mut response = query.execute_keep().unwrap();
+        response.read_to_string(&mut actual_response).unwrap();
+        let _ = response.close().unwrap();
+
+        assert_eq!("test_response", actual_response);
+        assert_eq!("\u{5}test\u{0}", query.into_inner().into_inner().to_string());
+    }
+
+    #[test]
+    fn test_query_executes_to_string() {
+        let (address, server) = spawn_server_replying(vec![(1, b"test_response\0\0"), (1, b"\0\0")]);
+        let client = Client::connect("127.0.0.1", address.port(), "admin", "admin").unwrap();
+
+        let query = Query::without_info("test".to_owned(), client);
+        let (actual_response, _) = query.execute_to_string().unwrap();
+        let _ = server.join().unwrap();
+
+        assert_eq!("test_response", actual_response);
+    }
+
+    #[test]
+    fn test_query_fails_to_execute_to_string_with_query_error() {
+        let connection =
+            Connection::from_str("partial\0\u{1}Stopped at ., 1/1:\n[XPST0008] Undeclared variable: $x.\0");
+
+        let query = Query::with_info("test".to_owned(), Client::new(connection));
+        let actual_error = query.execute_to_string().expect_err("Operation must fail");
+
+        assert!(matches!(actual_error, ClientError::QueryFailed(q) if q.code() == "XPST0008"));
+    }
+
+    #[test]
+    fn test_query_executes_into_writer() {
+        let (address, server) = spawn_server_replying(vec![(1, b"test\xFF\0response\0\0"), (1, b"\0\0")]);
+        let client = Client::connect("127.0.0.1", address.port(), "admin", "admin").unwrap();
+
+        let query = Query::without_info("test".to_owned(), client);
+        let mut out = vec![];
+        let (size, _) = query.execute_into(&mut out).unwrap();
+        let _ = server.join().unwrap();
+
+        assert_eq!(13, size);
+        assert_eq!(b"test\0response".to_vec(), out);
+    }
+
+    #[test]
+    fn test_query_fails_to_execute_into_writer_with_query_error() {
+        let connection =
+            Connection::from_str("partial\0\u{1}Stopped at ., 1/1:\n[XPST0008] Undeclared variable: $x.\0");
+
+        let query = Query::with_info("test".to_owned(), Client::new(connection));
+        let mut out = vec![];
+        let actual_error = query.execute_into(&mut out).err().unwrap();
+
+        assert!(matches!(actual_error, ClientError::QueryFailed(q) if q.code() == "XPST0008"));
+        assert_eq!(b"partial".to_vec(), out);
+    }
+
+    #[test]
+    fn test_query_executes_one_usize() {
+        let (address, server) = spawn_server_replying(vec![(1, b"42\0\0"), (1, b"\0\0")]);
+        let client = Client::connect("127.0.0.1", address.port(), "admin", "admin").unwrap();
+
+        let query = Query::without_info("test".to_owned(), client);
+        let (actual_result, _): (usize, _) = query.execute_one().unwrap();
+        let _ = server.join().unwrap();
+
+        assert_eq!(42, actual_result);
+    }
+
+    #[test]
+    fn test_query_executes_one_bool() {
+        let (address, server) = spawn_server_replying(vec![(1, b" true\n\0\0"), (1, b"\0\0")]);
+        let client = Client::connect("127.0.0.1", address.port(), "admin", "admin").unwrap();
+
+        let query = Query::without_info("test".to_owned(), client);
+        let (actual_result, _): (bool, _) = query.execute_one().unwrap();
+        let _ = server.join().unwrap();
+
+        assert!(actual_result);
+    }
+
+    #[test]
+    fn test_query_executes_one_f64() {
+        let (address, server) = spawn_server_replying(vec![(1, b"0.25\0\0"), (1, b"\0\0")]);
+        let client = Client::connect("127.0.0.1", address.port(), "admin", "admin").unwrap();
+
+        let query = Query::without_info("test".to_owned(), client);
+        let (actual_result, _): (f64, _) = query.execute_one().unwrap();
+        let _ = server.join().unwrap();
+
+        assert_eq!(0.25, actual_result);
+    }
+
+    #[test]
+    fn test_query_fails_to_execute_one_with_unparsable_result() {
+        let (address, server) = spawn_server_replying(vec![(1, b"lunar\0\0"), (1, b"\0\0")]);
+        let client = Client::connect("127.0.0.1", address.port(), "admin", "admin").unwrap();
+
+        let query = Query::without_info("test".to_owned(), client);
+        let actual_error = query.execute_one::<usize>().expect_err("Operation must fail");
+        let _ = server.join().unwrap();
+
+        assert!(matches!(actual_error, ClientError::ResultParse { result, .. } if result == "lunar"));
+    }
+
+    #[test]
+    fn test_query_executes_with_timeout() {
+        let (address, server) = spawn_server_replying(vec![(1, b"test_response\0\0"), (1, b"\0\0")]);
+        let client = Client::connect("127.0.0.1", address.port(), "admin", "admin").unwrap();
+
+        let query = Query::without_info("test".to_owned(), client);
+        let (actual_response, client) = query.execute_with_timeout(Duration::from_secs(5)).unwrap();
+        let _ = server.join().unwrap();
+
+        assert_eq!("test_response", actual_response);
+        assert_eq!(None, client.into_inner().into_inner().read_timeout().unwrap());
+    }
+
+    #[test]
+    fn test_query_fails_to_execute_with_timeout_when_result_is_slow() {
+        let (address, server) = spawn_server_replying(vec![(1, b"partial")]);
+        let client = Client::connect("127.0.0.1", address.port(), "admin", "admin").unwrap();
+
+        let query = Query::without_info("test".to_owned(), client);
+        let actual_error = query
+            .execute_with_timeout(Duration::from_millis(100))
+            .expect_err("Operation must fail");
+        let _ = server.join().unwrap();
+
+        assert!(matches!(actual_error, ClientError::Timeout(t) if t == Duration::from_millis(100)));
+    }
+
+    #[test]
+    fn test_query_restores_read_timeout_when_failing_to_execute_with_timeout() {
+        let (address, server) = spawn_server_replying(vec![(1, b"partial\0\x01Stopped at line 1, column 1.\0")]);
+        let client = Client::connect("127.0.0.1", address.port(), "admin", "admin").unwrap();
+        let clone = client.clone();
+
+        let query = Query::without_info("test".to_owned(), client);
+        let actual_error = query
+            .execute_with_timeout(Duration::from_secs(5))
+            .expect_err("Operation must fail");
+        let _ = server.join().unwrap();
+
+        assert!(matches!(actual_error, ClientError::QueryFailed(_)));
+        assert_eq!(None, clone.into_inner().into_inner().read_timeout().unwrap());
+    }
+
+    #[cfg(feature = "serde_json")]
+    #[test]
+    fn test_query_executes_json() {
+        let (address, server) = spawn_server_replying(vec![
+            (1, b"SERIALIZER: indent=no\0\0\0"),
+            (1, b"\0\0\0"),
+            (1, b"{\"lunar\": [1, 2]}\0\0"),
+            (1, b"\0\0\0"),
+            (1, b"\0\0"),
+        ]);
+        let client = Client::connect("127.0.0.1", address.port(), "admin", "admin").unwrap();
+
+        let query = Query::without_info("test".to_owned(), client);
+        let (value, _) = query.execute_json().unwrap();
+        let _ = server.join().unwrap();
+
+        assert_eq!(serde_json::json!({"lunar": [1, 2]}), value);
+    }
+
+    #[cfg(feature = "serde_json")]
+    #[test]
+    fn test_query_fails_to_execute_json_with_invalid_result() {
+        let (address, server) = spawn_server_replying(vec![
+            (1, b"SERIALIZER: \0\0\0"),
+            (1, b"\0\0\0"),
+            (1, b"lunar\0\0"),
+            (1, b"\0\0\0"),
+            (1, b"\0\0"),
+        ]);
+        let client = Client::connect("127.0.0.1", address.port(), "admin", "admin").unwrap();
+
+        let query = Query::without_info("test".to_owned(), client);
+        let actual_error = query.execute_json().err().unwrap();
+        let _ = server.join().unwrap();
+
+        assert!(matches!(actual_error, ClientError::JsonParse(_)));
+    }
+
+    #[test]
+    fn test_query_executes_update() {
+        let (address, server) = spawn_server_replying(vec![
+            (1, b"QUERYINFO: false\0\0\0"),
+            (1, b"\0\0\0"),
+            (1, b"\0\0"),
+            (1, b"Hit(s): 0 Items\nUpdated: 3 Items\nPrinted: 0 b\n\0\0"),
+            (1, b"\0\0\0"),
+            (1, b"\0\0"),
+        ]);
+        let client = Client::connect("127.0.0.1", address.port(), "admin", "admin").unwrap();
+
+        let query = Query::without_info("test".to_owned(), client);
+        let (updated, _) = query.execute_update().unwrap();
+        let _ = server.join().unwrap();
+
+        assert_eq!(3, updated);
+    }
+
+    #[test]
+    fn test_query_fails_to_execute_update_and_restores_query_info() {
+        let (address, server) = spawn_server_replying(vec![
+            (1, b"QUERYINFO: false\0\0\0"),
+            (1, b"\0\0\0"),
+            (1, b"\0\x01Stopped at ., 1/1:\n[XUDY0027] Target is empty.\0"),
+            (1, b"\0\0\0"),
+            (1, b"\0\0"),
+        ]);
+        let client = Client::connect("127.0.0.1", address.port(), "admin", "admin").unwrap();
+
+        let query = Query::without_info("test".to_owned(), client);
+        let actual_error = query.execute_update().err().unwrap();
+        let _ = server.join().unwrap();
+
+        assert!(matches!(actual_error, ClientError::QueryFailed(q) if q.code() == "XUDY0027"));
+    }
+
     #[test]
     fn test_query_fails_to_execute_with_failing_stream() {
         let connection = Connection::failing();
@@ -556,13 +1604,14 @@ mod tests {
     }
 
     #[test]
-    #[should_panic]
-    fn test_query_panics_updating_command_response_is_not_bool() {
+    fn test_query_fails_when_updating_command_response_is_not_bool() {
         let connection = Connection::from_str("test_response\0");
         let client = Client::new(connection);
 
         let mut query = Query::with_info("test".to_owned(), client);
-        let _ = query.updating().unwrap();
+        let actual_error = query.updating().expect_err("Operation must fail");
+
+        assert!(matches!(actual_error, ClientError::Protocol(_)));
     }
 
     #[test]
@@ -578,7 +1627,7 @@ mod tests {
     #[test]
     fn test_query_runs_options_command() {
         let expected_response = "ident=no";
-        let connection = Connection::from_str(&format!("{}\0\0", expected_response));
+        let connection = Connection::from_str(format!("{}\0\0", expected_response));
 
         let mut query = Query::with_info("test".to_owned(), Client::new(connection));
         let actual_response = query.options().unwrap();
@@ -592,6 +1641,19 @@ mod tests {
         assert_eq!(expected_buffer, actual_buffer);
     }
 
+    #[test]
+    fn test_query_applies_options_to_session_serializer() {
+        let connection = Connection::from_str("\0\0\0");
+        let mut query = Query::with_info("test".to_owned(), Client::new(connection));
+        let mut options = Options::from_str("method=xml").unwrap();
+        options.set_indent(false);
+
+        query.apply_options(&options).unwrap();
+
+        let actual_buffer = query.into_inner().into_inner().to_string();
+        assert_eq!("SET SERIALIZER method=xml,indent=no\u{0}", actual_buffer);
+    }
+
     #[test]
     fn test_query_fails_to_run_options_command_with_failing_stream() {
         let connection = Connection::failing();
@@ -605,7 +1667,7 @@ mod tests {
     #[test]
     fn test_query_runs_info_command() {
         let expected_response = QUERY_INFO;
-        let connection = Connection::from_str(&format!("{}\0\0", expected_response));
+        let connection = Connection::from_str(format!("{}\0\0", expected_response));
 
         let mut query = Query::with_info("test".to_owned(), Client::new(connection));
         let actual_response = query.info().unwrap();
@@ -619,6 +1681,39 @@ mod tests {
         assert_eq!(expected_buffer, actual_buffer);
     }
 
+    #[test]
+    fn test_query_fetches_info_with_query_info_turned_on_temporarily() {
+        let connection = Connection::from_str(format!("QUERYINFO: false\0\0\0\0\0\0{}\0\0\0\0\0", QUERY_INFO));
+
+        let mut query = Query::without_info("test".to_owned(), Client::new(connection));
+        let actual_response = query.fetch_info().unwrap();
+
+        assert_query_info!(actual_response);
+
+        let stream = query.into_inner().into_inner();
+        let actual_buffer = stream.to_string();
+        let expected_buffer = "GET QUERYINFO\0\
+            SET QUERYINFO true\0\
+            \u{6}test\0\
+            SET QUERYINFO false\0"
+            .to_owned();
+
+        assert_eq!(expected_buffer, actual_buffer);
+    }
+
+    #[test]
+    fn test_query_restores_query_info_when_fetching_info_fails() {
+        let connection = Connection::from_str("QUERYINFO: true\0\0\0\0\0\0Query not found.\0\u{1}\0\0\0");
+
+        let mut query = Query::without_info("test".to_owned(), Client::new(connection));
+        let actual_error = query.fetch_info().expect_err("Operation must fail");
+
+        assert!(matches!(actual_error, ClientError::CommandFailed { message } if message == "Query not found."));
+
+        let stream = query.into_inner().into_inner();
+        assert!(stream.to_string().ends_with("SET QUERYINFO true\0"));
+    }
+
     #[test]
     fn test_query_fails_to_run_info_command_with_failing_stream() {
         let connection = Connection::failing();
@@ -649,8 +1744,23 @@ mod tests {
         let connection = Connection::failing();
 
         let query = Query::with_info("test".to_owned(), Client::new(connection));
-        let actual_error = query.close().err().expect("Operation must fail");
+        let actual_error = query.close().expect_err("Operation must fail");
 
         assert!(matches!(actual_error, ClientError::Io(_)));
     }
+
+    #[test]
+    fn test_query_closes_on_drop() {
+        let client = Client::new(Connection::from_str("test_response\0"));
+        let observed = client.clone();
+
+        drop(Query::with_info("test".to_owned(), client));
+
+        assert_eq!("\u{2}test\u{0}", observed.into_inner().into_inner().to_string());
+    }
+
+    #[test]
+    fn test_dropping_query_ignores_failing_stream() {
+        drop(Query::with_info("test".to_owned(), Client::new(Connection::failing())));
+    }
 }
@@ -1,9 +1,14 @@
+use crate::connection::protocol::Unescaper;
 use crate::connection::Authenticated;
 use crate::errors::ClientError;
+#[cfg(feature = "encoding_rs")]
+use crate::query::serializer::decode;
 use crate::query::QueryFailed;
 use crate::{Client, Connection, DatabaseStream, Query, Result};
-use std::borrow::BorrowMut;
+use std::borrow::{Borrow, BorrowMut};
 use std::io::Read;
+use std::marker::PhantomData;
+use std::time::Instant;
 
 /// Response from a command. Depending on the command, it may or may not return UTF-8 string. Result is read using
 /// the [`Read`] trait.
@@ -34,35 +39,77 @@ use std::io::Read;
 /// ```
 ///
 /// [`Read`]: std::io::Read
-pub struct Response<T, HasInfo>
+///
+/// The response either owns the [`Query`] and gives it back on [`close`], or borrows it when created by
+/// [`Query::execute_keep`].
+///
+/// [`close`]: Response::close
+/// [`Query`]: crate::Query
+/// [`Query::execute_keep`]: crate::Query::execute_keep
+pub struct Response<T, HasInfo, Q = Query<T, HasInfo>>
 where
     T: DatabaseStream,
+    Q: BorrowMut<Query<T, HasInfo>>,
 {
-    query: Query<T, HasInfo>,
-    info_prefix: Option<Vec<u8>>,
-    info_complete: bool,
-    is_ok: bool,
+    phantom: PhantomData<(T, HasInfo)>,
+    query: Option<Q>,
+    unescaper: Unescaper,
     result_complete: bool,
+    started: Instant,
 }
 
-impl<T, HasInfo> Response<T, HasInfo>
+impl<T, HasInfo, Q> Response<T, HasInfo, Q>
 where
     T: DatabaseStream,
+    Q: BorrowMut<Query<T, HasInfo>>,
 {
-    pub(crate) fn new(query: Query<T, HasInfo>) -> Self {
+    pub(crate) fn new(query: Q, started: Instant) -> Self {
         Self {
-            query,
-            info_prefix: None,
-            info_complete: false,
-            is_ok: false,
+            phantom: PhantomData,
+            query: Some(query),
+            unescaper: Unescaper::new(),
             result_complete: false,
+            started,
         }
     }
 
+    /// Reads the rest of the result and decodes it from the `encoding`, for results not serialized in UTF-8. The
+    /// encoding the serializer uses is told by [`Options::encoding`].
+    ///
+    /// Fails with [`ClientError::Io`] of the [`InvalidData`] kind when the result is malformed in the `encoding`.
+    ///
+    /// # Example
+    /// ```
+    /// use basex::{Client, ClientError};
+    ///
+    /// # fn main() -> Result<(), ClientError> {
+    /// let client = Client::connect("localhost", 1984, "admin", "admin")?;
+    /// let mut query = client.query("'café'")?.without_info()?;
+    /// let mut options = query.options()?;
+    /// options.set_encoding("ISO-8859-1");
+    /// query.apply_options(&options)?;
+    ///
+    /// let mut response = query.execute()?;
+    /// let encoding = options.encoding().unwrap();
+    /// assert_eq!("café", response.read_to_string_with_encoding(encoding)?);
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// [`ClientError::Io`]: crate::ClientError::Io
+    /// [`InvalidData`]: std::io::ErrorKind::InvalidData
+    /// [`Options::encoding`]: crate::serializer::Options::encoding
+    #[cfg(feature = "encoding_rs")]
+    pub fn read_to_string_with_encoding(&mut self, encoding: &'static encoding_rs::Encoding) -> Result<String> {
+        let mut bytes = vec![];
+        self.read_to_end(&mut bytes)?;
+        decode(&bytes, encoding)
+    }
+
     /// Reads info and returns back client.
     ///
-    /// # Panics
-    /// Panics when the stream ends before result is fully streamed.
+    /// Fails with [`ClientError::Io`] of the [`UnexpectedEof`] kind when the stream ends before the result is fully
+    /// streamed.
     ///
     /// # Example
     /// ```
@@ -77,95 +124,82 @@ where
     /// # Ok(())
     /// # }
     /// ```
-    pub fn close(mut self) -> Result<Query<T, HasInfo>> {
-        let mut buf = [0u8; 4096];
+    ///
+    /// [`ClientError::Io`]: crate::ClientError::Io
+    /// [`UnexpectedEof`]: std::io::ErrorKind::UnexpectedEof
+    pub fn close(mut self) -> Result<Q> {
+        let outcome = self.end();
+        let query = self.query.take().expect("response is not closed");
 
-        while !self.result_complete && self.read(&mut buf)? > 0 {}
+        outcome.map(|_| query)
+    }
 
-        if !self.result_complete {
-            panic!("Unexpected end of stream.");
-        }
+    /// Finishes the response and reports its outcome to the observer of the client.
+    fn end(&mut self) -> Result<()> {
+        let outcome = self.finish();
+        let query: &Query<T, HasInfo> = self.query.as_ref().expect("response is not closed").borrow();
+        let client: &Client<T> = query.borrow();
+        client
+            .observer()
+            .on_query_end(query.id(), self.started.elapsed(), outcome.as_ref().map(|_| ()));
 
-        match self.is_ok {
-            true => Ok(self.query),
-            false => {
-                let info_suffix = if !self.info_complete {
-                    Some(self.connection().read_string()?)
-                } else {
-                    None
-                };
+        outcome
+    }
 
-                let mut info = String::from_utf8(self.info_prefix.unwrap_or_default())?;
+    /// Reads the rest of the result and the status, failing with the error message when the query has failed.
+    fn finish(&mut self) -> Result<()> {
+        let mut buf = [0u8; 4096];
 
-                if let Some(info_suffix) = info_suffix {
-                    info.push_str(info_suffix.as_str());
-                }
+        while self.read(&mut buf)? > 0 {}
 
-                Err(ClientError::QueryFailed(QueryFailed::new(info)))
-            }
+        match self.connection().is_ok()? {
+            true => Ok(()),
+            false => Err(ClientError::QueryFailed(QueryFailed::new(
+                self.connection().read_string()?,
+            ))),
         }
     }
 
     fn connection(&mut self) -> &mut Connection<T, Authenticated> {
-        let client: &mut Client<T> = self.query.borrow_mut();
+        let query: &mut Query<T, HasInfo> = self.query.as_mut().expect("response is not closed").borrow_mut();
+        let client: &mut Client<T> = query.borrow_mut();
         client.borrow_mut()
     }
 }
 
-impl<T, HasInfo> Read for Response<T, HasInfo>
+impl<T, HasInfo, Q> Drop for Response<T, HasInfo, Q>
 where
     T: DatabaseStream,
+    Q: BorrowMut<Query<T, HasInfo>>,
 {
+    /// Reads the rest of the response unless it was closed, ignoring any errors, so that the connection is left ready
+    /// for the next command. The outcome is still reported to the observer of the client.
+    fn drop(&mut self) {
+        if self.query.is_some() {
+            let _ = self.end();
+        }
+    }
+}
+
+impl<T, HasInfo, Q> Read for Response<T, HasInfo, Q>
+where
+    T: DatabaseStream,
+    Q: BorrowMut<Query<T, HasInfo>>,
+{
+    /// Reads the result without the escape bytes, leaving the status byte that follows it for [`close`].
+    ///
+    /// [`close`]: Response::close
     fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
         if self.result_complete {
             return Ok(0);
         }
 
-        let size = self.connection().read(buf)?;
-        let mut escape = false;
-        let mut shift = 0usize;
-        let mut position: Option<usize> = None;
-
-        for i in 0..size {
-            if buf[i] == 0xFF && !escape {
-                escape = true;
-                shift += 1;
-                continue;
-            }
-            if buf[i] == 0 && !escape {
-                position = Some(i);
-                break;
-            }
-
-            escape = false;
-            buf[i - shift] = buf[i];
-        }
-
-        if let Some(position) = position {
-            if size > position + 1 {
-                self.result_complete = true;
-                self.is_ok = match buf[..size][position + 1] {
-                    0 => true,
-                    1 => false,
-                    other => panic!("Invalid status byte \"{}\"", other),
-                };
-                if self.is_ok {
-                    self.info_complete = true;
-                } else {
-                    self.info_prefix = match buf[position + 2..size].iter().position(|&b| b == 0) {
-                        Some(length) => {
-                            self.info_complete = true;
-                            Some(buf[position + 2..position + 2 + length].to_vec())
-                        }
-                        None => Some(buf[position + 2..size].to_vec()),
-                    };
-                }
-            }
-
-            return Ok(position - shift);
-        }
+        let mut unescaper = std::mem::take(&mut self.unescaper);
+        let (size, terminated) = self.connection().read_escaped_into(buf, &mut unescaper)?;
+        self.unescaper = unescaper;
+        self.result_complete = terminated;
 
-        Ok(size - shift)
+        Ok(size)
     }
 }
 
@@ -173,14 +207,16 @@ where
 mod tests {
     use super::*;
     use crate::ClientError;
+    use std::io::ErrorKind;
+    use test_case::test_case;
 
     #[test]
     fn test_reading_result_from_response() {
-        let connection = Connection::from_str("result\0".to_owned());
+        let connection = Connection::from_str("result\0");
         let client = Client::new(connection);
 
         let query = Query::without_info("1".to_owned(), client);
-        let mut response = Response::new(query);
+        let mut response = Response::new(query, Instant::now());
         let mut actual_response = String::new();
         response.read_to_string(&mut actual_response).unwrap();
         let expected_response = "result".to_owned();
@@ -188,13 +224,41 @@ mod tests {
         assert_eq!(expected_response, actual_response);
     }
 
+    #[cfg(feature = "encoding_rs")]
+    #[test]
+    fn test_reading_latin_1_result_from_response() {
+        let connection = Connection::from_bytes(b"caf\xE9 cr\xE8me\0");
+        let query = Query::without_info("1".to_owned(), Client::new(connection));
+        let mut response = Response::new(query, Instant::now());
+
+        let actual_response = response
+            .read_to_string_with_encoding(encoding_rs::WINDOWS_1252)
+            .unwrap();
+
+        assert_eq!("café crème", actual_response);
+    }
+
+    #[cfg(feature = "encoding_rs")]
+    #[test]
+    fn test_reading_malformed_result_with_encoding_fails() {
+        let connection = Connection::from_bytes(b"caf\xE9\0");
+        let query = Query::without_info("1".to_owned(), Client::new(connection));
+        let mut response = Response::new(query, Instant::now());
+
+        let actual_error = response
+            .read_to_string_with_encoding(encoding_rs::UTF_8)
+            .expect_err("Operation must fail");
+
+        assert!(matches!(actual_error, ClientError::Io(e) if e.kind() == ErrorKind::InvalidData));
+    }
+
     #[test]
     fn test_reading_result_from_response_on_multiple_read_calls() {
         let connection = Connection::from_str("result".repeat(10) + "\0");
         let client = Client::new(connection);
 
         let query = Query::without_info("1".to_owned(), client);
-        let mut response = Response::new(query);
+        let mut response = Response::new(query, Instant::now());
         let mut actual_response = String::new();
         response.read_to_string(&mut actual_response).unwrap();
         let expected_response = "result".repeat(10).to_owned();
@@ -210,7 +274,7 @@ mod tests {
         let client = Client::new(connection);
 
         let query = Query::without_info("1".to_owned(), client);
-        let mut response = Response::new(query);
+        let mut response = Response::new(query, Instant::now());
         let mut actual_response: Vec<u8> = vec![];
         response.read_to_end(&mut actual_response).unwrap();
         let expected_response = vec![0u8, 1, 6, 9, 0xFF, 3];
@@ -228,7 +292,7 @@ mod tests {
         let client = Client::new(connection);
 
         let query = Query::without_info("1".to_owned(), client);
-        let mut response = Response::new(query);
+        let mut response = Response::new(query, Instant::now());
         let mut actual_response: Vec<u8> = vec![];
         response.read_to_end(&mut actual_response).unwrap();
         let expected_response = [0u8].repeat(10).to_vec();
@@ -245,7 +309,7 @@ mod tests {
         let client = Client::new(connection);
 
         let query = Query::without_info("1".to_owned(), client);
-        let response = Response::new(query);
+        let response = Response::new(query, Instant::now());
         let actual_error = response.close().err().unwrap();
 
         assert!(matches!(
@@ -260,7 +324,7 @@ mod tests {
         let connection = Connection::from_str(format!("partial_result\0\u{1}{}\0", expected_error));
         let client = Client::new(connection);
         let query = Query::without_info("1".to_owned(), client);
-        let response = Response::new(query);
+        let response = Response::new(query, Instant::now());
         let actual_error = response.close().err().unwrap();
 
         assert!(matches!(
@@ -270,22 +334,92 @@ mod tests {
     }
 
     #[test]
-    #[should_panic]
-    fn test_reading_panics_on_invalid_status_byte() {
-        let connection = Connection::from_str("partial_result\0\u{2}test_error\0".to_owned());
+    fn test_reading_fails_on_invalid_status_byte() {
+        let connection = Connection::from_str("partial_result\0\u{2}test_error\0");
         let client = Client::new(connection);
         let query = Query::without_info("1".to_owned(), client);
 
-        let _ = Response::new(query).read(&mut [0u8; 27]);
+        let actual_error = Response::new(query, Instant::now())
+            .close()
+            .expect_err("Operation must fail");
+
+        assert!(matches!(actual_error, ClientError::Protocol(_)));
     }
 
     #[test]
-    #[should_panic]
-    fn test_reading_panics_on_incomplete_result() {
-        let connection = Connection::from_str("partial_result".to_owned());
+    fn test_closing_fails_on_incomplete_result() {
+        let connection = Connection::from_str("partial_result");
         let client = Client::new(connection);
         let query = Query::without_info("1".to_owned(), client);
 
-        let _ = Response::new(query).close();
+        let actual_error = Response::new(query, Instant::now())
+            .close()
+            .expect_err("Operation must fail");
+
+        assert!(matches!(actual_error, ClientError::Io(e) if e.kind() == ErrorKind::UnexpectedEof));
+    }
+
+    #[test]
+    fn test_dropping_response_reads_rest_of_result_and_closes_query() {
+        let client = Client::new(Connection::from_str("partial_result\0\0"));
+        let observed = client.clone();
+
+        let query = Query::without_info("1".to_owned(), client);
+        let mut response = Response::new(query, Instant::now());
+        response.read_exact(&mut [0u8; 7]).unwrap();
+        drop(response);
+
+        assert_eq!("\u{2}1\u{0}", observed.into_inner().into_inner().to_string());
+    }
+
+    #[allow(clippy::unused_unit)]
+    #[test_case(1)]
+    #[test_case(2)]
+    #[test_case(4096)]
+    fn test_reading_result_with_escape_bytes_in_chunks(size: usize) {
+        let connection = Connection::from_bytes(&[0xFF, 0, 1, 0xFF, 0xFF, 0xFF, 0, 2, 0]);
+        let query = Query::without_info("1".to_owned(), Client::new(connection));
+        let mut response = Response::new(query, Instant::now());
+        let mut actual_response = vec![];
+        let mut buf = vec![0u8; size];
+
+        loop {
+            match response.read(&mut buf).unwrap() {
+                0 => break,
+                read => actual_response.extend_from_slice(&buf[..read]),
+            }
+        }
+
+        assert_eq!(vec![0u8, 1, 0xFF, 0, 2], actual_response);
+        response.close().expect("Operation must succeed.");
+    }
+
+    #[allow(clippy::unused_unit)]
+    #[test_case(1)]
+    #[test_case(2)]
+    fn test_reading_error_in_chunks(size: usize) {
+        let connection = Connection::from_str("partial_result\0\u{1}test_error");
+        let query = Query::without_info("1".to_owned(), Client::new(connection));
+        let mut response = Response::new(query, Instant::now());
+        let mut buf = vec![0u8; size];
+
+        while response.read(&mut buf).unwrap() > 0 {}
+        let actual_error = response.close().err().unwrap();
+
+        assert!(matches!(actual_error, ClientError::QueryFailed(q) if q.raw() == "test_error"));
+    }
+
+    #[test]
+    fn test_closing_leaves_next_response_unread() {
+        let connection = Connection::from_str("result\0\0next");
+        let query = Query::without_info("1".to_owned(), Client::new(connection));
+        let mut response = Response::new(query, Instant::now());
+        let mut actual_response = String::new();
+
+        response.read_to_string(&mut actual_response).unwrap();
+        let mut connection = response.close().unwrap().into_inner();
+
+        assert_eq!("result", actual_response);
+        assert_eq!("next", connection.read_string().unwrap());
     }
 }
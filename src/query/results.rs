@@ -0,0 +1,389 @@
+use crate::connection::Authenticated;
+use crate::errors::ClientError;
+use crate::query::QueryFailed;
+use crate::{Client, Connection, DatabaseStream, Query, Result};
+use std::borrow::BorrowMut;
+use std::fmt::{Display, Formatter};
+
+/// Type of a single result item, as [reported](https://docs.basex.org/wiki/Server_Protocol:_Types) by the server.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum XdmType {
+    Function,
+    Node,
+    Text,
+    ProcessingInstruction,
+    Element,
+    Document,
+    DocumentElement,
+    Attribute,
+    Comment,
+    Item,
+    Untyped,
+    AnyType,
+    AnySimpleType,
+    AnyAtomicType,
+    UntypedAtomic,
+    String,
+    NormalizedString,
+    Token,
+    Language,
+    NmToken,
+    Name,
+    NcName,
+    Id,
+    IdRef,
+    Entity,
+    Float,
+    Double,
+    Decimal,
+    PrecisionDecimal,
+    Integer,
+    NonPositiveInteger,
+    NegativeInteger,
+    Long,
+    Int,
+    Short,
+    Byte,
+    NonNegativeInteger,
+    UnsignedLong,
+    UnsignedInt,
+    UnsignedShort,
+    UnsignedByte,
+    PositiveInteger,
+    Duration,
+    YearMonthDuration,
+    DayTimeDuration,
+    DateTime,
+    DateTimeStamp,
+    Date,
+    Time,
+    GYearMonth,
+    GYear,
+    GMonthDay,
+    GDay,
+    GMonth,
+    Boolean,
+    Binary,
+    Base64Binary,
+    HexBinary,
+    AnyUri,
+    QName,
+    Notation,
+    /// Type code not known to this client.
+    Other(u8),
+}
+
+/// Codes and names of the known types.
+const TYPES: [(u8, XdmType, &str); 61] = [
+    (7, XdmType::Function, "function(*)"),
+    (8, XdmType::Node, "node()"),
+    (9, XdmType::Text, "text()"),
+    (10, XdmType::ProcessingInstruction, "processing-instruction()"),
+    (11, XdmType::Element, "element()"),
+    (12, XdmType::Document, "document-node()"),
+    (13, XdmType::DocumentElement, "document-node(element())"),
+    (14, XdmType::Attribute, "attribute()"),
+    (15, XdmType::Comment, "comment()"),
+    (32, XdmType::Item, "item()"),
+    (33, XdmType::Untyped, "xs:untyped"),
+    (34, XdmType::AnyType, "xs:anyType"),
+    (35, XdmType::AnySimpleType, "xs:anySimpleType"),
+    (36, XdmType::AnyAtomicType, "xs:anyAtomicType"),
+    (37, XdmType::UntypedAtomic, "xs:untypedAtomic"),
+    (38, XdmType::String, "xs:string"),
+    (39, XdmType::NormalizedString, "xs:normalizedString"),
+    (40, XdmType::Token, "xs:token"),
+    (41, XdmType::Language, "xs:language"),
+    (42, XdmType::NmToken, "xs:NMTOKEN"),
+    (43, XdmType::Name, "xs:Name"),
+    (44, XdmType::NcName, "xs:NCName"),
+    (45, XdmType::Id, "xs:ID"),
+    (46, XdmType::IdRef, "xs:IDREF"),
+    (47, XdmType::Entity, "xs:ENTITY"),
+    (48, XdmType::Float, "xs:float"),
+    (49, XdmType::Double, "xs:double"),
+    (50, XdmType::Decimal, "xs:decimal"),
+    (51, XdmType::PrecisionDecimal, "xs:precisionDecimal"),
+    (52, XdmType::Integer, "xs:integer"),
+    (53, XdmType::NonPositiveInteger, "xs:nonPositiveInteger"),
+    (54, XdmType::NegativeInteger, "xs:negativeInteger"),
+    (55, XdmType::Long, "xs:long"),
+    (56, XdmType::Int, "xs:int"),
+    (57, XdmType::Short, "xs:short"),
+    (58, XdmType::Byte, "xs:byte"),
+    (59, XdmType::NonNegativeInteger, "xs:nonNegativeInteger"),
+    (60, XdmType::UnsignedLong, "xs:unsignedLong"),
+    (61, XdmType::UnsignedInt, "xs:unsignedInt"),
+    (62, XdmType::UnsignedShort, "xs:unsignedShort"),
+    (63, XdmType::UnsignedByte, "xs:unsignedByte"),
+    (64, XdmType::PositiveInteger, "xs:positiveInteger"),
+    (65, XdmType::Duration, "xs:duration"),
+    (66, XdmType::YearMonthDuration, "xs:yearMonthDuration"),
+    (67, XdmType::DayTimeDuration, "xs:dayTimeDuration"),
+    (68, XdmType::DateTime, "xs:dateTime"),
+    (69, XdmType::DateTimeStamp, "xs:dateTimeStamp"),
+    (70, XdmType::Date, "xs:date"),
+    (71, XdmType::Time, "xs:time"),
+    (72, XdmType::GYearMonth, "xs:gYearMonth"),
+    (73, XdmType::GYear, "xs:gYear"),
+    (74, XdmType::GMonthDay, "xs:gMonthDay"),
+    (75, XdmType::GDay, "xs:gDay"),
+    (76, XdmType::GMonth, "xs:gMonth"),
+    (77, XdmType::Boolean, "xs:boolean"),
+    (78, XdmType::Binary, "basex:binary"),
+    (79, XdmType::Base64Binary, "xs:base64Binary"),
+    (80, XdmType::HexBinary, "xs:hexBinary"),
+    (81, XdmType::AnyUri, "xs:anyURI"),
+    (82, XdmType::QName, "xs:QName"),
+    (83, XdmType::Notation, "xs:NOTATION"),
+];
+
+impl XdmType {
+    /// Gets the type by the code the server sends it as.
+    pub fn from_code(code: u8) -> Self {
+        TYPES
+            .iter()
+            .find(|(c, _, _)| *c == code)
+            .map(|(_, xdm_type, _)| *xdm_type)
+            .unwrap_or(XdmType::Other(code))
+    }
+
+    /// The code the server sends this type as.
+    pub fn code(&self) -> u8 {
+        match self {
+            XdmType::Other(code) => *code,
+            xdm_type => TYPES.iter().find(|(_, t, _)| t == xdm_type).unwrap().0,
+        }
+    }
+}
+
+impl Display for XdmType {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            XdmType::Other(code) => write!(f, "unknown({})", code),
+            xdm_type => f.write_str(TYPES.iter().find(|(_, t, _)| t == xdm_type).unwrap().2),
+        }
+    }
+}
+
+/// Iterates over the result items of a [`Query`] one by one, along with their types.
+///
+/// Once all the items are read, the query fails with [`QueryFailed`] if it did. Call [`close`] to get the query back.
+/// Dropping the iterator reads the remaining items, so that the connection is left ready for the next command.
+///
+/// # Example
+/// ```
+/// # use basex::{Client, ClientError, XdmType};
+/// # fn main() -> Result<(), ClientError> {
+/// let client = Client::connect("localhost", 1984, "admin", "admin")?;
+/// let query = client.query("(1, 'two', <three/>)")?.without_info()?;
+/// let mut results = query.results()?;
+///
+/// let items = results.by_ref().collect::<Result<Vec<_>, _>>()?;
+/// assert_eq!((XdmType::Integer, b"1".to_vec()), items[0]);
+/// assert_eq!((XdmType::String, b"two".to_vec()), items[1]);
+/// assert_eq!((XdmType::Element, b"<three/>".to_vec()), items[2]);
+///
+/// results.close()?.close()?;
+/// # Ok(())
+/// # }
+/// ```
+///
+/// [`Query`]: crate::Query
+/// [`QueryFailed`]: crate::ClientError::QueryFailed
+/// [`close`]: self::ResultIter::close
+#[derive(Debug)]
+pub struct ResultIter<T, HasInfo>
+where
+    T: DatabaseStream,
+{
+    query: Option<Query<T, HasInfo>>,
+    finished: bool,
+}
+
+impl<T, HasInfo> ResultIter<T, HasInfo>
+where
+    T: DatabaseStream,
+{
+    pub(crate) fn new(query: Query<T, HasInfo>) -> Self {
+        Self {
+            query: Some(query),
+            finished: false,
+        }
+    }
+
+    /// Skips the remaining items and returns back the query.
+    pub fn close(mut self) -> Result<Query<T, HasInfo>> {
+        for item in self.by_ref() {
+            item?;
+        }
+        Ok(self.query.take().expect("results are not closed"))
+    }
+
+    fn read_item(&mut self) -> Result<Option<(XdmType, Vec<u8>)>> {
+        let connection = self.connection();
+
+        match connection.read_byte()? {
+            0 => match connection.is_ok()? {
+                true => Ok(None),
+                false => Err(ClientError::QueryFailed(QueryFailed::new(connection.read_string()?))),
+            },
+            code => Ok(Some((XdmType::from_code(code), connection.read_escaped()?))),
+        }
+    }
+
+    fn connection(&mut self) -> &mut Connection<T, Authenticated> {
+        let client: &mut Client<T> = self.query.as_mut().expect("results are not closed").borrow_mut();
+        client.borrow_mut()
+    }
+}
+
+impl<T, HasInfo> Drop for ResultIter<T, HasInfo>
+where
+    T: DatabaseStream,
+{
+    /// Reads the remaining items unless the iterator was closed, ignoring any errors, so that the connection is left
+    /// ready for the next command.
+    fn drop(&mut self) {
+        if self.query.is_some() {
+            for _ in self.by_ref() {}
+        }
+    }
+}
+
+impl<T, HasInfo> Iterator for ResultIter<T, HasInfo>
+where
+    T: DatabaseStream,
+{
+    type Item = Result<(XdmType, Vec<u8>)>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.finished {
+            return None;
+        }
+
+        let item = self.read_item();
+        self.finished = !matches!(item, Ok(Some(_)));
+        item.transpose()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::{spawn_server_replying, FailingStream};
+
+    #[test]
+    fn test_types_round_trip_through_codes() {
+        for (code, xdm_type, _) in TYPES.iter() {
+            assert_eq!(*xdm_type, XdmType::from_code(*code));
+            assert_eq!(*code, xdm_type.code());
+        }
+        assert_eq!(XdmType::Other(200), XdmType::from_code(200));
+        assert_eq!(200, XdmType::Other(200).code());
+    }
+
+    #[test]
+    fn test_types_format_as_names() {
+        assert_eq!("xs:string", XdmType::String.to_string());
+        assert_eq!("document-node(element())", XdmType::DocumentElement.to_string());
+        assert_eq!("unknown(200)", XdmType::Other(200).to_string());
+    }
+
+    #[test]
+    fn test_iterates_over_items() {
+        let connection = Connection::from_bytes(b"\x26two\0\x341\0\x0b<three/>\0\0\0");
+        let query = Query::without_info("test".to_owned(), Client::new(connection));
+
+        let mut results = query.results().unwrap();
+        let items = results.by_ref().collect::<Result<Vec<_>>>().unwrap();
+
+        assert_eq!(
+            vec![
+                (XdmType::String, b"two".to_vec()),
+                (XdmType::Integer, b"1".to_vec()),
+                (XdmType::Element, b"<three/>".to_vec()),
+            ],
+            items
+        );
+        assert!(results.next().is_none());
+        let query = results.close().unwrap();
+        assert_eq!("\u{4}test\u{0}", query.into_inner().into_inner().to_string());
+    }
+
+    #[test]
+    fn test_dropping_reads_remaining_items() {
+        let (address, server) =
+            spawn_server_replying(vec![(1, b"\x26one\0\x26two\0\0\0"), (1, b"\0\0"), (1, b"lunar\0\0\0")]);
+        let client = Client::connect("127.0.0.1", address.port(), "admin", "admin").unwrap();
+        let mut other = client.clone();
+        let query = Query::without_info("test".to_owned(), client);
+
+        let mut results = query.results().unwrap();
+        assert_eq!((XdmType::String, b"one".to_vec()), results.next().unwrap().unwrap());
+        drop(results);
+        let (result, _) = other.run_command("LIST").unwrap();
+        let _ = server.join().unwrap();
+
+        assert_eq!("lunar", result);
+    }
+
+    #[test]
+    fn test_unescapes_binary_items() {
+        let connection = Connection::from_bytes(&[78, 0xFF, 0, 1, 0xFF, 0xFF, 0, 0, 0]);
+        let query = Query::without_info("test".to_owned(), Client::new(connection));
+
+        let items = query.results().unwrap().collect::<Result<Vec<_>>>().unwrap();
+
+        assert_eq!(vec![(XdmType::Binary, vec![0, 1, 0xFF])], items);
+    }
+
+    #[test]
+    fn test_iterates_over_no_items() {
+        let connection = Connection::from_str("\0\0");
+        let query = Query::without_info("test".to_owned(), Client::new(connection));
+
+        let items = query.results().unwrap().collect::<Result<Vec<_>>>().unwrap();
+
+        assert!(items.is_empty());
+    }
+
+    #[test]
+    fn test_fails_with_query_error_after_items() {
+        let connection =
+            Connection::from_str("\u{26}one\0\0\u{1}Stopped at ., 1/1:\n[XPST0008] Undeclared variable: $x.\0");
+        let query = Query::without_info("test".to_owned(), Client::new(connection));
+
+        let mut results = query.results().unwrap();
+        assert_eq!((XdmType::String, b"one".to_vec()), results.next().unwrap().unwrap());
+        let actual_error = results.next().unwrap().expect_err("Operation must fail");
+
+        assert!(matches!(actual_error, ClientError::QueryFailed(q) if q.code() == "XPST0008"));
+        assert!(results.next().is_none());
+    }
+
+    #[test]
+    fn test_closing_fails_with_query_error() {
+        let connection = Connection::from_str("\0\u{1}Stopped at ., 1/1:\n[XPST0008] Undeclared variable: $x.\0");
+        let query = Query::without_info("test".to_owned(), Client::new(connection));
+
+        let actual_error = query.results().unwrap().close().expect_err("Operation must fail");
+
+        assert!(matches!(actual_error, ClientError::QueryFailed(_)));
+    }
+
+    #[test]
+    fn test_fails_to_iterate_with_failing_stream() {
+        let query = Query::without_info("test".to_owned(), Client::new(Connection::failing()));
+
+        let actual_error = query.results().expect_err("Operation must fail");
+
+        assert!(matches!(actual_error, ClientError::Io(_)));
+    }
+
+    #[test]
+    fn test_formats_as_debug() {
+        let query: Query<FailingStream> = Query::without_info("test".to_owned(), Client::new(Connection::failing()));
+        let _ = format!("{:?}", ResultIter::new(query));
+    }
+}
@@ -1,19 +1,41 @@
 use crate::{Client, DatabaseStream, Result};
-use std::collections::BTreeMap;
+#[cfg(feature = "encoding_rs")]
+use encoding_rs::Encoding;
 use std::error::Error;
 use std::fmt::{Display, Formatter};
+#[cfg(feature = "encoding_rs")]
+use std::io;
 use std::result;
 use std::str::FromStr;
 
+/// Decodes the `bytes` from the `encoding`, failing with [`ClientError::Io`] of the [`InvalidData`] kind when they are
+/// malformed in it.
+///
+/// [`ClientError::Io`]: crate::ClientError::Io
+/// [`InvalidData`]: std::io::ErrorKind::InvalidData
+#[cfg(feature = "encoding_rs")]
+pub(crate) fn decode(bytes: &[u8], encoding: &'static Encoding) -> Result<String> {
+    match encoding.decode_without_bom_handling_and_without_replacement(bytes) {
+        Some(decoded) => Ok(decoded.into_owned()),
+        None => Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("stream did not contain valid {}", encoding.name()),
+        )
+        .into()),
+    }
+}
+
 /// Error that have occurred when parsing the option's value.
 #[derive(Debug)]
 pub struct ParseError {
+    expected: &'static str,
     value: String,
 }
 
 impl ParseError {
-    fn new(value: &str) -> Self {
+    fn new(expected: &'static str, value: &str) -> Self {
         Self {
+            expected,
             value: value.to_owned(),
         }
     }
@@ -21,7 +43,7 @@ impl ParseError {
 
 impl Display for ParseError {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        f.write_str(&format!("expected boolean option, got: {}", self.value))
+        f.write_str(&format!("expected {} option, got: {}", self.expected, self.value))
     }
 }
 
@@ -29,6 +51,10 @@ impl Error for ParseError {}
 
 /// Options for query [serializer](https://docs.basex.org/wiki/Serialization).
 ///
+/// The options keep the order they were parsed or first set in, so that parsing and formatting them round-trips
+/// exactly. Options with the same entries in a different order are not equal. Commas in the values are doubled, as the
+/// server expects, so that nested options like [`CsvOptions`] survive the round-trip.
+///
 /// # Example
 ///
 /// ```
@@ -63,36 +89,156 @@ impl Error for ParseError {}
 /// ```
 #[derive(Debug, Clone, PartialEq)]
 pub struct Options {
-    options: BTreeMap<String, Attribute>,
+    options: Vec<(String, Attribute)>,
 }
 
 impl Options {
-    fn new(options: BTreeMap<String, Attribute>) -> Self {
+    fn new(options: Vec<(String, Attribute)>) -> Self {
         Self { options }
     }
 
     /// Gets mutable reference to an attribute if it exists.
     pub fn get(&self, key: &str) -> Option<&Attribute> {
-        self.options.get(key)
+        self.options.iter().find(|(k, _)| k == key).map(|(_, value)| value)
+    }
+
+    /// Gets the attribute converted to `A` if it exists, failing when its value does not represent `A`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use basex::serializer::{Options, ParseError};
+    /// # use std::str::FromStr;
+    /// # fn main() -> Result<(), ParseError> {
+    /// let options = Options::from_str("encoding=US-ASCII,indent=yes")?;
+    /// assert_eq!(Some(true), options.get_as::<bool>("indent")?);
+    /// assert_eq!(Some("US-ASCII".to_owned()), options.get_as::<String>("encoding")?);
+    /// assert_eq!(None, options.get_as::<bool>("method")?);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn get_as<A: FromAttribute>(&self, key: &str) -> result::Result<Option<A>, ParseError> {
+        self.get(key).map(A::from_attribute).transpose()
     }
 
-    /// Inserts new attribute value.
+    /// Inserts new attribute value. Replaces the value of an existing attribute in place, keeping its position.
     pub fn set(&mut self, key: &str, value: impl ToAttribute) -> &Attribute {
-        self.options.insert(key.to_owned(), value.to_attribute());
-        self.get(key).unwrap()
+        let index = match self.options.iter().position(|(k, _)| k == key) {
+            Some(index) => {
+                self.options[index].1 = value.to_attribute();
+                index
+            }
+            None => {
+                self.options.push((key.to_owned(), value.to_attribute()));
+                self.options.len() - 1
+            }
+        };
+        &self.options[index].1
+    }
+
+    /// Sets the serialization `method`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use basex::serializer::{Options, ParseError, SerializationMethod};
+    /// # use std::str::FromStr;
+    /// # fn main() -> Result<(), ParseError> {
+    /// let mut options = Options::from_str("")?;
+    /// options
+    ///     .set_method(SerializationMethod::Json)
+    ///     .set_indent(true)
+    ///     .set_encoding("UTF-8")
+    ///     .set_item_separator("|");
+    /// assert_eq!("method=json,indent=yes,encoding=UTF-8,item-separator=|", &options.to_string());
+    /// assert_eq!(Some(SerializationMethod::Json), options.get_as("method")?);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn set_method(&mut self, method: SerializationMethod) -> &mut Self {
+        self.set("method", method);
+        self
+    }
+
+    /// Sets whether the output gets indented.
+    pub fn set_indent(&mut self, indent: bool) -> &mut Self {
+        self.set("indent", indent);
+        self
+    }
+
+    /// Sets the `encoding` of the output.
+    pub fn set_encoding(&mut self, encoding: &str) -> &mut Self {
+        self.set("encoding", encoding);
+        self
+    }
+
+    /// Sets the `separator` written between the items of the output.
+    pub fn set_item_separator(&mut self, separator: &str) -> &mut Self {
+        self.set("item-separator", separator);
+        self
+    }
+
+    /// Sets the nested `csv` options used by the CSV serialization method.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use basex::serializer::{CsvFormat, CsvOptions, Options, ParseError, SerializationMethod};
+    /// # use std::str::FromStr;
+    /// # fn main() -> Result<(), ParseError> {
+    /// let mut options = Options::from_str("")?;
+    /// options.set_method(SerializationMethod::Csv).set_csv(CsvOptions {
+    ///     header: true,
+    ///     separator: ';',
+    ///     format: CsvFormat::Attributes,
+    /// });
+    /// assert_eq!(
+    ///     "method=csv,csv=header=yes,,separator=semicolon,,format=attributes",
+    ///     &options.to_string()
+    /// );
+    /// assert_eq!(Some(true), options.get_as::<CsvOptions>("csv")?.map(|csv| csv.header));
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn set_csv(&mut self, csv: CsvOptions) -> &mut Self {
+        self.set("csv", csv);
+        self
+    }
+
+    /// The encoding the output is serialized in, which is UTF-8 unless set otherwise. Returns `None` when the encoding
+    /// is not known.
+    ///
+    /// The encodings are looked up by the [WHATWG labels](https://encoding.spec.whatwg.org/#names-and-labels), which
+    /// e.g. treat `ISO-8859-1` as its superset `windows-1252`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use basex::serializer::{Options, ParseError};
+    /// # use std::str::FromStr;
+    /// # fn main() -> Result<(), ParseError> {
+    /// let options = Options::from_str("encoding=ISO-8859-1")?;
+    /// assert_eq!(Some(encoding_rs::WINDOWS_1252), options.encoding());
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg(feature = "encoding_rs")]
+    pub fn encoding(&self) -> Option<&'static Encoding> {
+        match self.get("encoding") {
+            Some(label) => Encoding::for_label(label.as_str().as_bytes()),
+            None => Some(encoding_rs::UTF_8),
+        }
     }
 
     /// Saves the options to the server serializer for current session.
     pub fn save<T: DatabaseStream>(&self, client: Client<T>) -> Result<Client<T>> {
-        let (client, _) = client
-            .execute(&format!("SET SERIALIZER {}", self.to_string()))?
-            .close()?;
+        let (client, _) = client.execute(&format!("SET SERIALIZER {}", self))?.close()?;
         Ok(client)
     }
 }
 
-impl ToString for Options {
-    fn to_string(&self) -> String {
+impl Display for Options {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         let mut str = String::new();
         for (key, value) in self.options.iter() {
             if !str.is_empty() {
@@ -100,9 +246,9 @@ impl ToString for Options {
             }
             str.push_str(key);
             str.push('=');
-            str.push_str(&value.to_string());
+            str.push_str(&value.as_str().replace(',', ",,"));
         }
-        str
+        f.write_str(&str)
     }
 }
 
@@ -110,16 +256,17 @@ impl FromStr for Options {
     type Err = ParseError;
 
     fn from_str(s: &str) -> result::Result<Self, Self::Err> {
-        let mut options: BTreeMap<String, Attribute> = BTreeMap::new();
+        let mut options = Options::new(vec![]);
         let mut tuple = (String::new(), String::new());
         let mut key_complete = false;
-        for x in s.chars() {
-            if x == '=' {
+        let mut chars = s.chars().peekable();
+        while let Some(x) = chars.next() {
+            if x == '=' && !key_complete {
                 key_complete = true;
                 continue;
             }
-            if x == ',' {
-                options.insert(tuple.0.to_owned(), Attribute::from_str(&tuple.1)?);
+            if x == ',' && chars.next_if_eq(&',').is_none() {
+                options.set(&tuple.0, tuple.1.as_str());
                 tuple.0.clear();
                 tuple.1.clear();
                 key_complete = false;
@@ -132,10 +279,44 @@ impl FromStr for Options {
             }
         }
         if !tuple.0.is_empty() {
-            options.insert(tuple.0.to_owned(), Attribute::from_str(&tuple.1)?);
+            options.set(&tuple.0, tuple.1.as_str());
         }
 
-        Ok(Options::new(options))
+        Ok(options)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for Options {
+    /// Serializes as a flat map of the option names to their values, in order.
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> result::Result<S::Ok, S::Error> {
+        serializer.collect_map(self.options.iter().map(|(key, value)| (key, value.as_str())))
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for Options {
+    /// Deserializes from a flat map of the option names to their values, keeping the order of the map.
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> result::Result<Self, D::Error> {
+        struct OptionsVisitor;
+
+        impl<'de> serde::de::Visitor<'de> for OptionsVisitor {
+            type Value = Options;
+
+            fn expecting(&self, f: &mut Formatter) -> std::fmt::Result {
+                f.write_str("a map of serializer options")
+            }
+
+            fn visit_map<A: serde::de::MapAccess<'de>>(self, mut map: A) -> result::Result<Options, A::Error> {
+                let mut options = Options::new(vec![]);
+                while let Some((key, value)) = map.next_entry::<String, String>()? {
+                    options.set(&key, value.as_str());
+                }
+                Ok(options)
+            }
+        }
+
+        deserializer.deserialize_map(OptionsVisitor)
     }
 }
 
@@ -155,6 +336,222 @@ impl ToAttribute for &str {
     }
 }
 
+/// Output [method](https://docs.basex.org/wiki/Serialization#Parameters) of the serializer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SerializationMethod {
+    Xml,
+    Xhtml,
+    Html,
+    Text,
+    Json,
+    Csv,
+    Adaptive,
+    BaseX,
+}
+
+impl Display for SerializationMethod {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            SerializationMethod::Xml => "xml",
+            SerializationMethod::Xhtml => "xhtml",
+            SerializationMethod::Html => "html",
+            SerializationMethod::Text => "text",
+            SerializationMethod::Json => "json",
+            SerializationMethod::Csv => "csv",
+            SerializationMethod::Adaptive => "adaptive",
+            SerializationMethod::BaseX => "basex",
+        })
+    }
+}
+
+impl FromStr for SerializationMethod {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> result::Result<Self, Self::Err> {
+        match s {
+            "xml" => Ok(SerializationMethod::Xml),
+            "xhtml" => Ok(SerializationMethod::Xhtml),
+            "html" => Ok(SerializationMethod::Html),
+            "text" => Ok(SerializationMethod::Text),
+            "json" => Ok(SerializationMethod::Json),
+            "csv" => Ok(SerializationMethod::Csv),
+            "adaptive" => Ok(SerializationMethod::Adaptive),
+            "basex" => Ok(SerializationMethod::BaseX),
+            _ => Err(ParseError::new("serialization method", s)),
+        }
+    }
+}
+
+impl ToAttribute for SerializationMethod {
+    fn to_attribute(&self) -> Attribute {
+        Attribute::from_str(&self.to_string()).unwrap()
+    }
+}
+
+impl FromAttribute for SerializationMethod {
+    fn from_attribute(attribute: &Attribute) -> result::Result<Self, ParseError> {
+        SerializationMethod::from_str(attribute.as_str())
+    }
+}
+
+/// Shape of the XML the CSV serializer expects, as described by the
+/// [CSV module](https://docs.basex.org/wiki/CSV_Module#Conversion).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CsvFormat {
+    /// Fields are elements named after the header, or `entry` without one.
+    Direct,
+    /// Fields are `entry` elements with the header names in `name` attributes.
+    Attributes,
+}
+
+impl Display for CsvFormat {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            CsvFormat::Direct => "direct",
+            CsvFormat::Attributes => "attributes",
+        })
+    }
+}
+
+impl FromStr for CsvFormat {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> result::Result<Self, Self::Err> {
+        match s {
+            "direct" => Ok(CsvFormat::Direct),
+            "attributes" => Ok(CsvFormat::Attributes),
+            _ => Err(ParseError::new("CSV format", s)),
+        }
+    }
+}
+
+/// Nested [options](https://docs.basex.org/wiki/CSV_Module#Options) of the CSV serialization method, formatted as a
+/// parameter string like `header=yes,separator=semicolon`.
+///
+/// The options missing when parsing take the server defaults, which are also the [`Default`] ones: no header, comma
+/// separator and direct format.
+///
+/// # Example
+///
+/// ```
+/// # use basex::serializer::{CsvFormat, CsvOptions, ParseError};
+/// # use std::str::FromStr;
+/// # fn main() -> Result<(), ParseError> {
+/// let csv = CsvOptions::from_str("header=yes,separator=tab")?;
+/// assert!(csv.header);
+/// assert_eq!('\t', csv.separator);
+/// assert_eq!(CsvFormat::Direct, csv.format);
+/// assert_eq!("header=yes,separator=tab,format=direct", &csv.to_string());
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct CsvOptions {
+    /// Whether the first line holds the names of the fields.
+    pub header: bool,
+    /// Character separating the fields.
+    pub separator: char,
+    /// Shape of the serialized XML.
+    pub format: CsvFormat,
+}
+
+impl Default for CsvOptions {
+    fn default() -> Self {
+        Self {
+            header: false,
+            separator: ',',
+            format: CsvFormat::Direct,
+        }
+    }
+}
+
+impl Display for CsvOptions {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let separator = match self.separator {
+            ',' => "comma".to_owned(),
+            ';' => "semicolon".to_owned(),
+            ':' => "colon".to_owned(),
+            '\t' => "tab".to_owned(),
+            ' ' => "space".to_owned(),
+            separator => separator.to_string(),
+        };
+        let mut options = Options::new(vec![]);
+        options.set("header", self.header);
+        options.set("separator", separator.as_str());
+        options.set("format", self.format.to_string().as_str());
+        write!(f, "{}", options)
+    }
+}
+
+impl FromStr for CsvOptions {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> result::Result<Self, Self::Err> {
+        let options = Options::from_str(s)?;
+        let mut csv = CsvOptions::default();
+        if let Some(header) = options.get_as("header")? {
+            csv.header = header;
+        }
+        if let Some(separator) = options.get_as::<String>("separator")? {
+            csv.separator = match separator.as_str() {
+                "comma" => ',',
+                "semicolon" => ';',
+                "colon" => ':',
+                "tab" => '\t',
+                "space" => ' ',
+                _ => {
+                    let mut chars = separator.chars();
+                    match (chars.next(), chars.next()) {
+                        (Some(separator), None) => separator,
+                        _ => return Err(ParseError::new("CSV separator", &separator)),
+                    }
+                }
+            };
+        }
+        if let Some(format) = options.get_as::<String>("format")? {
+            csv.format = CsvFormat::from_str(&format)?;
+        }
+
+        Ok(csv)
+    }
+}
+
+impl ToAttribute for CsvOptions {
+    fn to_attribute(&self) -> Attribute {
+        Attribute::from_str(&self.to_string()).unwrap()
+    }
+}
+
+impl FromAttribute for CsvOptions {
+    fn from_attribute(attribute: &Attribute) -> result::Result<Self, ParseError> {
+        CsvOptions::from_str(attribute.as_str())
+    }
+}
+
+/// Makes this type able to be read from an [`Attribute`], the inverse of [`ToAttribute`].
+///
+/// [`Attribute`]: self::Attribute
+/// [`ToAttribute`]: self::ToAttribute
+pub trait FromAttribute: Sized {
+    fn from_attribute(attribute: &Attribute) -> result::Result<Self, ParseError>;
+}
+
+impl FromAttribute for bool {
+    fn from_attribute(attribute: &Attribute) -> result::Result<Self, ParseError> {
+        match attribute.inner.as_str() {
+            "yes" => Ok(true),
+            "no" => Ok(false),
+            _ => Err(ParseError::new("boolean", &attribute.inner)),
+        }
+    }
+}
+
+impl FromAttribute for String {
+    fn from_attribute(attribute: &Attribute) -> result::Result<Self, ParseError> {
+        Ok(attribute.inner.clone())
+    }
+}
+
 /// Attribute of the serializer.
 #[derive(Debug, Clone, PartialEq)]
 pub struct Attribute {
@@ -169,11 +566,7 @@ impl Attribute {
 
     /// Returns this attribute as boolean.
     pub fn as_bool(&self) -> result::Result<bool, ParseError> {
-        match self.inner.as_str() {
-            "yes" => Ok(true),
-            "no" => Ok(false),
-            _ => Err(ParseError::new(&self.inner)),
-        }
+        bool::from_attribute(self)
     }
 }
 
@@ -185,9 +578,9 @@ impl FromStr for Attribute {
     }
 }
 
-impl ToString for Attribute {
-    fn to_string(&self) -> String {
-        self.inner.clone()
+impl Display for Attribute {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.inner)
     }
 }
 
@@ -203,6 +596,30 @@ mod tests {
         Ok(())
     }
 
+    #[cfg(feature = "encoding_rs")]
+    #[test]
+    fn test_encoding_defaults_to_utf_8() {
+        let options = Options::from_str("indent=yes").unwrap();
+
+        assert_eq!(Some(encoding_rs::UTF_8), options.encoding());
+    }
+
+    #[cfg(feature = "encoding_rs")]
+    #[test]
+    fn test_unknown_encoding_is_none() {
+        let options = Options::from_str("encoding=Klingon").unwrap();
+
+        assert_eq!(None, options.encoding());
+    }
+
+    #[cfg(feature = "encoding_rs")]
+    #[test]
+    fn test_decoding_fails_on_malformed_bytes() {
+        let actual_error = decode(&[0xD8, 0x00], encoding_rs::UTF_16BE).expect_err("Decoding must fail");
+
+        assert!(matches!(actual_error, crate::ClientError::Io(e) if e.kind() == io::ErrorKind::InvalidData));
+    }
+
     #[test]
     fn test_true_attribute_as_bool_is_true() {
         assert!(true.to_attribute().as_bool().unwrap());
@@ -223,22 +640,35 @@ mod tests {
 
     #[test]
     fn test_parse_error_formats_as_debug() {
-        format!("{:?}", ParseError::new("test"));
+        let _ = format!("{:?}", ParseError::new("boolean", "test"));
     }
 
     #[test]
     fn test_parse_error_formats_as_empty() {
-        format!("{}", ParseError::new("test"));
+        let _ = format!("{}", ParseError::new("boolean", "test"));
     }
 
     #[test]
     fn test_options_formats_as_debug() {
-        format!("{:?}", Options::new(BTreeMap::new()));
+        let _ = format!("{:?}", Options::new(vec![]));
     }
 
     #[test]
     fn test_attribute_formats_as_debug() {
-        format!("{:?}", Attribute::from_str("").unwrap());
+        let _ = format!("{:?}", Attribute::from_str("").unwrap());
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_options_round_trip_through_json() -> result::Result<(), ParseError> {
+        let expected_options = Options::from_str("indent=yes,encoding=US-ASCII")?;
+
+        let json = serde_json::to_string(&expected_options).unwrap();
+        assert_eq!(r#"{"indent":"yes","encoding":"US-ASCII"}"#, json);
+
+        let actual_options: Options = serde_json::from_str(&json).unwrap();
+        assert_eq!(expected_options, actual_options);
+        Ok(())
     }
 
     #[test]
@@ -246,7 +676,22 @@ mod tests {
         let mut options = Options::from_str("").unwrap();
         options.set("indent", false);
         options.set("encoding", "UTF-8");
-        assert_eq!("encoding=UTF-8,indent=no", &options.to_string());
+        assert_eq!("indent=no,encoding=UTF-8", &options.to_string());
+    }
+
+    #[test]
+    fn test_options_round_trip_in_original_order() -> result::Result<(), ParseError> {
+        let expected = "method=json,indent=yes,encoding=UTF-8,item-separator=|";
+
+        let mut options = Options::from_str(expected)?;
+        assert_eq!(expected, &options.to_string());
+
+        options.set("indent", false);
+        assert_eq!(
+            "method=json,indent=no,encoding=UTF-8,item-separator=|",
+            &options.to_string()
+        );
+        Ok(())
     }
 
     #[test]
@@ -257,6 +702,124 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_attributes_can_be_read_from_options_as_types() -> result::Result<(), ParseError> {
+        let options = Options::from_str("encoding=UTF-8,indent=yes")?;
+        assert_eq!(Some(true), options.get_as::<bool>("indent")?);
+        assert_eq!(Some("UTF-8".to_owned()), options.get_as::<String>("encoding")?);
+        assert_eq!(None, options.get_as::<String>("method")?);
+        Ok(())
+    }
+
+    #[test]
+    fn test_reading_attribute_as_wrong_type_fails() {
+        let options = Options::from_str("encoding=UTF-8").unwrap();
+        options.get_as::<bool>("encoding").expect_err("Parsing must fail");
+    }
+
+    #[test]
+    fn test_typed_setters_change_options() -> result::Result<(), ParseError> {
+        let mut options = Options::from_str("method=xml")?;
+        options
+            .set_method(SerializationMethod::Adaptive)
+            .set_indent(false)
+            .set_encoding("US-ASCII")
+            .set_item_separator(" ");
+
+        assert_eq!(
+            "method=adaptive,indent=no,encoding=US-ASCII,item-separator= ",
+            &options.to_string()
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_serialization_methods_round_trip() {
+        let methods = [
+            SerializationMethod::Xml,
+            SerializationMethod::Xhtml,
+            SerializationMethod::Html,
+            SerializationMethod::Text,
+            SerializationMethod::Json,
+            SerializationMethod::Csv,
+            SerializationMethod::Adaptive,
+            SerializationMethod::BaseX,
+        ];
+
+        for method in methods.iter() {
+            let options = Options::from_str(&format!("method={}", method)).unwrap();
+            assert_eq!(Some(*method), options.get_as("method").unwrap());
+        }
+    }
+
+    #[test]
+    fn test_unknown_serialization_method_fails_to_parse() {
+        SerializationMethod::from_str("yaml").expect_err("Parsing must fail");
+    }
+
+    #[test]
+    fn test_options_round_trip_doubled_commas() -> result::Result<(), ParseError> {
+        let expected = "item-separator=,,,indent=yes";
+
+        let options = Options::from_str(expected)?;
+        assert_eq!(Some(",".to_owned()), options.get_as("item-separator")?);
+        assert_eq!(Some(true), options.get_as("indent")?);
+        assert_eq!(expected, &options.to_string());
+        Ok(())
+    }
+
+    #[test]
+    fn test_csv_options_round_trip_through_options() -> result::Result<(), ParseError> {
+        let expected_csv = CsvOptions {
+            header: true,
+            separator: ';',
+            format: CsvFormat::Attributes,
+        };
+        let mut options = Options::from_str("method=csv")?;
+        options.set_csv(expected_csv);
+
+        let formatted = options.to_string();
+        assert_eq!(
+            "method=csv,csv=header=yes,,separator=semicolon,,format=attributes",
+            &formatted
+        );
+
+        let options = Options::from_str(&formatted)?;
+        assert_eq!(Some(SerializationMethod::Csv), options.get_as("method")?);
+        assert_eq!(Some(expected_csv), options.get_as("csv")?);
+        Ok(())
+    }
+
+    #[test]
+    fn test_csv_options_round_trip_separators() {
+        for separator in [',', ';', ':', '\t', ' ', '|'].iter() {
+            let expected_csv = CsvOptions {
+                separator: *separator,
+                ..CsvOptions::default()
+            };
+
+            let actual_csv = CsvOptions::from_str(&expected_csv.to_string()).unwrap();
+            assert_eq!(expected_csv, actual_csv);
+        }
+    }
+
+    #[test]
+    fn test_csv_options_default_missing_options() -> result::Result<(), ParseError> {
+        assert_eq!(CsvOptions::default(), CsvOptions::from_str("")?);
+        assert_eq!(
+            "header=no,separator=comma,format=direct",
+            &CsvOptions::default().to_string()
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_csv_options_fail_to_parse_invalid_values() {
+        CsvOptions::from_str("separator=||").expect_err("Parsing must fail");
+        CsvOptions::from_str("format=xquery").expect_err("Parsing must fail");
+        CsvOptions::from_str("header=maybe").expect_err("Parsing must fail");
+    }
+
     #[test]
     fn test_changing_value_changes_options() -> result::Result<(), ParseError> {
         let mut options = Options::from_str("encoding=US-ASCII,indent=yes")?;
@@ -1,14 +1,27 @@
-use std::io::Read;
+use std::fs::File;
+use std::io::{Cursor, Read, Result};
+use std::path::{Path, PathBuf};
 
 pub trait AsResource<'a> {
     type Reader: Read;
 
     fn into_read(self) -> Self::Reader;
+
+    /// Converts into the reader, failing when the resource cannot be read at all, such as a file that does not exist.
+    /// Called before a command is sent, so that such a failure leaves nothing half-sent on the connection.
+    fn try_into_read(self) -> Result<Self::Reader>
+    where
+        Self: Sized,
+    {
+        Ok(self.into_read())
+    }
 }
 
+/// Streams the reader as it is read, without buffering it whole, so that e.g. `&mut BufReader<File>`, `&mut dyn Read`
+/// or `&mut dyn BufRead` can be sent regardless of their size.
 impl<'a, T> AsResource<'a> for &'a mut T
 where
-    T: Read,
+    T: Read + ?Sized,
 {
     type Reader = &'a mut T;
 
@@ -24,3 +37,132 @@ impl<'a> AsResource<'a> for &'a str {
         self.as_bytes()
     }
 }
+
+impl<'a> AsResource<'a> for Vec<u8> {
+    type Reader = Cursor<Vec<u8>>;
+
+    fn into_read(self) -> Self::Reader {
+        Cursor::new(self)
+    }
+}
+
+impl<'a> AsResource<'a> for &'a Vec<u8> {
+    type Reader = &'a [u8];
+
+    fn into_read(self) -> Self::Reader {
+        self.as_slice()
+    }
+}
+
+#[cfg(feature = "bytes")]
+impl<'a> AsResource<'a> for bytes::Bytes {
+    type Reader = bytes::buf::Reader<bytes::Bytes>;
+
+    fn into_read(self) -> Self::Reader {
+        bytes::Buf::reader(self)
+    }
+}
+
+impl<'a> AsResource<'a> for &'a Path {
+    type Reader = FileReader;
+
+    fn into_read(self) -> Self::Reader {
+        FileReader::new(self.to_path_buf())
+    }
+
+    fn try_into_read(self) -> Result<Self::Reader> {
+        FileReader::open(self.to_path_buf())
+    }
+}
+
+impl<'a> AsResource<'a> for PathBuf {
+    type Reader = FileReader;
+
+    fn into_read(self) -> Self::Reader {
+        FileReader::new(self)
+    }
+
+    fn try_into_read(self) -> Result<Self::Reader> {
+        FileReader::open(self)
+    }
+}
+
+/// Reads a file that is either opened upfront by [`AsResource::try_into_read`], or on the first read, in which case a
+/// failure to open it surfaces as a read error while the resource is being sent.
+#[derive(Debug)]
+pub struct FileReader {
+    path: PathBuf,
+    file: Option<File>,
+}
+
+impl FileReader {
+    fn new(path: PathBuf) -> Self {
+        Self { path, file: None }
+    }
+
+    fn open(path: PathBuf) -> Result<Self> {
+        let file = File::open(&path)?;
+        Ok(Self { path, file: Some(file) })
+    }
+}
+
+impl Read for FileReader {
+    fn read(&mut self, buf: &mut [u8]) -> Result<usize> {
+        let file = match self.file {
+            Some(ref mut file) => file,
+            None => self.file.insert(File::open(&self.path)?),
+        };
+
+        file.read(buf)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use std::io::{BufRead, BufReader, ErrorKind};
+
+    #[test]
+    fn test_dyn_buf_read_is_read_through() {
+        let mut reader = BufReader::new(&b"<wojak><pink_index>69</pink_index></wojak>"[..]);
+        let reader: &mut dyn BufRead = &mut reader;
+
+        let mut actual = String::new();
+        reader.into_read().read_to_string(&mut actual).unwrap();
+
+        assert_eq!("<wojak><pink_index>69</pink_index></wojak>", actual);
+    }
+
+    #[test]
+    fn test_path_is_read_from_file() {
+        let path = std::env::temp_dir().join(format!("basex-resource-{}.xml", std::process::id()));
+        fs::write(&path, "<wojak><pink_index>69</pink_index></wojak>").unwrap();
+
+        let mut actual = String::new();
+        let result = path.as_path().into_read().read_to_string(&mut actual);
+        fs::remove_file(&path).unwrap();
+
+        result.unwrap();
+        assert_eq!("<wojak><pink_index>69</pink_index></wojak>", actual);
+    }
+
+    #[test]
+    fn test_path_fails_to_read_when_file_does_not_exist() {
+        let path = std::env::temp_dir().join("basex-resource-that-does-not-exist.xml");
+        let mut reader = path.into_read();
+
+        let actual_error = reader.read(&mut [0u8; 8]).expect_err("Operation must fail");
+
+        assert_eq!(ErrorKind::NotFound, actual_error.kind());
+    }
+
+    #[test]
+    fn test_path_fails_to_convert_when_file_does_not_exist() {
+        let path = std::env::temp_dir().join("basex-resource-that-does-not-exist.xml");
+
+        let actual_error = path.try_into_read().expect_err("Operation must fail");
+
+        assert_eq!(ErrorKind::NotFound, actual_error.kind());
+    }
+}
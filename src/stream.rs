@@ -1,6 +1,10 @@
 use crate::Result;
+use std::fmt::{Debug, Formatter};
 use std::io::{Read, Write};
 use std::net::TcpStream;
+#[cfg(unix)]
+use std::os::unix::net::UnixStream;
+use std::sync::{Arc, Mutex};
 
 /// Represents a stream usable for BaseX database [`Connection`].
 ///
@@ -21,3 +25,152 @@ impl DatabaseStream for TcpStream {
         Ok(TcpStream::try_clone(self)?)
     }
 }
+
+#[cfg(unix)]
+impl DatabaseStream for UnixStream {
+    fn try_clone(&self) -> Result<Self> {
+        Ok(UnixStream::try_clone(self)?)
+    }
+}
+
+/// Stream that mirrors all the bytes read from and written to the `inner` stream into a log, e.g. to diagnose a
+/// protocol desync. Meant purely for debugging, as the log also captures the credentials digest and all the data.
+///
+/// Each chunk is logged as one line of hexadecimal bytes, starting with `>` when sent to the server and `<` when
+/// received from it. Instances created by [`try_clone`] share the same log. Failing to write the log never fails the
+/// stream.
+///
+/// # Example
+/// ```no_run
+/// # use basex::{Client, ClientError};
+/// # use std::fs::File;
+/// # fn main() -> Result<(), ClientError> {
+/// let log = File::create("basex.log")?;
+/// let mut client = Client::connect_with_tee("localhost", 1984, "admin", "admin", log)?;
+/// let databases = client.list()?;
+/// # Ok(())
+/// # }
+/// ```
+///
+/// [`try_clone`]: crate::DatabaseStream::try_clone
+pub struct TeeStream<S, W>
+where
+    S: DatabaseStream,
+    W: Write,
+{
+    inner: S,
+    log: Arc<Mutex<W>>,
+}
+
+impl<S, W> TeeStream<S, W>
+where
+    S: DatabaseStream,
+    W: Write,
+{
+    /// Wraps the `inner` stream, mirroring its traffic into the `log`.
+    pub fn new(inner: S, log: W) -> Self {
+        Self::shared(inner, Arc::new(Mutex::new(log)))
+    }
+
+    /// Wraps the `inner` stream, mirroring its traffic into the `log` shared with other streams, e.g. the ones dialed
+    /// when reconnecting.
+    pub(crate) fn shared(inner: S, log: Arc<Mutex<W>>) -> Self {
+        Self { inner, log }
+    }
+
+    fn record(&self, direction: char, bytes: &[u8]) {
+        if bytes.is_empty() {
+            return;
+        }
+        let mut line = String::with_capacity(bytes.len() * 2 + 3);
+        line.push(direction);
+        line.push(' ');
+        for byte in bytes {
+            line.push_str(&format!("{:02x}", byte));
+        }
+        line.push('\n');
+
+        if let Ok(mut log) = self.log.lock() {
+            let _ = log.write_all(line.as_bytes());
+        }
+    }
+}
+
+impl<S, W> Debug for TeeStream<S, W>
+where
+    S: DatabaseStream + Debug,
+    W: Write,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("TeeStream")
+            .field("inner", &self.inner)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<S, W> Read for TeeStream<S, W>
+where
+    S: DatabaseStream,
+    W: Write,
+{
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        let size = self.inner.read(buf)?;
+        self.record('<', &buf[..size]);
+        Ok(size)
+    }
+}
+
+impl<S, W> Write for TeeStream<S, W>
+where
+    S: DatabaseStream,
+    W: Write,
+{
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        let size = self.inner.write(buf)?;
+        self.record('>', &buf[..size]);
+        Ok(size)
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        self.inner.flush()?;
+        if let Ok(mut log) = self.log.lock() {
+            let _ = log.flush();
+        }
+        Ok(())
+    }
+}
+
+impl<S, W> DatabaseStream for TeeStream<S, W>
+where
+    S: DatabaseStream,
+    W: Write,
+{
+    fn try_clone(&self) -> Result<Self> {
+        Ok(Self {
+            inner: self.inner.try_clone()?,
+            log: Arc::clone(&self.log),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::MockStream;
+    use crate::Connection;
+
+    #[test]
+    fn test_logs_both_directions() {
+        let mut log = vec![];
+        {
+            let stream = TeeStream::new(MockStream::new("BaseX:19501915960728\0".to_owned()), &mut log);
+            let _ = Connection::new(stream).authenticate("admin", "admin").unwrap();
+        }
+
+        let expected_log = "\
+< 42617365583a31393530313931353936303732380000
+> 61646d696e00616631336232306166306530623065333531376134303663343236323264336400
+";
+        assert_eq!(expected_log, String::from_utf8(log).unwrap());
+    }
+}
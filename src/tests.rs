@@ -1,35 +1,118 @@
 use super::*;
 use circbuf::CircBuf;
 use std::cell::RefCell;
+use std::fmt::{Display, Formatter};
 use std::io::{copy, Read, Write};
+use std::net::{SocketAddr, TcpListener, TcpStream};
+#[cfg(unix)]
+use std::os::unix::net::{UnixListener, UnixStream};
 use std::rc::Rc;
+use std::thread;
+use std::thread::JoinHandle;
+
+/// Spawns a server that authenticates a single `admin` client and hands back its end of the socket without
+/// responding to anything else.
+pub(crate) fn spawn_server() -> (SocketAddr, JoinHandle<TcpStream>) {
+    spawn_server_replying(vec![])
+}
+
+/// Spawns a server that authenticates a single `admin` client and then, for each exchange, waits for the given number
+/// of argument terminators and writes the reply. Hands back its end of the socket once all replies are written.
+pub(crate) fn spawn_server_replying(exchanges: Vec<(usize, &'static [u8])>) -> (SocketAddr, JoinHandle<TcpStream>) {
+    spawn_server_at("127.0.0.1:0", exchanges)
+}
+
+/// Spawns a server listening on the `bind` address that behaves like [`spawn_server_replying`].
+pub(crate) fn spawn_server_at(
+    bind: &str,
+    exchanges: Vec<(usize, &'static [u8])>,
+) -> (SocketAddr, JoinHandle<TcpStream>) {
+    let listener = TcpListener::bind(bind).unwrap();
+    let address = listener.local_addr().unwrap();
+    let server = thread::spawn(move || {
+        let (mut stream, _) = listener.accept().unwrap();
+        serve(&mut stream, exchanges);
+        stream
+    });
+
+    (address, server)
+}
+
+/// Spawns a server listening on a Unix domain socket at `path` that behaves like [`spawn_server_replying`].
+#[cfg(unix)]
+pub(crate) fn spawn_unix_server_replying(
+    path: &std::path::Path,
+    exchanges: Vec<(usize, &'static [u8])>,
+) -> JoinHandle<UnixStream> {
+    let listener = UnixListener::bind(path).unwrap();
+    thread::spawn(move || {
+        let (mut stream, _) = listener.accept().unwrap();
+        serve(&mut stream, exchanges);
+        stream
+    })
+}
+
+fn serve<S: Read + Write>(stream: &mut S, exchanges: Vec<(usize, &'static [u8])>) {
+    stream.write_all(b"BaseX:19501915960728\0").unwrap();
+    let mut auth_string = [0u8; "admin\0af13b20af0e0b0e3517a406c42622d3d\0".len()];
+    stream.read_exact(&mut auth_string).unwrap();
+    stream.write_all(&[0]).unwrap();
+
+    for (terminators, reply) in exchanges {
+        let mut byte = [0u8; 1];
+        let mut remaining = terminators;
+        while remaining > 0 {
+            stream.read_exact(&mut byte).unwrap();
+            if byte[0] == 0 {
+                remaining -= 1;
+            }
+        }
+        stream.write_all(reply).unwrap();
+    }
+}
 
 #[derive(Debug)]
 pub(crate) struct MockStream {
     buffer: Rc<RefCell<Vec<u8>>>,
     response: CircBuf,
+    broken: bool,
 }
 
 impl MockStream {
     pub(crate) fn from_bytes(response: &[u8]) -> Self {
         let mut buffer = CircBuf::with_capacity(response.len() + 1).unwrap();
         buffer.write_all(response).unwrap();
-        buffer.write(&[0]).unwrap();
+        let _ = buffer.write(&[0]).unwrap();
 
         Self {
             buffer: Rc::new(RefCell::new(vec![])),
             response: buffer,
+            broken: false,
+        }
+    }
+
+    /// Creates a stream whose writes fail with [`BrokenPipe`], like a connection closed by the server.
+    ///
+    /// [`BrokenPipe`]: std::io::ErrorKind::BrokenPipe
+    pub(crate) fn broken() -> Self {
+        Self {
+            broken: true,
+            ..Self::from_bytes(&[])
         }
     }
 
     pub(crate) fn new(response: String) -> Self {
         Self::from_bytes(response.as_bytes())
     }
+
+    pub(crate) fn written(&self) -> Vec<u8> {
+        self.buffer.borrow().clone()
+    }
 }
 
-impl ToString for MockStream {
-    fn to_string(&self) -> String {
-        String::from_utf8(self.buffer.borrow().clone()).unwrap()
+impl Display for MockStream {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&String::from_utf8(self.buffer.borrow().clone()).unwrap())
     }
 }
 
@@ -41,6 +124,9 @@ impl Read for MockStream {
 
 impl Write for MockStream {
     fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        if self.broken {
+            return Err(std::io::ErrorKind::BrokenPipe.into());
+        }
         let bytes_written = buf.len();
         self.buffer.borrow_mut().extend(buf);
         Ok(bytes_written)
@@ -59,6 +145,7 @@ impl DatabaseStream for MockStream {
         Ok(MockStream {
             buffer: Rc::clone(&self.buffer),
             response: cloned_buff,
+            broken: self.broken,
         })
     }
 }
@@ -68,13 +155,13 @@ pub(crate) struct FailingStream;
 
 impl Read for FailingStream {
     fn read(&mut self, _buf: &mut [u8]) -> std::io::Result<usize> {
-        Err(std::io::Error::new(std::io::ErrorKind::Other, ""))
+        Err(std::io::Error::other(""))
     }
 }
 
 impl Write for FailingStream {
     fn write(&mut self, _buf: &[u8]) -> std::io::Result<usize> {
-        Err(std::io::Error::new(std::io::ErrorKind::Other, ""))
+        Err(std::io::Error::other(""))
     }
 
     fn flush(&mut self) -> std::io::Result<()> {
@@ -87,3 +174,31 @@ impl DatabaseStream for FailingStream {
         unimplemented!()
     }
 }
+
+/// Reads the `bytes` in chunks of the given `sizes`, cycling through them, to simulate a stream that delivers data
+/// in small pieces.
+#[derive(Debug)]
+pub(crate) struct ChunkedReader<'a> {
+    bytes: &'a [u8],
+    sizes: std::iter::Cycle<std::slice::Iter<'a, usize>>,
+}
+
+impl<'a> ChunkedReader<'a> {
+    pub(crate) fn new(bytes: &'a [u8], sizes: &'a [usize]) -> Self {
+        Self {
+            bytes,
+            sizes: sizes.iter().cycle(),
+        }
+    }
+}
+
+impl Read for ChunkedReader<'_> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        let size = (*self.sizes.next().unwrap()).min(buf.len()).min(self.bytes.len());
+        let (chunk, rest) = self.bytes.split_at(size);
+        buf[..size].copy_from_slice(chunk);
+        self.bytes = rest;
+
+        Ok(size)
+    }
+}
@@ -0,0 +1,165 @@
+use crate::{DatabaseStream, Result};
+use rustls::pki_types::ServerName;
+use rustls::{ClientConfig, ClientConnection, RootCertStore, StreamOwned};
+use std::convert::TryFrom;
+use std::io::{Error, ErrorKind, Read, Write};
+use std::net::TcpStream;
+use std::sync::{Arc, Mutex, MutexGuard};
+
+/// TLS encrypted stream usable for BaseX database [`Connection`], e.g. when the server is fronted by a TLS terminator.
+///
+/// A TLS session cannot be duplicated, so instances created by [`try_clone`] share the same session and take turns
+/// reading and writing it.
+///
+/// # Example
+/// ```no_run
+/// # use basex::{Client, ClientError, Connection, TlsStream};
+/// # use std::net::TcpStream;
+/// # fn main() -> Result<(), ClientError> {
+/// let stream = TcpStream::connect("basex.example.com:1985")?;
+/// let stream = TlsStream::connect(stream, "basex.example.com")?;
+/// let connection = Connection::new(stream).authenticate("admin", "admin")?;
+///
+/// let client = Client::new(connection);
+/// # Ok(())
+/// # }
+/// ```
+///
+/// [`Connection`]: crate::connection::Connection
+/// [`try_clone`]: crate::DatabaseStream::try_clone
+#[derive(Debug)]
+pub struct TlsStream {
+    session: Arc<Mutex<StreamOwned<ClientConnection, TcpStream>>>,
+}
+
+impl TlsStream {
+    /// Performs the TLS handshake over the `stream`, verifying the certificate of `server_name` against the Mozilla
+    /// root certificates.
+    pub fn connect(stream: TcpStream, server_name: &str) -> Result<Self> {
+        let roots = RootCertStore {
+            roots: webpki_roots::TLS_SERVER_ROOTS.to_vec(),
+        };
+        let config = ClientConfig::builder_with_provider(Arc::new(rustls::crypto::ring::default_provider()))
+            .with_safe_default_protocol_versions()
+            .map_err(|e| Error::new(ErrorKind::InvalidInput, e))?
+            .with_root_certificates(roots)
+            .with_no_client_auth();
+
+        Self::connect_with_config(stream, server_name, Arc::new(config))
+    }
+
+    /// Performs the TLS handshake over the `stream` using a custom `config`, e.g. to trust a self-signed certificate.
+    pub fn connect_with_config(mut stream: TcpStream, server_name: &str, config: Arc<ClientConfig>) -> Result<Self> {
+        let server_name =
+            ServerName::try_from(server_name.to_owned()).map_err(|e| Error::new(ErrorKind::InvalidInput, e))?;
+        let mut session =
+            ClientConnection::new(config, server_name).map_err(|e| Error::new(ErrorKind::InvalidInput, e))?;
+
+        while session.is_handshaking() {
+            session.complete_io(&mut stream)?;
+        }
+
+        Ok(Self::new(StreamOwned::new(session, stream)))
+    }
+
+    fn new(session: StreamOwned<ClientConnection, TcpStream>) -> Self {
+        Self {
+            session: Arc::new(Mutex::new(session)),
+        }
+    }
+
+    fn session(&self) -> std::io::Result<MutexGuard<'_, StreamOwned<ClientConnection, TcpStream>>> {
+        self.session.lock().map_err(|_| Error::other("TLS session is poisoned"))
+    }
+}
+
+impl Read for TlsStream {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        self.session()?.read(buf)
+    }
+}
+
+impl Write for TlsStream {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.session()?.write(buf)
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        self.session()?.flush()
+    }
+}
+
+impl DatabaseStream for TlsStream {
+    fn try_clone(&self) -> Result<Self> {
+        Ok(Self {
+            session: Arc::clone(&self.session),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ClientError;
+    use std::net::TcpListener;
+    use std::thread;
+
+    fn config() -> Arc<ClientConfig> {
+        let config = ClientConfig::builder_with_provider(Arc::new(rustls::crypto::ring::default_provider()))
+            .with_safe_default_protocol_versions()
+            .unwrap()
+            .with_root_certificates(RootCertStore::empty())
+            .with_no_client_auth();
+
+        Arc::new(config)
+    }
+
+    #[test]
+    fn test_handshake_fails_with_plain_server() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = listener.local_addr().unwrap();
+        let server = thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            let _ = stream.write_all(b"BaseX:19501915960728\0");
+        });
+
+        let stream = TcpStream::connect(address).unwrap();
+        let actual_error =
+            TlsStream::connect_with_config(stream, "localhost", config()).expect_err("Operation must fail");
+        server.join().unwrap();
+
+        assert!(matches!(actual_error, ClientError::Io(_)));
+    }
+
+    #[test]
+    fn test_handshake_fails_with_invalid_server_name() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let stream = TcpStream::connect(listener.local_addr().unwrap()).unwrap();
+
+        let actual_error =
+            TlsStream::connect_with_config(stream, "not a name", config()).expect_err("Operation must fail");
+
+        assert!(matches!(actual_error, ClientError::Io(e) if e.kind() == ErrorKind::InvalidInput));
+    }
+
+    #[test]
+    fn test_cloning_shares_session() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let stream = TcpStream::connect(listener.local_addr().unwrap()).unwrap();
+        let session = ClientConnection::new(config(), ServerName::try_from("localhost").unwrap()).unwrap();
+
+        let tls_stream = TlsStream::new(StreamOwned::new(session, stream));
+        let cloned_stream = tls_stream.try_clone().unwrap();
+
+        assert!(Arc::ptr_eq(&tls_stream.session, &cloned_stream.session));
+    }
+
+    #[test]
+    fn test_formats_as_debug() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let stream = TcpStream::connect(listener.local_addr().unwrap()).unwrap();
+        let session = ClientConnection::new(config(), ServerName::try_from("localhost").unwrap()).unwrap();
+
+        let _ = format!("{:?}", TlsStream::new(StreamOwned::new(session, stream)));
+    }
+}
@@ -1,3 +1,5 @@
+#![allow(dead_code)]
+
 use rust_embed::RustEmbed;
 
 #[derive(RustEmbed)]
@@ -0,0 +1,19 @@
+mod common;
+
+use basex::{Client, ClientError};
+
+#[test]
+fn test_database_opens_after_creation() -> Result<(), ClientError> {
+    let mut client = Client::connect("localhost", 1984, "admin", "admin")?;
+
+    let info = client.create("c3f0b1e")?.without_input()?;
+    assert!(info.starts_with("Database 'c3f0b1e' created"));
+
+    let info = client.open("c3f0b1e")?;
+    assert!(info.starts_with("Database 'c3f0b1e' was opened"), "actual: {}", info);
+
+    let actual_error = client.open("c3f0b1e_missing").expect_err("Operation must fail");
+    assert!(matches!(actual_error, ClientError::CommandFailed { .. }));
+
+    Ok(())
+}
@@ -1,4 +1,5 @@
-use basex;
+#![allow(clippy::unused_unit)]
+
 use basex::{Client, ClientError, ToQueryArgument};
 use std::io::Read;
 use std::net::IpAddr;
@@ -19,6 +20,10 @@ use test_case::test_case;
 #[test_case(5.5f32, "5.5", "xs:float")]
 #[test_case(5.5f64, "5.5", "xs:double")]
 #[test_case(&5.2f64, "5.2", "xs:double")]
+#[test_case(f32::INFINITY, "INF", "xs:float")]
+#[test_case(f64::INFINITY, "INF", "xs:double")]
+#[test_case(f64::NEG_INFINITY, "-INF", "xs:double")]
+#[test_case(f64::NAN, "NaN", "xs:double")]
 #[test_case(Some(true), "true", "xs:boolean")]
 fn test_query_binds_variables<'a, T: 'a + ToQueryArgument<'a>>(
     value: T,
@@ -1,6 +1,5 @@
 mod common;
 
-use basex;
 use basex::{Client, ClientError};
 use common::Asset;
 use std::io::Read;
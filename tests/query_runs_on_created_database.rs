@@ -1,4 +1,3 @@
-use basex;
 use basex::{Client, ClientError};
 use std::io::Read;
 
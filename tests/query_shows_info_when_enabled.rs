@@ -1,4 +1,3 @@
-use basex;
 use basex::compiler::Info;
 use basex::{Client, ClientError};
 
@@ -20,10 +19,7 @@ fn test_query_shows_info_when_enabled() -> Result<(), ClientError> {
     query.close()?;
 
     println!("{}", actual_info);
-    assert_eq!(
-        Some(database_name),
-        actual_info.read_locking().as_ref().map(|v| v.as_str())
-    );
+    assert_eq!(Some(database_name), actual_info.read_locking().as_deref());
     assert_eq!(None, actual_info.write_locking());
     assert_eq!(xquery, actual_info.query());
     Ok(())
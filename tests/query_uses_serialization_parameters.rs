@@ -1,4 +1,3 @@
-use basex;
 use basex::serializer::Options;
 use basex::{Client, ClientError};
 use std::str::FromStr;
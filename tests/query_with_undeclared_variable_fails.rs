@@ -1,6 +1,5 @@
 mod common;
 
-use basex;
 use basex::{Client, ClientError};
 
 #[test]
@@ -16,7 +15,7 @@ fn test_query_with_undeclared_variable_fails() -> Result<(), ClientError> {
         assert_eq!("Undeclared variable: $x.", q.message());
         assert_eq!(1, q.line());
         assert_eq!(1, q.position());
-        assert_eq!(".", q.file());
+        assert_eq!(None, q.file());
         assert_eq!("XPST0008", q.code());
     }
     Ok(())
@@ -1,6 +1,5 @@
 mod common;
 
-use basex;
 use basex::{Client, ClientError};
 use std::io::Read;
 